@@ -0,0 +1,62 @@
+//! Converts the artwork in "/symbols" into Rust constants, so that the SVG
+//! files don't have to be parsed when drawing symbols.
+
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use svg::{
+    node::{Value, element::path::Data, element::tag::Type},
+    parser::Event,
+};
+
+fn main() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("symbols");
+    println!("cargo::rerun-if-changed={}", root.display());
+
+    let mut files = svg_files(&root);
+    files.extend(svg_files(&root.join("numbers")));
+    files.sort();
+
+    let mut out = String::new();
+    for file in files {
+        let name = file.strip_prefix(&root).unwrap().with_extension("");
+        let name = name.to_str().unwrap().replace(['/', '\\'], "_").to_uppercase();
+        let content = fs::read_to_string(&file).unwrap();
+        writeln!(out, "pub(crate) const {name}: &[&str] = &[").unwrap();
+        for data in paths(&content) {
+            writeln!(out, "    {data:?},").unwrap();
+        }
+        writeln!(out, "];").unwrap();
+    }
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("symbol_paths.rs"), out).unwrap();
+}
+
+fn svg_files(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "svg"))
+        .collect()
+}
+
+/// The data of every path in `content`, written the same way as the `svg`
+/// crate writes it.
+fn paths(content: &str) -> Vec<String> {
+    svg::read(content)
+        .unwrap()
+        .filter_map(|event| {
+            if let Event::Tag("path", Type::Empty | Type::Start, attributes) = event {
+                let data = Data::parse(attributes.get("d").unwrap()).unwrap();
+                Some(Value::from(data).to_string())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
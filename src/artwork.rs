@@ -0,0 +1,173 @@
+use std::{fs, io, path::Path};
+
+use svg::node::element::SVG;
+
+use crate::{
+    Color, Fill, Foil, Glyph, Palette, Plate, Theme, color::ALL_COLORS, symbols::parse_artwork,
+};
+
+/// Artwork of glyphs loaded at runtime, which is drawn instead of the built-in
+/// artwork. Circles are filled using [`Artwork::palette`].
+///
+/// Use it as a [`Theme`], e.g. with
+/// [`Mana::as_svg_with_theme`][crate::Mana::as_svg_with_theme].
+///
+/// # Example
+///
+/// ```no_run
+/// use mana_symbols::{Artwork, Mana, SVGConfig};
+///
+/// let artwork = Artwork::load("my_symbols").unwrap();
+/// let mana: Mana = "U".parse().unwrap();
+/// let svg = mana.as_svg_with_theme(&SVGConfig::default(), &artwork);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Artwork {
+    /// The fill colors of the circles.
+    pub palette: Palette,
+
+    glyphs: Vec<(Glyph, SVG)>,
+}
+
+impl Artwork {
+    /// Load artwork from the SVG files in `dir`, which are named like the
+    /// built-in artwork: `w.svg`, `u.svg`, `b.svg`, `r.svg`, `g.svg` for the
+    /// colors, `c.svg`, `p.svg` and `s.svg` for colorless, phyrexian and snow
+    /// mana, `x.svg`, `y.svg` and `z.svg` for variables, `infinity.svg` for
+    /// `{∞}`, `t.svg`, `q.svg`, `e.svg` and `tk.svg` for the tap, untap,
+    /// energy and ticket symbols, `chaos.svg` for the chaos symbol and
+    /// `numbers/0.svg`, `numbers/1.svg`, etc. for numbers.
+    ///
+    /// Missing files are drawn using the built-in artwork. Only the `viewBox`
+    /// and `<path>` elements of each file are used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file could not be read, or if it is not a valid
+    /// SVG.
+    pub fn load(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let dir = dir.as_ref();
+        let mut artwork = Self::default();
+
+        let colors = ALL_COLORS.map(|color| (Glyph::Color(color), color_file(color)));
+        let others = [
+            (Glyph::Colorless, "c.svg"),
+            (Glyph::Phyrexian, "p.svg"),
+            (Glyph::Snow, "s.svg"),
+            (Glyph::Variable('X'), "x.svg"),
+            (Glyph::Variable('Y'), "y.svg"),
+            (Glyph::Variable('Z'), "z.svg"),
+            (Glyph::Infinity, "infinity.svg"),
+            (Glyph::Tap, "t.svg"),
+            (Glyph::Untap, "q.svg"),
+            (Glyph::Energy, "e.svg"),
+            (Glyph::Ticket, "tk.svg"),
+            (Glyph::Chaos, "chaos.svg"),
+        ];
+        for (glyph, file) in colors.into_iter().chain(others) {
+            let path = dir.join(file);
+            if path.is_file() {
+                artwork.insert_file(glyph, &path)?;
+            }
+        }
+
+        let numbers = dir.join("numbers");
+        if numbers.is_dir() {
+            for entry in fs::read_dir(numbers)? {
+                let path = entry?.path();
+                let number = path
+                    .file_name()
+                    .and_then(|x| x.to_str())
+                    .and_then(|x| x.strip_suffix(".svg"))
+                    .and_then(|x| x.parse().ok());
+                if let Some(number) = number {
+                    artwork.insert_file(Glyph::Number(number), &path)?;
+                }
+            }
+        }
+
+        Ok(artwork)
+    }
+
+    /// Use the SVG `content` as the artwork of `glyph`. Returns `false`, and
+    /// does nothing, if `content` is not a valid SVG.
+    pub fn insert(&mut self, glyph: Glyph, content: &str) -> bool {
+        let Some(svg) = parse_artwork(content) else {
+            return false;
+        };
+        self.glyphs.retain(|(x, _)| *x != glyph);
+        self.glyphs.push((glyph, svg));
+        true
+    }
+
+    fn insert_file(&mut self, glyph: Glyph, path: &Path) -> io::Result<()> {
+        let content = fs::read_to_string(path)?;
+        if self.insert(glyph, &content) {
+            Ok(())
+        } else {
+            let message = format!("invalid SVG: {}", path.display());
+            Err(io::Error::new(io::ErrorKind::InvalidData, message))
+        }
+    }
+}
+
+const fn color_file(color: Color) -> &'static str {
+    match color {
+        Color::White => "w.svg",
+        Color::Blue => "u.svg",
+        Color::Black => "b.svg",
+        Color::Red => "r.svg",
+        Color::Green => "g.svg",
+    }
+}
+
+impl Theme for Artwork {
+    fn circle_fill(&self, fill: Fill) -> String {
+        self.palette.circle_fill(fill)
+    }
+
+    fn stroke(&self) -> Option<(String, f64)> {
+        self.palette.stroke()
+    }
+
+    fn foil(&self) -> Option<Foil> {
+        self.palette.foil()
+    }
+
+    fn plate(&self) -> Option<Plate> {
+        self.palette.plate()
+    }
+
+    fn glyph(&self, glyph: Glyph) -> Option<SVG> {
+        self.glyphs.iter().find(|(x, _)| *x == glyph).map(|(_, svg)| svg.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Mana, SVGConfig};
+
+    #[test]
+    fn load() {
+        let dir = std::env::temp_dir().join(format!("mana_symbols_artwork_{}", std::process::id()));
+        fs::create_dir_all(dir.join("numbers")).unwrap();
+        let square = r#"<svg viewBox="0 0 10 10"><path fill="red" d="M0 0h10v10h-10z"/></svg>"#;
+        fs::write(dir.join("u.svg"), square).unwrap();
+        fs::write(dir.join("numbers/42.svg"), square).unwrap();
+
+        let artwork = Artwork::load(&dir).unwrap();
+        assert!(artwork.glyph(Glyph::Color(Color::Blue)).is_some());
+        assert!(artwork.glyph(Glyph::Number(42)).is_some());
+        assert!(artwork.glyph(Glyph::Color(Color::Red)).is_none());
+
+        let mana: Mana = "42".parse().unwrap();
+        let svg = mana.as_svg_with_theme(&SVGConfig::default(), &artwork).to_string();
+        assert!(svg.contains(r#"viewBox="0 0 10 10""#));
+
+        fs::write(dir.join("r.svg"), "<svg><path d=\"not a path\"/></svg>").unwrap();
+        assert!(Artwork::load(&dir).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{Mana, SVGConfig, canonical_svg, export::file_stem};
+
+/// Writes the SVG of each mana symbol to a file in a directory the first time
+/// it is used, so that HTML can refer to the file instead of containing the
+/// SVG. Used by [`Mana::write_html_with_assets`] and
+/// [`Manas::write_html_with_assets`][crate::Manas::write_html_with_assets].
+///
+/// Files are named like the files of
+/// [`export_symbols`][crate::export_symbols], e.g. `u.svg` or `2_w.svg`, and
+/// are overwritten the first time they are used, so that they always match
+/// [`AssetWriter::config`].
+///
+/// ```no_run
+/// use mana_symbols::{AssetWriter, HtmlOptions, Manas, SVGConfig};
+///
+/// let mut assets = AssetWriter::new("static/mana", "/mana", SVGConfig::default());
+/// let manas: Manas = "{2}{U}".parse().unwrap();
+/// let html = manas.as_html_with_assets(&HtmlOptions::default(), &mut assets).unwrap();
+/// assert!(html.contains(r#"src="/mana/u.svg""#));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AssetWriter {
+    dir: PathBuf,
+    url: String,
+    config: SVGConfig,
+    written: Vec<Mana>,
+}
+
+impl AssetWriter {
+    /// Write files to `dir`, which is created if it does not exist, where
+    /// `url` is the URL of `dir` used in HTML (e.g. `/static/mana`). If `url`
+    /// is empty, the URLs are relative to the page.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>, url: impl Into<String>, config: SVGConfig) -> Self {
+        Self { dir: dir.into(), url: url.into(), config, written: Vec::new() }
+    }
+
+    /// The directory which files are written to.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The configuration of every SVG.
+    #[must_use]
+    pub const fn config(&self) -> &SVGConfig {
+        &self.config
+    }
+
+    /// Write the SVG of `mana` unless it has already been written, and return
+    /// its URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory could not be created or the file
+    /// could not be written.
+    pub fn asset(&mut self, mana: &Mana) -> io::Result<String> {
+        let name = format!("{}.svg", file_stem(*mana));
+        if !self.written.contains(mana) {
+            fs::create_dir_all(&self.dir)?;
+            fs::write(self.dir.join(&name), canonical_svg(&mana.as_svg(&self.config)))?;
+            self.written.push(*mana);
+        }
+
+        let url = self.url.trim_end_matches('/');
+        Ok(if self.url.is_empty() { name } else { format!("{url}/{name}") })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HtmlOptions, Manas};
+
+    #[test]
+    fn writes_once() {
+        let dir = std::env::temp_dir().join(format!("mana_symbols_assets_{}", std::process::id()));
+        let mut assets = AssetWriter::new(&dir, "/mana/", SVGConfig::default());
+
+        let manas: Manas = "{U}{2/W}{U}".parse().unwrap();
+        let html = manas.as_html_with_assets(&HtmlOptions::default(), &mut assets).unwrap();
+        assert!(html.contains(r#"title="Blue mana" src="/mana/u.svg">"#));
+        assert!(html.contains(r#"src="/mana/2_w.svg">"#));
+        assert_eq!(assets.written.len(), 2);
+
+        let svg = fs::read_to_string(dir.join("u.svg")).unwrap();
+        assert!(svg.starts_with("<svg"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
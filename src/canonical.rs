@@ -0,0 +1,85 @@
+use std::fmt::{self, Write};
+
+use svg::node::{Node, element::SVG};
+
+/// Write `svg` to `output` in a canonical form which does not depend on the
+/// version of the [`svg`] crate, so that the output can be compared byte for
+/// byte, e.g. in caches keyed by their content.
+///
+/// - Every element starts on a new line, except the first one.
+/// - Attributes are written in increasing order of their names (comparing
+///   bytes), as `name="value"`.
+/// - `&`, `<` and `>` are escaped in text, and `"` and `'` are also escaped in
+///   attribute values.
+/// - Elements without children are written as `<name/>`, and text is written
+///   directly after the start tag without a new line.
+///
+/// ```
+/// use mana_symbols::{Mana, SVGConfig, write_canonical_svg};
+///
+/// let mana: Mana = "U".parse().unwrap();
+/// let mut svg = String::new();
+/// write_canonical_svg(&mut svg, &mana.as_svg(&SVGConfig::default())).unwrap();
+/// assert!(svg.starts_with(r#"<svg aria-label="Blue mana" role="img" viewBox="#));
+/// ```
+pub fn write_canonical_svg<W: Write>(output: &mut W, svg: &SVG) -> fmt::Result {
+    write_node(output, svg)
+}
+
+/// Returns `svg` in the canonical form described in [`write_canonical_svg`].
+#[must_use]
+pub fn canonical_svg(svg: &SVG) -> String {
+    let mut out = String::new();
+    write_canonical_svg(&mut out, svg).unwrap();
+    out
+}
+
+fn write_node<W: Write>(output: &mut W, node: &dyn Node) -> fmt::Result {
+    // Only elements have attributes, so anything else is text. The content of
+    // text nodes is not accessible, but they are always displayed escaped.
+    let Some(attributes) = node.get_attributes() else {
+        return write!(output, "{node}");
+    };
+
+    let name = node.get_name();
+    write!(output, "<{name}")?;
+    let mut attributes: Vec<_> = attributes.iter().collect();
+    attributes.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    for (name, value) in attributes {
+        write!(output, r#" {name}=""#)?;
+        write_escaped(output, value)?;
+        write!(output, "\"")?;
+    }
+
+    let children = node.get_children().map_or(&[][..], Vec::as_slice);
+    if children.is_empty() {
+        return write!(output, "/>");
+    }
+    write!(output, ">")?;
+    let mut text = false;
+    for child in children {
+        text = child.get_attributes().is_none();
+        if !text {
+            writeln!(output)?;
+        }
+        write_node(output, child.as_ref())?;
+    }
+    if !text {
+        writeln!(output)?;
+    }
+    write!(output, "</{name}>")
+}
+
+fn write_escaped<W: Write>(output: &mut W, value: &str) -> fmt::Result {
+    for c in value.chars() {
+        match c {
+            '&' => output.write_str("&amp;")?,
+            '<' => output.write_str("&lt;")?,
+            '>' => output.write_str("&gt;")?,
+            '"' => output.write_str("&quot;")?,
+            '\'' => output.write_str("&apos;")?,
+            c => output.write_char(c)?,
+        }
+    }
+    Ok(())
+}
@@ -3,7 +3,7 @@ use std::fmt::{Display, Write};
 use nom::{IResult, Parser, branch::alt, character::complete::char, combinator::value};
 
 /// One of the five [colors](https://mtg.wiki/page/Color) of the color pie
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Color {
     /// [White](https://mtg.wiki/page/White) (W)
     White = 0,
@@ -51,6 +51,32 @@ impl Color {
         }
     }
 
+    /// The two colors adjacent to this color on the
+    /// [color wheel](https://mtg.wiki/page/Color_wheel).
+    ///
+    /// ```
+    /// use mana_symbols::Color;
+    ///
+    /// assert_eq!(Color::White.allies(), [Color::Blue, Color::Green]);
+    /// ```
+    #[must_use]
+    pub const fn allies(self) -> [Self; 2] {
+        [self.next(1), self.next(4)]
+    }
+
+    /// The two colors opposite to this color on the
+    /// [color wheel](https://mtg.wiki/page/Color_wheel).
+    ///
+    /// ```
+    /// use mana_symbols::Color;
+    ///
+    /// assert_eq!(Color::White.enemies(), [Color::Black, Color::Red]);
+    /// ```
+    #[must_use]
+    pub const fn enemies(self) -> [Self; 2] {
+        [self.next(2), self.next(3)]
+    }
+
     #[must_use]
     pub(crate) const fn next(self, i: usize) -> Self {
         Self::from_usize((self as usize).wrapping_add(i))
@@ -72,6 +98,20 @@ impl Color {
         }
     }
 
+    /// The color used when drawing mana symbols (see [`Color::hex`]) as red,
+    /// green and blue values.
+    ///
+    /// ```
+    /// use mana_symbols::Color;
+    ///
+    /// assert_eq!(Color::Blue.hex(), "#aae0fa");
+    /// assert_eq!(Color::Blue.rgb(), (0xaa, 0xe0, 0xfa));
+    /// ```
+    #[must_use]
+    pub const fn rgb(self) -> (u8, u8, u8) {
+        hex_to_rgb(self.hex())
+    }
+
     pub(crate) const fn name(self) -> &'static str {
         match self {
             Self::White => "white",
@@ -93,6 +133,26 @@ impl Color {
     }
 }
 
+/// Parse a color written as `#rrggbb`.
+pub(crate) const fn hex_to_rgb(hex: &str) -> (u8, u8, u8) {
+    const fn digit(c: u8) -> u8 {
+        match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            b'A'..=b'F' => c - b'A' + 10,
+            _ => panic!("invalid hex digit"),
+        }
+    }
+
+    let bytes = hex.as_bytes();
+    assert!(bytes.len() == 7 && bytes[0] == b'#');
+    (
+        digit(bytes[1]) * 16 + digit(bytes[2]),
+        digit(bytes[3]) * 16 + digit(bytes[4]),
+        digit(bytes[5]) * 16 + digit(bytes[6]),
+    )
+}
+
 // Colors of the five main colors
 pub const HEX_W: &str = "#fffbd5";
 pub const HEX_U: &str = "#aae0fa";
@@ -0,0 +1,296 @@
+use std::fmt::Display;
+
+use crate::{Color, ColorSet};
+
+/// A pair of two colors, named after the [guilds](https://mtg.wiki/page/Guild) of Ravnica.
+///
+/// ```
+/// use mana_symbols::{Color, ColorSet, Guild};
+///
+/// let colors: ColorSet = Guild::Izzet.into();
+/// assert_eq!(colors.to_string(), "UR");
+/// assert_eq!(Guild::try_from(colors), Ok(Guild::Izzet));
+/// assert_eq!(Guild::Izzet.colors(), [Color::Blue, Color::Red]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Guild {
+    /// WU
+    Azorius,
+    /// UB
+    Dimir,
+    /// BR
+    Rakdos,
+    /// RG
+    Gruul,
+    /// GW
+    Selesnya,
+    /// WB
+    Orzhov,
+    /// UR
+    Izzet,
+    /// BG
+    Golgari,
+    /// RW
+    Boros,
+    /// GU
+    Simic,
+}
+
+impl Guild {
+    /// Every guild.
+    pub const ALL: [Self; 10] = [
+        Self::Azorius,
+        Self::Dimir,
+        Self::Rakdos,
+        Self::Gruul,
+        Self::Selesnya,
+        Self::Orzhov,
+        Self::Izzet,
+        Self::Golgari,
+        Self::Boros,
+        Self::Simic,
+    ];
+
+    /// The colors, in the order they are usually written.
+    #[must_use]
+    pub const fn colors(self) -> [Color; 2] {
+        match self {
+            Self::Azorius => [Color::White, Color::Blue],
+            Self::Dimir => [Color::Blue, Color::Black],
+            Self::Rakdos => [Color::Black, Color::Red],
+            Self::Gruul => [Color::Red, Color::Green],
+            Self::Selesnya => [Color::Green, Color::White],
+            Self::Orzhov => [Color::White, Color::Black],
+            Self::Izzet => [Color::Blue, Color::Red],
+            Self::Golgari => [Color::Black, Color::Green],
+            Self::Boros => [Color::Red, Color::White],
+            Self::Simic => [Color::Green, Color::Blue],
+        }
+    }
+
+    /// The name, e.g. `"Azorius"`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Azorius => "Azorius",
+            Self::Dimir => "Dimir",
+            Self::Rakdos => "Rakdos",
+            Self::Gruul => "Gruul",
+            Self::Selesnya => "Selesnya",
+            Self::Orzhov => "Orzhov",
+            Self::Izzet => "Izzet",
+            Self::Golgari => "Golgari",
+            Self::Boros => "Boros",
+            Self::Simic => "Simic",
+        }
+    }
+}
+
+impl Display for Guild {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl From<Guild> for ColorSet {
+    fn from(value: Guild) -> Self {
+        value.colors().into_iter().collect()
+    }
+}
+
+impl TryFrom<ColorSet> for Guild {
+    type Error = ();
+
+    fn try_from(value: ColorSet) -> Result<Self, Self::Error> {
+        Self::ALL.into_iter().find(|x| ColorSet::from(*x) == value).ok_or(())
+    }
+}
+
+impl TryFrom<&[Color]> for Guild {
+    type Error = ();
+
+    /// Colors may be repeated and be in any order.
+    fn try_from(value: &[Color]) -> Result<Self, Self::Error> {
+        value.iter().copied().collect::<ColorSet>().try_into()
+    }
+}
+
+/// Three colors where one color is allied with the other two, named after the
+/// [shards](https://mtg.wiki/page/Shard) of Alara.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shard {
+    /// GWU
+    Bant,
+    /// WUB
+    Esper,
+    /// UBR
+    Grixis,
+    /// BRG
+    Jund,
+    /// RGW
+    Naya,
+}
+
+impl Shard {
+    /// Every shard.
+    pub const ALL: [Self; 5] = [Self::Bant, Self::Esper, Self::Grixis, Self::Jund, Self::Naya];
+
+    /// The colors, in the order they are usually written.
+    #[must_use]
+    pub const fn colors(self) -> [Color; 3] {
+        match self {
+            Self::Bant => [Color::Green, Color::White, Color::Blue],
+            Self::Esper => [Color::White, Color::Blue, Color::Black],
+            Self::Grixis => [Color::Blue, Color::Black, Color::Red],
+            Self::Jund => [Color::Black, Color::Red, Color::Green],
+            Self::Naya => [Color::Red, Color::Green, Color::White],
+        }
+    }
+
+    /// The name, e.g. `"Bant"`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Bant => "Bant",
+            Self::Esper => "Esper",
+            Self::Grixis => "Grixis",
+            Self::Jund => "Jund",
+            Self::Naya => "Naya",
+        }
+    }
+}
+
+impl Display for Shard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl From<Shard> for ColorSet {
+    fn from(value: Shard) -> Self {
+        value.colors().into_iter().collect()
+    }
+}
+
+impl TryFrom<ColorSet> for Shard {
+    type Error = ();
+
+    fn try_from(value: ColorSet) -> Result<Self, Self::Error> {
+        Self::ALL.into_iter().find(|x| ColorSet::from(*x) == value).ok_or(())
+    }
+}
+
+impl TryFrom<&[Color]> for Shard {
+    type Error = ();
+
+    /// Colors may be repeated and be in any order.
+    fn try_from(value: &[Color]) -> Result<Self, Self::Error> {
+        value.iter().copied().collect::<ColorSet>().try_into()
+    }
+}
+
+/// Three colors where one color is the enemy of the other two, named after
+/// the [wedges](https://mtg.wiki/page/Wedge) (clans of Tarkir).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Wedge {
+    /// WBG
+    Abzan,
+    /// URW
+    Jeskai,
+    /// BGU
+    Sultai,
+    /// RWB
+    Mardu,
+    /// GUR
+    Temur,
+}
+
+impl Wedge {
+    /// Every wedge.
+    pub const ALL: [Self; 5] = [Self::Abzan, Self::Jeskai, Self::Sultai, Self::Mardu, Self::Temur];
+
+    /// The colors, in the order they are usually written.
+    #[must_use]
+    pub const fn colors(self) -> [Color; 3] {
+        match self {
+            Self::Abzan => [Color::White, Color::Black, Color::Green],
+            Self::Jeskai => [Color::Blue, Color::Red, Color::White],
+            Self::Sultai => [Color::Black, Color::Green, Color::Blue],
+            Self::Mardu => [Color::Red, Color::White, Color::Black],
+            Self::Temur => [Color::Green, Color::Blue, Color::Red],
+        }
+    }
+
+    /// The name, e.g. `"Abzan"`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Abzan => "Abzan",
+            Self::Jeskai => "Jeskai",
+            Self::Sultai => "Sultai",
+            Self::Mardu => "Mardu",
+            Self::Temur => "Temur",
+        }
+    }
+}
+
+impl Display for Wedge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl From<Wedge> for ColorSet {
+    fn from(value: Wedge) -> Self {
+        value.colors().into_iter().collect()
+    }
+}
+
+impl TryFrom<ColorSet> for Wedge {
+    type Error = ();
+
+    fn try_from(value: ColorSet) -> Result<Self, Self::Error> {
+        Self::ALL.into_iter().find(|x| ColorSet::from(*x) == value).ok_or(())
+    }
+}
+
+impl TryFrom<&[Color]> for Wedge {
+    type Error = ();
+
+    /// Colors may be repeated and be in any order.
+    fn try_from(value: &[Color]) -> Result<Self, Self::Error> {
+        value.iter().copied().collect::<ColorSet>().try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_order() {
+        for guild in Guild::ALL {
+            let colors: Vec<Color> = ColorSet::from(guild).iter().collect();
+            assert_eq!(colors, guild.colors());
+        }
+        for shard in Shard::ALL {
+            let colors: Vec<Color> = ColorSet::from(shard).iter().collect();
+            assert_eq!(colors, shard.colors());
+        }
+        for wedge in Wedge::ALL {
+            let colors: Vec<Color> = ColorSet::from(wedge).iter().collect();
+            assert_eq!(colors, wedge.colors());
+        }
+    }
+
+    #[test]
+    fn from_colors() {
+        assert_eq!(Guild::try_from([Color::Blue, Color::White].as_slice()), Ok(Guild::Azorius));
+        assert_eq!(
+            Wedge::try_from([Color::Red, Color::Blue, Color::White].as_slice()),
+            Ok(Wedge::Jeskai)
+        );
+        assert_eq!(Shard::try_from([Color::Red, Color::Blue, Color::White].as_slice()), Err(()));
+        assert_eq!(Guild::try_from(ColorSet::new()), Err(()));
+    }
+}
@@ -1,5 +1,30 @@
-use crate::{Color, color::ALL_COLORS};
+use std::{
+    fmt::{Display, Write},
+    str::FromStr,
+};
+
+use crate::{Color, ColorSetDisplay, ColorSetStyle, Guild, Shard, Wedge, color::ALL_COLORS};
 
+/// A set of [colors](Color).
+///
+/// When iterating or displaying the colors, they are ordered in the way they
+/// are usually written, which minimizes the distance between them on the
+/// [color wheel](https://mtg.wiki/page/Color_wheel) (e.g. `GW` instead of
+/// `WG`).
+///
+/// ```
+/// use mana_symbols::{Color, ColorSet};
+///
+/// let colors: ColorSet = [Color::White, Color::Green].into_iter().collect();
+/// assert_eq!(colors.to_string(), "GW");
+/// assert_eq!(colors.len(), 2);
+///
+/// let mut other = ColorSet::new();
+/// other.insert(Color::Blue);
+/// assert_eq!(colors.union(other).to_string(), "GWU");
+/// assert!(colors.intersection(other).is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct ColorSet {
     bitset: u8,
 }
@@ -7,12 +32,22 @@ pub struct ColorSet {
 /// There are 2 ^ 5 different color-sets
 const COLOR_SETS: usize = 0b11111 + 1;
 
+/// Names of each set of four colors, as the name of the nephilim and the
+/// Commander 2016 deck, together with the bitset.
+const FOUR_COLOR_NAMES: [(&str, &str, u8); 5] = [
+    ("Glint-Eye", "Chaos", 0b11110),
+    ("Dune-Brood", "Aggression", 0b11101),
+    ("Ink-Treader", "Altruism", 0b11011),
+    ("Witch-Maw", "Growth", 0b10111),
+    ("Yore-Tiller", "Artifice", 0b01111),
+];
+
 const fn add_order(a: &mut [[u8; 5]; COLOR_SETS], color: Color, offsets: &[usize]) {
     let mut set = ColorSet::new();
     let mut i: usize = 0;
     while i < offsets.len() {
         let c = color.next(offsets[i]);
-        set.set_color(c);
+        set.insert(c);
         i += 1;
     }
     let mut i: usize = 1;
@@ -58,23 +93,313 @@ const ORDER_ARRAY: [[u8; 5]; COLOR_SETS] = {
 };
 
 impl ColorSet {
+    /// An empty set of colors.
+    #[must_use]
     pub const fn new() -> Self {
         Self { bitset: 0 }
     }
 
-    pub const fn set_color(&mut self, color: Color) {
+    pub(crate) const fn from_bits(bitset: u8) -> Self {
+        debug_assert!(bitset < COLOR_SETS as u8);
+        Self { bitset }
+    }
+
+    /// The number of colors in the set.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.bitset.count_ones() as usize
+    }
+
+    /// Whether the set has no colors, i.e. it is colorless.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.bitset == 0
+    }
+
+    /// Add `color` to the set. Returns whether it was not already in the set.
+    pub const fn insert(&mut self, color: Color) -> bool {
         // colors should be numbered [0-4]
         debug_assert!((color as u8) < 5);
 
+        let new = !self.contains(color);
         // set the bit corresponding to the color
         self.bitset |= 1 << color as u8;
+        new
+    }
+
+    /// Remove `color` from the set. Returns whether it was in the set.
+    pub const fn remove(&mut self, color: Color) -> bool {
+        let old = self.contains(color);
+        self.bitset &= !(1 << color as u8);
+        old
     }
 
-    pub const fn order_values(&self) -> &[u8] {
+    /// The colors in either `self` or `other`.
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        Self { bitset: self.bitset | other.bitset }
+    }
+
+    /// The colors in both `self` and `other`.
+    #[must_use]
+    pub const fn intersection(self, other: Self) -> Self {
+        Self { bitset: self.bitset & other.bitset }
+    }
+
+    /// The colors in `self` but not in `other`.
+    #[must_use]
+    pub const fn difference(self, other: Self) -> Self {
+        Self { bitset: self.bitset & !other.bitset }
+    }
+
+    /// Whether the set consists of two colors which are adjacent on the
+    /// [color wheel](https://mtg.wiki/page/Color_wheel) (see [`Color::allies`]).
+    ///
+    /// ```
+    /// use mana_symbols::{Color, ColorSet};
+    ///
+    /// let wu: ColorSet = [Color::White, Color::Blue].into_iter().collect();
+    /// let wb: ColorSet = [Color::White, Color::Black].into_iter().collect();
+    ///
+    /// assert!(wu.is_allied_pair());
+    /// assert!(!wb.is_allied_pair());
+    /// assert!(wb.is_enemy_pair());
+    /// ```
+    #[must_use]
+    pub fn is_allied_pair(&self) -> bool {
+        self.pair().is_some_and(|(a, b)| a.allies().contains(&b))
+    }
+
+    /// Whether the set consists of two colors which are opposite on the
+    /// [color wheel](https://mtg.wiki/page/Color_wheel) (see
+    /// [`Color::enemies`]).
+    #[must_use]
+    pub fn is_enemy_pair(&self) -> bool {
+        self.pair().is_some_and(|(a, b)| a.enemies().contains(&b))
+    }
+
+    fn pair(&self) -> Option<(Color, Color)> {
+        let mut colors = self.iter();
+        match (colors.next(), colors.next(), colors.next()) {
+            (Some(a), Some(b), None) => Some((a, b)),
+            _ => None,
+        }
+    }
+
+    /// Iterate over the colors in the set, in the order they are usually
+    /// written (see [`ColorSet`]).
+    pub fn iter(&self) -> impl Iterator<Item = Color> + use<> {
+        self.into_iter()
+    }
+
+    /// Add `color` to the set.
+    #[deprecated(note = "use `ColorSet::insert` instead")]
+    pub const fn set_color(&mut self, color: Color) {
+        self.insert(color);
+    }
+
+    /// Whether `color` is in the set.
+    #[must_use]
+    pub const fn contains(&self, color: Color) -> bool {
+        self.bitset & (1 << color as u8) != 0
+    }
+
+    /// Whether every color in `self` is also in `other`.
+    #[must_use]
+    pub const fn is_subset(&self, other: &Self) -> bool {
+        self.bitset & !other.bitset == 0
+    }
+
+    /// The nickname of the colors, i.e. the name of the [guild](Guild),
+    /// [shard](Shard) or [wedge](Wedge), or the name of the
+    /// [nephilim](https://mtg.wiki/page/Nephilim) for four colors.
+    ///
+    /// ```
+    /// use mana_symbols::{Color, ColorSet};
+    ///
+    /// let colors: ColorSet = "RW".parse().unwrap();
+    /// assert_eq!(colors.nickname(), Some("Boros"));
+    ///
+    /// let colors: ColorSet = "UBRG".parse().unwrap();
+    /// assert_eq!(colors.nickname(), Some("Glint-Eye"));
+    ///
+    /// let colors: ColorSet = "U".parse().unwrap();
+    /// assert_eq!(colors.nickname(), None);
+    /// ```
+    #[must_use]
+    pub fn nickname(&self) -> Option<&'static str> {
+        if let Ok(guild) = Guild::try_from(*self) {
+            Some(guild.name())
+        } else if let Ok(shard) = Shard::try_from(*self) {
+            Some(shard.name())
+        } else if let Ok(wedge) = Wedge::try_from(*self) {
+            Some(wedge.name())
+        } else {
+            FOUR_COLOR_NAMES
+                .iter()
+                .find(|(_, _, bits)| *bits == self.bitset)
+                .map(|(name, _, _)| *name)
+        }
+    }
+
+    /// The colors with the given nickname, in any case (see
+    /// [`ColorSet::nickname`]). Four colors may also be named after the
+    /// [Commander 2016](https://mtg.wiki/page/Commander_2016) decks, e.g.
+    /// `Chaos` for `UBRG`.
+    ///
+    /// ```
+    /// use mana_symbols::{ColorSet, Guild};
+    ///
+    /// assert_eq!(ColorSet::from_nickname("Boros"), Some(Guild::Boros.into()));
+    /// assert_eq!(ColorSet::from_nickname("glint-eye"), ColorSet::from_nickname("Chaos"));
+    /// assert_eq!(ColorSet::from_nickname("Blue"), None);
+    /// ```
+    #[must_use]
+    pub fn from_nickname(name: &str) -> Option<Self> {
+        let guilds = Guild::ALL.into_iter().map(|x| (x.name(), Self::from(x)));
+        let shards = Shard::ALL.into_iter().map(|x| (x.name(), Self::from(x)));
+        let wedges = Wedge::ALL.into_iter().map(|x| (x.name(), Self::from(x)));
+        let four = FOUR_COLOR_NAMES.iter().flat_map(|&(nephilim, commander, bits)| {
+            [(nephilim, Self::from_bits(bits)), (commander, Self::from_bits(bits))]
+        });
+
+        guilds
+            .chain(shards)
+            .chain(wedges)
+            .chain(four)
+            .find(|(nickname, _)| nickname.eq_ignore_ascii_case(name))
+            .map(|(_, colors)| colors)
+    }
+
+    /// Every set of two colors, allied pairs first, in the order of
+    /// [`Guild::ALL`].
+    ///
+    /// ```
+    /// use mana_symbols::ColorSet;
+    ///
+    /// let pairs: Vec<String> = ColorSet::all_pairs().map(|x| x.to_string()).collect();
+    /// assert_eq!(pairs, ["WU", "UB", "BR", "RG", "GW", "WB", "UR", "BG", "RW", "GU"]);
+    /// ```
+    pub fn all_pairs() -> impl Iterator<Item = Self> {
+        Guild::ALL.into_iter().map(Self::from)
+    }
+
+    /// Every set of three colors, shards first, in the order of
+    /// [`Shard::ALL`] and [`Wedge::ALL`].
+    ///
+    /// ```
+    /// use mana_symbols::ColorSet;
+    ///
+    /// let triples: Vec<String> = ColorSet::all_triples().map(|x| x.to_string()).collect();
+    /// assert_eq!(triples[..2], ["GWU", "WUB"]);
+    /// assert_eq!(triples.len(), 10);
+    /// ```
+    pub fn all_triples() -> impl Iterator<Item = Self> {
+        let shards = Shard::ALL.into_iter().map(Self::from);
+        let wedges = Wedge::ALL.into_iter().map(Self::from);
+        shards.chain(wedges)
+    }
+
+    /// Every set of four colors, ordered by the missing color as `W`, `U`,
+    /// `B`, `R`, `G`.
+    ///
+    /// ```
+    /// use mana_symbols::ColorSet;
+    ///
+    /// let quads: Vec<String> = ColorSet::all_quads().map(|x| x.to_string()).collect();
+    /// assert_eq!(quads, ["UBRG", "BRGW", "RGWU", "GWUB", "WUBR"]);
+    /// ```
+    pub fn all_quads() -> impl Iterator<Item = Self> {
+        FOUR_COLOR_NAMES.into_iter().map(|(_, _, bits)| Self::from_bits(bits))
+    }
+
+    /// Display the colors as text, using the given [`ColorSetStyle`].
+    ///
+    /// ```
+    /// use mana_symbols::{ColorSet, ColorSetStyle};
+    ///
+    /// let colors: ColorSet = "WUG".parse().unwrap();
+    /// assert_eq!(colors.display_with(ColorSetStyle::Letters).to_string(), "GWU");
+    /// assert_eq!(colors.display_with(ColorSetStyle::LowercaseLetters).to_string(), "gwu");
+    /// assert_eq!(colors.display_with(ColorSetStyle::Nickname).to_string(), "Bant");
+    /// ```
+    #[must_use]
+    pub const fn display_with(self, style: ColorSetStyle) -> ColorSetDisplay {
+        ColorSetDisplay { colors: self, style }
+    }
+
+    pub(crate) const fn order_values(&self) -> &[u8] {
         &ORDER_ARRAY[self.bitset as usize]
     }
 }
 
+impl Display for ColorSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for color in self.iter() {
+            f.write_char(color.char())?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses either the letters of the colors, in any order and case (e.g. `WUG`
+/// or `wug`), or a nickname in any case (e.g. `Bant` or `izzet`, see
+/// [`ColorSet::from_nickname`]).
+///
+/// ```
+/// use mana_symbols::{ColorSet, Guild};
+///
+/// let colors: ColorSet = "wug".parse().unwrap();
+/// assert_eq!(colors.to_string(), "GWU");
+///
+/// let colors: ColorSet = "Izzet".parse().unwrap();
+/// assert_eq!(colors, Guild::Izzet.into());
+///
+/// assert!("WUX".parse::<ColorSet>().is_err());
+/// ```
+impl FromStr for ColorSet {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(colors) = Self::from_nickname(s) {
+            return Ok(colors);
+        }
+
+        s.chars()
+            .map(|c| {
+                let c = c.to_ascii_uppercase();
+                ALL_COLORS.into_iter().find(|color| color.char() == c).ok_or(())
+            })
+            .collect()
+    }
+}
+
+impl FromIterator<Color> for ColorSet {
+    fn from_iter<T: IntoIterator<Item = Color>>(iter: T) -> Self {
+        let mut set = Self::new();
+        for color in iter {
+            set.insert(color);
+        }
+        set
+    }
+}
+
+impl IntoIterator for ColorSet {
+    type Item = Color;
+    type IntoIter = std::iter::Flatten<std::array::IntoIter<Option<Color>, 5>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let order = self.order_values();
+        let mut colors = [None; 5];
+        for color in ALL_COLORS {
+            if self.contains(color) {
+                colors[order[color as usize] as usize] = Some(color);
+            }
+        }
+        colors.into_iter().flatten()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,7 +407,7 @@ mod tests {
     fn sort_colors(colors: &mut [Color], goal: &[Color]) {
         let mut color_set = ColorSet::new();
         for &c in colors.iter() {
-            color_set.set_color(c);
+            color_set.insert(c);
         }
 
         let order = color_set.order_values();
@@ -108,6 +433,85 @@ mod tests {
         sort_colors(&mut unsorted, &sorted);
     }
 
+    #[test]
+    fn display() {
+        let set = |colors: &[Color]| colors.iter().copied().collect::<ColorSet>().to_string();
+        assert_eq!(set(&[]), "");
+        assert_eq!(set(&[Color::Green, Color::Blue]), "GU");
+        assert_eq!(set(&[Color::White, Color::Red, Color::Blue]), "URW");
+        assert_eq!(set(&ALL_COLORS), "WUBRG");
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: ColorSet = [Color::White, Color::Blue].into_iter().collect();
+        let b: ColorSet = [Color::Blue, Color::Black].into_iter().collect();
+        assert_eq!(a.union(b).len(), 3);
+        assert_eq!(a.intersection(b).iter().collect::<Vec<_>>(), [Color::Blue]);
+        assert_eq!(a.difference(b).iter().collect::<Vec<_>>(), [Color::White]);
+
+        let mut c = a;
+        assert!(!c.insert(Color::White));
+        assert!(c.remove(Color::White));
+        assert!(!c.remove(Color::White));
+        assert!(c.is_subset(&a));
+        assert!(!a.is_subset(&c));
+    }
+
+    #[test]
+    fn from_str() {
+        let set = |colors: &[Color]| colors.iter().copied().collect::<ColorSet>();
+        assert_eq!("".parse(), Ok(ColorSet::new()));
+        assert_eq!("UG".parse(), Ok(set(&[Color::Green, Color::Blue])));
+        assert_eq!("gUu".parse(), Ok(set(&[Color::Green, Color::Blue])));
+        assert_eq!("SIMIC".parse(), Ok(set(&[Color::Green, Color::Blue])));
+        assert_eq!("jeskai".parse(), Ok(set(&[Color::White, Color::Red, Color::Blue])));
+        assert_eq!("Simi".parse::<ColorSet>(), Err(()));
+        assert_eq!("C".parse::<ColorSet>(), Err(()));
+
+        for colors in (0..COLOR_SETS as u8).map(ColorSet::from_bits) {
+            for style in
+                [ColorSetStyle::Letters, ColorSetStyle::LowercaseLetters, ColorSetStyle::Nickname]
+            {
+                assert_eq!(colors.display_with(style).to_string().parse(), Ok(colors));
+            }
+        }
+    }
+
+    #[test]
+    fn nicknames() {
+        let mut named = 0;
+        for colors in (0..COLOR_SETS as u8).map(ColorSet::from_bits) {
+            if let Some(nickname) = colors.nickname() {
+                assert_eq!(ColorSet::from_nickname(nickname), Some(colors));
+                named += 1;
+            }
+        }
+        assert_eq!(named, 10 + 5 + 5 + 5);
+
+        for (_, commander, bits) in FOUR_COLOR_NAMES {
+            let colors = ColorSet::from_bits(bits);
+            assert_eq!(colors.len(), 4);
+            assert_eq!(ColorSet::from_nickname(commander), Some(colors));
+        }
+        assert_eq!("Yore-Tiller".parse::<ColorSet>().unwrap().to_string(), "WUBR");
+    }
+
+    #[test]
+    fn all_combinations() {
+        let all: Vec<ColorSet> = ColorSet::all_pairs()
+            .chain(ColorSet::all_triples())
+            .chain(ColorSet::all_quads())
+            .collect();
+        assert_eq!(all.len(), 10 + 10 + 5);
+        for (i, colors) in all.iter().enumerate() {
+            assert!(!all[..i].contains(colors));
+        }
+        assert!(ColorSet::all_pairs().all(|x| x.len() == 2));
+        assert!(ColorSet::all_triples().all(|x| x.len() == 3));
+        assert!(ColorSet::all_quads().all(|x| x.len() == 4));
+    }
+
     #[test]
     fn urw() {
         let mut unsorted = [Color::White, Color::Red, Color::Blue];
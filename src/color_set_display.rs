@@ -0,0 +1,46 @@
+use std::fmt::{Display, Write};
+
+use crate::ColorSet;
+
+/// How a [`ColorSet`] is displayed as text.
+///
+/// Used by [`ColorSet::display_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorSetStyle {
+    /// `GWU`, the same as the [`Display`] implementation of [`ColorSet`].
+    #[default]
+    Letters,
+
+    /// `gwu`
+    LowercaseLetters,
+
+    /// `Bant`, the nickname of the colors (see [`ColorSet::nickname`]). Sets
+    /// without a nickname are displayed as [`ColorSetStyle::Letters`].
+    Nickname,
+}
+
+/// A [`ColorSet`] displayed using a [`ColorSetStyle`], created by
+/// [`ColorSet::display_with`].
+#[derive(Debug, Clone, Copy)]
+pub struct ColorSetDisplay {
+    pub(crate) colors: ColorSet,
+    pub(crate) style: ColorSetStyle,
+}
+
+impl Display for ColorSetDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.style {
+            ColorSetStyle::Letters => self.colors.fmt(f),
+            ColorSetStyle::LowercaseLetters => {
+                for color in self.colors {
+                    f.write_char(color.char().to_ascii_lowercase())?;
+                }
+                Ok(())
+            }
+            ColorSetStyle::Nickname => match self.colors.nickname() {
+                Some(nickname) => f.write_str(nickname),
+                None => self.colors.fmt(f),
+            },
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use svg::node::{
+    Attributes, Node,
+    element::{Group, SVG},
+};
+
+use crate::SVGConfig;
+
+/// Attributes which only place the contents of a nested SVG, and are replaced
+/// by a transform when it is inlined.
+const VIEWPORT: [&str; 7] =
+    ["x", "y", "width", "height", "viewBox", "preserveAspectRatio", "xmlns"];
+
+/// Make `svg` only use features of SVG 1.1 Tiny, as decided by
+/// [`SVGConfig::compatible`]. Nested SVGs are inlined as transformed groups,
+/// and attributes which SVG 1.1 Tiny lacks are removed. Nested SVGs with
+/// `overflow="hidden"` are kept, since SVG 1.1 Tiny has no other way to cut
+/// off what is drawn outside them.
+pub(crate) fn make_compatible(mut svg: SVG, config: &SVGConfig) -> SVG {
+    if config.compatible {
+        // Every SVG of this crate has a view box
+        let view_box = svg.get_attributes().and_then(view_box);
+        let viewport = view_box.map_or((0.0, 0.0), |[_, _, width, height]| (width, height));
+        make_node_compatible(&mut svg, viewport);
+    }
+    svg
+}
+
+/// Where `viewport` is the width and height which percentages are relative to.
+fn make_node_compatible(node: &mut dyn Node, viewport: (f64, f64)) {
+    if let Some(attributes) = node.get_attributes_mut() {
+        // The screen reader attributes of SVG 1.1 Tiny are `title` and `desc`
+        attributes.retain(|name, _| name != "role" && !name.starts_with("aria-"));
+        attributes.remove("vector-effect");
+    }
+    let Some(children) = node.get_children_mut() else { return };
+    for child in children {
+        if child.get_attributes().and_then(|attributes| attributes.get("paint-order")).is_some() {
+            *child = Box::new(stroke_below(child.clone()));
+        }
+        let mut viewport = viewport;
+        let clips = child.get_attributes_mut().and_then(|attributes| attributes.remove("overflow"));
+        if child.get_name() == "svg" && clips.is_none() {
+            let (group, inner) = inline_svg(child.as_mut(), viewport);
+            *child = Box::new(group);
+            viewport = inner;
+        }
+        make_node_compatible(child.as_mut(), viewport);
+    }
+}
+
+/// Draw `node` twice, first with its stroke and then without it, which is how
+/// `paint-order="stroke"` draws it.
+fn stroke_below(mut stroked: Box<dyn Node>) -> Group {
+    if let Some(attributes) = stroked.get_attributes_mut() {
+        attributes.remove("paint-order");
+    }
+    let mut filled = stroked.clone();
+    if let Some(attributes) = filled.get_attributes_mut() {
+        attributes.remove("stroke-width");
+        attributes.insert("stroke".to_string(), "none".into());
+    }
+    Group::new().add(stroked).add(filled)
+}
+
+/// Move the children and the other attributes of the nested SVG `node` to a
+/// group, which is transformed to draw them in the same place. Returns the
+/// group and the viewport of its children.
+fn inline_svg(node: &mut dyn Node, viewport: (f64, f64)) -> (Group, (f64, f64)) {
+    let mut group = Group::new();
+    let Some(attributes) = node.get_attributes_mut() else { return (group, viewport) };
+
+    let x = length(attributes, "x", viewport.0).unwrap_or(0.0);
+    let y = length(attributes, "y", viewport.1).unwrap_or(0.0);
+    // A missing width or height is 100%
+    let width = length(attributes, "width", viewport.0).unwrap_or(viewport.0);
+    let height = length(attributes, "height", viewport.1).unwrap_or(viewport.1);
+
+    let (x, y, scale_x, scale_y, inner) = match view_box(attributes) {
+        Some([min_x, min_y, view_width, view_height]) if view_width > 0.0 && view_height > 0.0 => {
+            let (scale_x, scale_y, align_x, align_y) = aspect_ratio(
+                attributes.get("preserveAspectRatio").map_or("xMidYMid meet", |value| value),
+                width / view_width,
+                height / view_height,
+            );
+            let x = align_x.mul_add(view_width.mul_add(-scale_x, width), x) - min_x * scale_x;
+            let y = align_y.mul_add(view_height.mul_add(-scale_y, height), y) - min_y * scale_y;
+            (x, y, scale_x, scale_y, (view_width, view_height))
+        }
+        _ => (x, y, 1.0, 1.0, (width, height)),
+    };
+    let mut transform = Vec::new();
+    if x != 0.0 || y != 0.0 {
+        transform.push(format!("translate({x} {y})"));
+    }
+    if scale_x != 1.0 || scale_y != 1.0 {
+        transform.push(format!("scale({scale_x} {scale_y})"));
+    }
+
+    for name in VIEWPORT {
+        attributes.remove(name);
+    }
+    for (name, value) in attributes.drain() {
+        group = group.set(name, value);
+    }
+    if !transform.is_empty() {
+        group = group.set("transform", transform.join(" "));
+    }
+    if let Some(children) = node.get_children_mut() {
+        for child in children.drain(..) {
+            group = group.add(child);
+        }
+    }
+    (group, inner)
+}
+
+/// The scale and alignment decided by `preserveAspectRatio`, where `scale_x`
+/// and `scale_y` would stretch the view box to fill the viewport.
+fn aspect_ratio(value: &str, scale_x: f64, scale_y: f64) -> (f64, f64, f64, f64) {
+    let mut words = value.split_whitespace();
+    let align = words.next().unwrap_or("xMidYMid");
+    if align == "none" {
+        return (scale_x, scale_y, 0.0, 0.0);
+    }
+    let scale =
+        if words.next() == Some("slice") { scale_x.max(scale_y) } else { scale_x.min(scale_y) };
+    let fraction = |min, max| {
+        if align.contains(min) {
+            0.0
+        } else if align.contains(max) {
+            1.0
+        } else {
+            0.5
+        }
+    };
+    (scale, scale, fraction("xMin", "xMax"), fraction("YMin", "YMax"))
+}
+
+fn view_box(attributes: &Attributes) -> Option<[f64; 4]> {
+    let value = attributes.get("viewBox")?;
+    let mut numbers = value.split([' ', ',']).filter(|x| !x.is_empty()).map(str::parse);
+    let mut next = || numbers.next()?.ok();
+    Some([next()?, next()?, next()?, next()?])
+}
+
+/// The length in the attribute `name`, where percentages are relative to
+/// `relative_to`.
+fn length(attributes: &Attributes, name: &str, relative_to: f64) -> Option<f64> {
+    let value = attributes.get(name)?;
+    match value.strip_suffix('%') {
+        Some(percent) => Some(percent.trim().parse::<f64>().ok()? / 100.0 * relative_to),
+        None => value.trim().parse().ok(),
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt::{Display, Write};
+
+use nom::{IResult, Parser, character::complete::satisfy};
+
+#[cfg(feature = "svg")]
+use crate::Palette;
+
+/// A color which is not one of the five [colors](crate::Color) of the color
+/// pie, e.g. purple in a custom Magic set.
+///
+/// Custom colors are created by registering them in a [`ColorRegistry`], and
+/// are represented by a single letter. Mana of a custom color is
+/// [`Mana::Custom`][crate::Mana::Custom].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CustomColor {
+    letter: char,
+}
+
+impl Display for CustomColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_char(self.letter)
+    }
+}
+
+impl CustomColor {
+    /// The letter representing the color.
+    #[must_use]
+    pub const fn letter(self) -> char {
+        self.letter
+    }
+}
+
+/// Letters which are already used by mana symbols and other symbols of costs
+/// (see [`Symbol`](crate::Symbol)).
+const RESERVED: &[char] = &['W', 'U', 'B', 'R', 'G', 'C', 'S', 'X', 'Y', 'Z', 'P', 'T', 'Q', 'E'];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    color: CustomColor,
+    name: String,
+    hex: String,
+}
+
+/// A collection of [custom colors](CustomColor).
+///
+/// Text containing custom colors can be parsed using
+/// [`Manas::parse_with`][crate::Manas::parse_with], and the colors are used
+/// when rendering after calling [`ColorRegistry::extend_palette`].
+///
+/// # Example
+///
+/// ```
+/// use mana_symbols::{ColorRegistry, Manas};
+///
+/// let mut registry = ColorRegistry::new();
+/// let purple = registry.register('O', "purple", "#c9a0dc").unwrap();
+///
+/// let manas = Manas::from_str_with("{2}{O}{U}", &registry).unwrap();
+/// assert_eq!(manas.to_string(), "{2}{O}{U}");
+///
+/// # #[cfg(feature = "svg")]
+/// # {
+/// let mut config = mana_symbols::SVGConfig::default();
+/// registry.extend_palette(&mut config.palette);
+/// assert_eq!(config.palette.custom_color(purple), "#c9a0dc");
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColorRegistry {
+    entries: Vec<Entry>,
+}
+
+impl ColorRegistry {
+    /// An empty registry.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    /// Register a custom color, represented by `letter`, which should be drawn
+    /// using `hex` (e.g. `#c9a0dc`).
+    ///
+    /// Returns `None` if `letter` is not an uppercase ASCII letter, if it is
+    /// already used by another mana symbol or if it has already been
+    /// registered.
+    pub fn register(&mut self, letter: char, name: &str, hex: &str) -> Option<CustomColor> {
+        if !letter.is_ascii_uppercase() || RESERVED.contains(&letter) || self.get(letter).is_some()
+        {
+            return None;
+        }
+
+        let color = CustomColor { letter };
+        self.entries.push(Entry { color, name: name.to_string(), hex: hex.to_string() });
+        Some(color)
+    }
+
+    /// The custom color represented by `letter`, if it has been registered.
+    #[must_use]
+    pub fn get(&self, letter: char) -> Option<CustomColor> {
+        self.entries.iter().map(|x| x.color).find(|x| x.letter == letter)
+    }
+
+    /// The name of a registered custom color.
+    #[must_use]
+    pub fn name(&self, color: CustomColor) -> Option<&str> {
+        self.entries.iter().find(|x| x.color == color).map(|x| x.name.as_str())
+    }
+
+    /// The fill color of a registered custom color.
+    #[must_use]
+    pub fn hex(&self, color: CustomColor) -> Option<&str> {
+        self.entries.iter().find(|x| x.color == color).map(|x| x.hex.as_str())
+    }
+
+    /// Iterate over the custom colors, in the order they were registered.
+    pub fn iter(&self) -> impl Iterator<Item = CustomColor> + '_ {
+        self.entries.iter().map(|x| x.color)
+    }
+
+    /// Add the fill color and name of every registered custom color to
+    /// `palette`.
+    #[cfg(feature = "svg")]
+    pub fn extend_palette(&self, palette: &mut Palette) {
+        for entry in &self.entries {
+            palette.custom.insert(entry.color, entry.hex.clone());
+            palette.custom_names.insert(entry.color, entry.name.clone());
+        }
+    }
+
+    pub(crate) fn parse<'a>(&self, input: &'a str) -> IResult<&'a str, CustomColor> {
+        satisfy(|c| self.get(c).is_some()).map(|letter| CustomColor { letter }).parse(input)
+    }
+}
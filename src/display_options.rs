@@ -0,0 +1,101 @@
+use std::fmt::Display;
+
+use crate::{Mana, SingleMana, SplitMana};
+
+/// Options for displaying mana symbols as text.
+///
+/// Used by [`Manas::display_with`][crate::Manas::display_with].
+///
+/// For default options, which are the same as the [`Display`] implementation
+/// of [`Manas`][crate::Manas], use [`DisplayOptions::default`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayOptions {
+    /// Written between each mana symbol.
+    pub separator: String,
+
+    /// How each mana symbol is enclosed.
+    pub braces: BraceStyle,
+
+    /// How Phyrexian mana is marked.
+    pub phyrexian: PhyrexianStyle,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            separator: String::new(),
+            braces: BraceStyle::Curly,
+            phyrexian: PhyrexianStyle::Letter,
+        }
+    }
+}
+
+/// How mana symbols are enclosed when displayed as text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BraceStyle {
+    /// `{U}`
+    #[default]
+    Curly,
+
+    /// `[U]`
+    Square,
+
+    /// `(U)`
+    Round,
+
+    /// `U`
+    None,
+}
+
+impl BraceStyle {
+    const fn pair(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Curly => ("{", "}"),
+            Self::Square => ("[", "]"),
+            Self::Round => ("(", ")"),
+            Self::None => ("", ""),
+        }
+    }
+}
+
+/// How Phyrexian mana is marked when displayed as text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PhyrexianStyle {
+    /// `{U/P}` and `{B/G/P}`
+    #[default]
+    Letter,
+
+    /// `{UΦ}` and `{B/GΦ}`, as written by some older sources.
+    Phi,
+}
+
+/// Mana symbols displayed using [`DisplayOptions`], created by
+/// [`Manas::display_with`][crate::Manas::display_with].
+#[derive(Debug, Clone, Copy)]
+pub struct ManasDisplay<'a> {
+    pub(crate) manas: &'a [Mana],
+    pub(crate) options: &'a DisplayOptions,
+}
+
+impl Display for ManasDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (open, close) = self.options.braces.pair();
+        for (i, mana) in self.manas.iter().enumerate() {
+            if i != 0 {
+                f.write_str(&self.options.separator)?;
+            }
+            f.write_str(open)?;
+            match (self.options.phyrexian, mana) {
+                (PhyrexianStyle::Phi, Mana::Single(SingleMana::Phyrexian(color))) => {
+                    write!(f, "{color}Φ")?;
+                }
+                (PhyrexianStyle::Phi, Mana::Split(SplitMana::Duo { a, b, phyrexian: true })) => {
+                    write!(f, "{a}/{b}Φ")?;
+                }
+                _ => mana.fmt(f)?,
+            }
+            f.write_str(close)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,374 @@
+use egui::{Color32, Mesh, Painter, Pos2, Rect, Response, Sense, Shape, TextStyle, Ui, Vec2};
+use lyon_tessellation::{
+    BuffersBuilder, FillOptions, FillRule, FillTessellator, FillVertex, StrokeOptions,
+    StrokeTessellator, StrokeVertex, VertexBuffers,
+    geom::point,
+    path::{Path as LyonPath, iterator::PathIterator},
+};
+use resvg::usvg::{self, Node, Paint, Transform, tiny_skia_path::PathSegment};
+use svg::node::element::SVG;
+
+use crate::svg_tree::svg_to_tree;
+
+/// How far the tessellated curves may be from the real ones, where the width
+/// of a mana symbol is 32.
+const TOLERANCE: f32 = 0.02;
+
+/// Mana symbols tessellated into triangles, which can be drawn by
+/// [egui](https://www.egui.rs) without rasterizing them first. Created by
+/// [`Mana::as_egui_mesh`](crate::Mana::as_egui_mesh) and
+/// [`Manas::as_egui_mesh`](crate::Manas::as_egui_mesh).
+///
+/// Tessellating the symbols takes some time, so the mesh should be created
+/// once and then drawn every frame, using [`SymbolMesh::show`] or as a
+/// [`Widget`](egui::Widget), which makes the symbols as high as the body
+/// text.
+///
+/// Filters (i.e. [`SVGConfig::drop_shadow`](crate::SVGConfig::drop_shadow))
+/// are not drawn.
+///
+/// ```
+/// use mana_symbols::{Manas, SVGConfig};
+///
+/// let manas: Manas = "{2}{U}{U}".parse().unwrap();
+/// let mesh = manas.as_egui_mesh(&SVGConfig::default()).unwrap();
+///
+/// # let ctx = egui::Context::default();
+/// # let _ = ctx.run(Default::default(), |ctx| {
+/// egui::CentralPanel::default().show(ctx, |ui| {
+///     ui.add(&mesh);
+///     mesh.show(ui, 32.0);
+/// });
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct SymbolMesh {
+    mesh: Mesh,
+    size: Vec2,
+}
+
+impl SymbolMesh {
+    /// The size of the symbols, where the width of a mana symbol is 32.
+    #[must_use]
+    pub const fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    /// Draw the symbols stretched to fill `rect`.
+    pub fn paint(&self, painter: &Painter, rect: Rect) {
+        let scale = rect.size() / self.size;
+        let mut mesh = self.mesh.clone();
+        for vertex in &mut mesh.vertices {
+            vertex.pos = rect.min + vertex.pos.to_vec2() * scale;
+        }
+        painter.add(Shape::mesh(mesh));
+    }
+
+    /// Add the symbols to `ui`, `height` points high.
+    pub fn show(&self, ui: &mut Ui, height: f32) -> Response {
+        let size = Vec2::new(height * self.size.x / self.size.y, height);
+        let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+        if ui.is_rect_visible(rect) {
+            self.paint(ui.painter(), rect);
+        }
+        response
+    }
+}
+
+impl egui::Widget for &SymbolMesh {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let height = ui.text_style_height(&TextStyle::Body);
+        self.show(ui, height)
+    }
+}
+
+/// Tessellate `svg`. Returns `None` if it could not be parsed.
+pub(crate) fn svg_to_mesh(svg: &SVG) -> Option<SymbolMesh> {
+    let tree = svg_to_tree(svg)?;
+    let mut mesh = Mesh::default();
+    add_group(&mut mesh, tree.root(), Transform::default(), 1.0);
+    let size = Vec2::new(tree.size().width(), tree.size().height());
+    Some(SymbolMesh { mesh, size })
+}
+
+fn add_group(mesh: &mut Mesh, group: &usvg::Group, transform: Transform, opacity: f32) {
+    let transform = transform.pre_concat(group.transform());
+    let opacity = opacity * group.opacity().get();
+
+    // Masked content is drawn separately, so that it can be clipped
+    let mut content = Mesh::default();
+    let target = if group.mask().is_some() { &mut content } else { &mut *mesh };
+    for child in group.children() {
+        match child {
+            Node::Group(group) => add_group(target, group, transform, opacity),
+            Node::Path(path) => add_path(target, path, transform, opacity),
+            Node::Text(text) => add_group(target, text.flattened(), transform, opacity),
+            Node::Image(_) => {}
+        }
+    }
+
+    if let Some(mask) = group.mask() {
+        match mask_polygon(mask.root(), transform) {
+            Some(polygon) => add_clipped(mesh, &content, &polygon),
+            None => mesh.append(content),
+        }
+    }
+}
+
+fn add_path(mesh: &mut Mesh, path: &usvg::Path, transform: Transform, opacity: f32) {
+    if !path.is_visible() {
+        return;
+    }
+    let lyon_path = to_lyon(path.data());
+    let mut buffers: VertexBuffers<Pos2, u32> = VertexBuffers::new();
+
+    let mut add = |buffers: &mut VertexBuffers<Pos2, u32>, paint: &Paint, paint_opacity: f32| {
+        let start = mesh.vertices.len() as u32;
+        for &local in &buffers.vertices {
+            let color = paint_color(paint, local, opacity * paint_opacity);
+            mesh.colored_vertex(map(transform, local), color);
+        }
+        mesh.indices.extend(buffers.indices.iter().map(|i| start + i));
+        buffers.vertices.clear();
+        buffers.indices.clear();
+    };
+
+    if let Some(fill) = path.fill() {
+        let rule = match fill.rule() {
+            usvg::FillRule::NonZero => FillRule::NonZero,
+            usvg::FillRule::EvenOdd => FillRule::EvenOdd,
+        };
+        let options = FillOptions::tolerance(TOLERANCE).with_fill_rule(rule);
+        let mut builder = BuffersBuilder::new(&mut buffers, |v: FillVertex| {
+            Pos2::new(v.position().x, v.position().y)
+        });
+        if FillTessellator::new().tessellate_path(&lyon_path, &options, &mut builder).is_ok() {
+            add(&mut buffers, fill.paint(), fill.opacity().get());
+        }
+    }
+
+    if let Some(stroke) = path.stroke() {
+        let options = StrokeOptions::tolerance(TOLERANCE).with_line_width(stroke.width().get());
+        let mut builder = BuffersBuilder::new(&mut buffers, |v: StrokeVertex| {
+            Pos2::new(v.position().x, v.position().y)
+        });
+        if StrokeTessellator::new().tessellate_path(&lyon_path, &options, &mut builder).is_ok() {
+            add(&mut buffers, stroke.paint(), stroke.opacity().get());
+        }
+    }
+}
+
+fn to_lyon(path: &usvg::tiny_skia_path::Path) -> LyonPath {
+    let mut builder = LyonPath::builder();
+    let mut open = false;
+    for segment in path.segments() {
+        match segment {
+            PathSegment::MoveTo(p) => {
+                if open {
+                    builder.end(false);
+                }
+                builder.begin(point(p.x, p.y));
+                open = true;
+            }
+            PathSegment::LineTo(p) => {
+                builder.line_to(point(p.x, p.y));
+            }
+            PathSegment::QuadTo(c, p) => {
+                builder.quadratic_bezier_to(point(c.x, c.y), point(p.x, p.y));
+            }
+            PathSegment::CubicTo(c1, c2, p) => {
+                builder.cubic_bezier_to(point(c1.x, c1.y), point(c2.x, c2.y), point(p.x, p.y));
+            }
+            PathSegment::Close => {
+                if open {
+                    builder.end(true);
+                }
+                open = false;
+            }
+        }
+    }
+    if open {
+        builder.end(false);
+    }
+    builder.build()
+}
+
+/// The color of `paint` at `local`, in the coordinates of the path.
+/// Gradients are evaluated at each vertex and interpolated between them.
+fn paint_color(paint: &Paint, local: Pos2, opacity: f32) -> Color32 {
+    let (color, stop_opacity) = match paint {
+        Paint::Color(color) => (*color, 1.0),
+        Paint::LinearGradient(gradient) => {
+            let mut p = usvg::tiny_skia_path::Point::from_xy(local.x, local.y);
+            if let Some(inverse) = gradient.transform().invert() {
+                inverse.map_point(&mut p);
+            }
+            let (dx, dy) = (gradient.x2() - gradient.x1(), gradient.y2() - gradient.y1());
+            let length = dx.mul_add(dx, dy * dy);
+            let t = if length > 0.0 {
+                (p.x - gradient.x1()).mul_add(dx, (p.y - gradient.y1()) * dy) / length
+            } else {
+                0.0
+            };
+            gradient_color(gradient.stops(), t.clamp(0.0, 1.0))
+        }
+        Paint::RadialGradient(gradient) => gradient_color(gradient.stops(), 0.5),
+        Paint::Pattern(_) => (usvg::Color::black(), 0.0),
+    };
+    let alpha = (opacity * stop_opacity * 255.0).round() as u8;
+    Color32::from_rgba_unmultiplied(color.red, color.green, color.blue, alpha)
+}
+
+fn gradient_color(stops: &[usvg::Stop], t: f32) -> (usvg::Color, f32) {
+    let Some(first) = stops.first() else {
+        return (usvg::Color::black(), 0.0);
+    };
+    let mut previous = first;
+    for stop in stops {
+        let offset = stop.offset().get();
+        if t <= offset {
+            let start = previous.offset().get();
+            let s = if offset > start { (t - start) / (offset - start) } else { 1.0 };
+            let lerp = |a: u8, b: u8| f32::from(a).mul_add(1.0 - s, f32::from(b) * s).round() as u8;
+            let (a, b) = (previous.color(), stop.color());
+            let color = usvg::Color::new_rgb(
+                lerp(a.red, b.red),
+                lerp(a.green, b.green),
+                lerp(a.blue, b.blue),
+            );
+            let opacity = previous.opacity().get().mul_add(1.0 - s, stop.opacity().get() * s);
+            return (color, opacity);
+        }
+        previous = stop;
+    }
+    (previous.color(), previous.opacity().get())
+}
+
+fn map(transform: Transform, p: Pos2) -> Pos2 {
+    let mut p = usvg::tiny_skia_path::Point::from_xy(p.x, p.y);
+    transform.map_point(&mut p);
+    Pos2::new(p.x, p.y)
+}
+
+/// The outline of the first path in a mask, as a counterclockwise polygon.
+/// Masks are assumed to be convex, which holds for the masks used by this
+/// crate.
+fn mask_polygon(root: &usvg::Group, transform: Transform) -> Option<Vec<Pos2>> {
+    let transform = transform.pre_concat(root.transform());
+    let path = root.children().iter().find_map(|node| match node {
+        Node::Path(path) => Some(path),
+        _ => None,
+    })?;
+    let mut polygon: Vec<Pos2> = Vec::new();
+    for event in to_lyon(path.data()).iter().flattened(TOLERANCE) {
+        if let lyon_tessellation::path::Event::Line { to, .. } = event {
+            polygon.push(map(transform, Pos2::new(to.x, to.y)));
+        }
+    }
+    if signed_area(&polygon) < 0.0 {
+        polygon.reverse();
+    }
+    (polygon.len() >= 3).then_some(polygon)
+}
+
+fn signed_area(polygon: &[Pos2]) -> f32 {
+    let mut area = 0.0;
+    for (i, a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        area += a.x.mul_add(b.y, -(b.x * a.y));
+    }
+    area / 2.0
+}
+
+/// Add the triangles of `content` to `mesh`, clipped to the convex `polygon`.
+fn add_clipped(mesh: &mut Mesh, content: &Mesh, polygon: &[Pos2]) {
+    for triangle in content.indices.chunks_exact(3) {
+        let mut vertices: Vec<(Pos2, Color32)> = triangle
+            .iter()
+            .map(|&i| {
+                let vertex = content.vertices[i as usize];
+                (vertex.pos, vertex.color)
+            })
+            .collect();
+        // The triangle has to be counterclockwise too
+        if signed_area(&vertices.iter().map(|x| x.0).collect::<Vec<_>>()) < 0.0 {
+            vertices.reverse();
+        }
+
+        // Sutherland–Hodgman
+        for (i, &a) in polygon.iter().enumerate() {
+            let b = polygon[(i + 1) % polygon.len()];
+            let inside = |p: Pos2| (b - a).x.mul_add(p.y - a.y, -((b - a).y * (p.x - a.x))) >= 0.0;
+            let input = std::mem::take(&mut vertices);
+            for (j, &current) in input.iter().enumerate() {
+                let previous = input[(j + input.len() - 1) % input.len()];
+                match (inside(previous.0), inside(current.0)) {
+                    (true, true) => vertices.push(current),
+                    (true, false) => vertices.push(intersection(previous, current, a, b)),
+                    (false, true) => {
+                        vertices.push(intersection(previous, current, a, b));
+                        vertices.push(current);
+                    }
+                    (false, false) => {}
+                }
+            }
+            if vertices.is_empty() {
+                break;
+            }
+        }
+
+        if vertices.len() >= 3 {
+            let start = mesh.vertices.len() as u32;
+            for &(pos, color) in &vertices {
+                mesh.colored_vertex(pos, color);
+            }
+            for k in 1..(vertices.len() as u32 - 1) {
+                mesh.add_triangle(start, start + k, start + k + 1);
+            }
+        }
+    }
+}
+
+/// Where the segment from `p` to `q` crosses the line through `a` and `b`.
+fn intersection(p: (Pos2, Color32), q: (Pos2, Color32), a: Pos2, b: Pos2) -> (Pos2, Color32) {
+    let edge = b - a;
+    let cross = |v: Vec2, w: Vec2| v.x.mul_add(w.y, -(v.y * w.x));
+    let denominator = cross(edge, q.0 - p.0);
+    let t = if denominator == 0.0 { 0.0 } else { cross(a - p.0, edge) / -denominator };
+    let t = t.clamp(0.0, 1.0);
+    (p.0 + (q.0 - p.0) * t, p.1.lerp_to_gamma(q.1, t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Mana, SVGConfig};
+
+    #[test]
+    fn mesh() {
+        let mana: Mana = "U".parse().unwrap();
+        let config = SVGConfig { shadow: false, shadow_offset: 0.0, ..Default::default() };
+        let mesh = mana.as_egui_mesh(&config).unwrap();
+        assert_eq!(mesh.size(), Vec2::splat(32.0));
+
+        let blue = Color32::from_rgb(0xaa, 0xe0, 0xfa);
+        assert!(mesh.mesh.vertices.iter().any(|x| x.color == blue));
+        assert!(
+            mesh.mesh
+                .vertices
+                .iter()
+                .all(|x| Rect::from_min_size(Pos2::ZERO, mesh.size).expand(0.5).contains(x.pos))
+        );
+    }
+
+    #[test]
+    fn hybrid_clipped() {
+        let mana: Mana = "W/U".parse().unwrap();
+        let config = SVGConfig { shadow: false, shadow_offset: 0.0, ..Default::default() };
+        let mesh = mana.as_egui_mesh(&config).unwrap();
+
+        // The halves are squares and triangles, clipped by a circle
+        let center = Pos2::new(16.0, 16.0);
+        assert!(mesh.mesh.vertices.iter().all(|x| x.pos.distance(center) <= 16.0 + TOLERANCE));
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt::{self, Write};
+
+use crate::Mana;
+
+/// Custom [Discord](https://discord.com) emojis for mana symbols, used by
+/// [`Manas::to_discord`](crate::Manas::to_discord).
+///
+/// Every emoji has a name and an ID, which Discord assigns when the emoji is
+/// uploaded. By default, the name of the emoji for a symbol is `mana`
+/// followed by its text without braces or slashes, e.g. `manaU` for `{U}` or
+/// `manaWP` for `{W/P}` (see [`EmojiMap::default_name`]).
+///
+/// ```
+/// use mana_symbols::{EmojiMap, Manas};
+///
+/// let mut emojis = EmojiMap::new();
+/// emojis.insert("U".parse().unwrap(), 1234);
+/// emojis.insert_named("2".parse().unwrap(), "two", 5678);
+///
+/// let manas: Manas = "{2}{U}".parse().unwrap();
+/// assert_eq!(manas.to_discord(&emojis), "<:two:5678><:manaU:1234>");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EmojiMap {
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    mana: Mana,
+    name: String,
+    id: u64,
+}
+
+impl EmojiMap {
+    /// An empty map.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    /// Use the emoji with `id` for `mana`, with the name given by
+    /// [`EmojiMap::default_name`]. Replaces any emoji previously used for
+    /// `mana`.
+    pub fn insert(&mut self, mana: Mana, id: u64) {
+        self.insert_named(mana, &Self::default_name(&mana), id);
+    }
+
+    /// Use the emoji called `name` with `id` for `mana`. Replaces any emoji
+    /// previously used for `mana`.
+    pub fn insert_named(&mut self, mana: Mana, name: &str, id: u64) {
+        let entry = Entry { mana, name: name.to_string(), id };
+        match self.entries.iter_mut().find(|x| x.mana == mana) {
+            Some(old) => *old = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    /// The emoji used for `mana`, e.g. `<:manaU:1234>`.
+    #[must_use]
+    pub fn get(&self, mana: &Mana) -> Option<String> {
+        let entry = self.entries.iter().find(|x| x.mana == *mana)?;
+        Some(format!("<:{}:{}>", entry.name, entry.id))
+    }
+
+    /// The default name of the emoji for `mana`. `{∞}` is called
+    /// `manainfinity`.
+    ///
+    /// ```
+    /// use mana_symbols::{EmojiMap, Mana};
+    ///
+    /// let wu: Mana = "W/U".parse().unwrap();
+    /// let ten: Mana = "10".parse().unwrap();
+    /// let infinity: Mana = "∞".parse().unwrap();
+    /// assert_eq!(EmojiMap::default_name(&wu), "manaWU");
+    /// assert_eq!(EmojiMap::default_name(&ten), "mana10");
+    /// assert_eq!(EmojiMap::default_name(&infinity), "manainfinity");
+    /// ```
+    #[must_use]
+    pub fn default_name(mana: &Mana) -> String {
+        let mut name = "mana".to_string();
+        let text = mana.to_string().replace('∞', "infinity");
+        name.extend(text.chars().filter(char::is_ascii_alphanumeric));
+        name
+    }
+
+    /// Write the emoji for `mana`, or its text (e.g. `{U}`) if it has no
+    /// emoji.
+    pub(crate) fn write<W: Write>(&self, output: &mut W, mana: &Mana) -> fmt::Result {
+        match self.get(mana) {
+            Some(emoji) => output.write_str(&emoji),
+            None => write!(output, "{{{mana}}}"),
+        }
+    }
+}
+
+/// Custom [Slack](https://slack.com) emojis for mana symbols, used by
+/// [`Manas::to_slack`](crate::Manas::to_slack).
+///
+/// Slack emojis are only referred to by name, so every symbol has an emoji:
+/// either one set using [`SlackEmojiMap::insert`], or the one given by
+/// [`SlackEmojiMap::default_name`], e.g. `mana-u` for `{U}` or `mana-wp` for
+/// `{W/P}`.
+///
+/// ```
+/// use mana_symbols::{Manas, SlackEmojiMap};
+///
+/// let mut emojis = SlackEmojiMap::new();
+/// emojis.insert("U".parse().unwrap(), "blue");
+///
+/// let manas: Manas = "{2}{U}".parse().unwrap();
+/// assert_eq!(manas.to_slack(&emojis), ":mana-2::blue:");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SlackEmojiMap {
+    entries: Vec<(Mana, String)>,
+}
+
+impl SlackEmojiMap {
+    /// A map using the default names for every symbol.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    /// Use the emoji called `name` for `mana`, instead of its default name.
+    /// Replaces any emoji previously used for `mana`.
+    pub fn insert(&mut self, mana: Mana, name: &str) {
+        match self.entries.iter_mut().find(|x| x.0 == mana) {
+            Some(old) => old.1 = name.to_string(),
+            None => self.entries.push((mana, name.to_string())),
+        }
+    }
+
+    /// The name of the emoji used for `mana`, e.g. `mana-u`.
+    #[must_use]
+    pub fn get(&self, mana: &Mana) -> String {
+        match self.entries.iter().find(|x| x.0 == *mana) {
+            Some((_, name)) => name.clone(),
+            None => Self::default_name(mana),
+        }
+    }
+
+    /// The default name of the emoji for `mana`. `{∞}` is called
+    /// `mana-infinity`.
+    ///
+    /// ```
+    /// use mana_symbols::{Mana, SlackEmojiMap};
+    ///
+    /// let wu: Mana = "W/U".parse().unwrap();
+    /// let ten: Mana = "10".parse().unwrap();
+    /// let infinity: Mana = "∞".parse().unwrap();
+    /// assert_eq!(SlackEmojiMap::default_name(&wu), "mana-wu");
+    /// assert_eq!(SlackEmojiMap::default_name(&ten), "mana-10");
+    /// assert_eq!(SlackEmojiMap::default_name(&infinity), "mana-infinity");
+    /// ```
+    #[must_use]
+    pub fn default_name(mana: &Mana) -> String {
+        let mut name = "mana-".to_string();
+        let text = mana.to_string().to_ascii_lowercase().replace('∞', "infinity");
+        name.extend(text.chars().filter(char::is_ascii_alphanumeric));
+        name
+    }
+
+    /// Write the emoji for `mana`, e.g. `:mana-u:`.
+    pub(crate) fn write<W: Write>(&self, output: &mut W, mana: &Mana) -> fmt::Result {
+        write!(output, ":{}:", self.get(mana))
+    }
+}
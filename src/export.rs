@@ -0,0 +1,137 @@
+use std::{fmt::Write, fs, io, path::Path};
+
+use crate::{Mana, SVGConfig, canonical_svg};
+
+/// Options for [`export_symbols`].
+///
+/// For default options, use [`ExportOptions::default`].
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    /// Configuration of each SVG.
+    pub config: SVGConfig,
+
+    /// If set, a PNG image of this size in pixels is also written for each
+    /// mana symbol.
+    #[cfg(feature = "png")]
+    pub png_size: Option<u32>,
+}
+
+/// Write every mana symbol which has been printed on a card (see
+/// [`Mana::all_printed`]) as an SVG file in `dir`, which is created if it does
+/// not exist.
+///
+/// Each file is named after the text representation of the mana symbol in
+/// lowercase, where `/` is replaced by `_`, e.g. `u.svg`, `2_w.svg` and
+/// `w_u_p.svg`. `{∞}` is written to `infinity.svg`.
+///
+/// ```no_run
+/// use mana_symbols::{ExportOptions, export_symbols};
+///
+/// export_symbols("static/mana", &ExportOptions::default()).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if `dir` could not be created or a file could not be
+/// written.
+pub fn export_symbols(dir: impl AsRef<Path>, options: &ExportOptions) -> io::Result<()> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+
+    for mana in Mana::all_printed() {
+        let name = file_stem(mana);
+        let svg = mana.as_svg(&options.config);
+        fs::write(dir.join(format!("{name}.svg")), canonical_svg(&svg))?;
+
+        #[cfg(feature = "png")]
+        if let Some(size) = options.png_size
+            && let Some(png) = mana.as_png(size, &options.config)
+        {
+            fs::write(dir.join(format!("{name}.png")), png)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn file_stem(mana: Mana) -> String {
+    ascii_name(mana).replace('/', "_")
+}
+
+/// The text representation of `mana` in lowercase, with `∞` spelled out.
+fn ascii_name(mana: Mana) -> String {
+    mana.to_string().to_lowercase().replace('∞', "infinity")
+}
+
+/// A CSS stylesheet with a class for every mana symbol which has been printed
+/// on a card (see [`Mana::all_printed`]), which draws the symbol as a
+/// background image. The images are the size of the surrounding text.
+///
+/// Each class is named `mana-` followed by the text representation of the
+/// mana symbol in lowercase without `/`, e.g. `mana-u`, `mana-2w` and
+/// `mana-wup`. `{∞}` uses `mana-infinity`.
+///
+/// ```
+/// use mana_symbols::{SVGConfig, stylesheet};
+///
+/// let css = stylesheet(&SVGConfig::default());
+/// assert!(css.contains(".mana-u {"));
+/// assert!(css.contains(".mana-2w {"));
+/// ```
+#[must_use]
+pub fn stylesheet(config: &SVGConfig) -> String {
+    let mut out = String::new();
+    write_stylesheet(&mut out, config).unwrap();
+    out
+}
+
+/// Write the CSS stylesheet of [`stylesheet`] to `output`.
+pub fn write_stylesheet<W: Write>(output: &mut W, config: &SVGConfig) -> std::fmt::Result {
+    for mana in Mana::all_printed() {
+        let class = ascii_name(mana).replace('/', "");
+        let data_uri = mana.as_svg_data_uri(config);
+        writeln!(output, ".mana-{class} {{")?;
+        writeln!(output, "  display: inline-block;")?;
+        // Half mana only shows the left half of the circle
+        let width = if mana.is_half() { "0.5em" } else { "1em" };
+        writeln!(output, "  width: {width};")?;
+        writeln!(output, "  height: 1em;")?;
+        writeln!(output, "  vertical-align: middle;")?;
+        writeln!(output, "  background: url(\"{data_uri}\") no-repeat;")?;
+        writeln!(output, "  background-size: contain;")?;
+        writeln!(output, "}}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export() {
+        let dir = std::env::temp_dir().join(format!("mana_symbols_export_{}", std::process::id()));
+        export_symbols(&dir, &ExportOptions::default()).unwrap();
+
+        let files = fs::read_dir(&dir).unwrap().count();
+        assert_eq!(files, Mana::all_printed().count());
+        assert!(dir.join("w_u_p.svg").is_file());
+        assert!(dir.join("1000000.svg").is_file());
+        assert!(dir.join("infinity.svg").is_file());
+        assert!(dir.join("hw.svg").is_file());
+        assert!(dir.join("hr.svg").is_file());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn stylesheet_classes() {
+        let css = stylesheet(&SVGConfig::default());
+        let classes: Vec<&str> = css.lines().filter(|x| x.starts_with('.')).collect();
+        assert_eq!(classes.len(), Mana::all_printed().count());
+        for (i, class) in classes.iter().enumerate() {
+            assert!(!classes[..i].contains(class));
+        }
+        assert!(css.contains(".mana-hw {\n  display: inline-block;\n  width: 0.5em;"));
+    }
+}
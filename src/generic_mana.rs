@@ -1,7 +1,10 @@
 use std::fmt::{Display, Write};
 
 use nom::{
-    IResult, Parser, branch::alt, bytes::complete::take_while, character::complete::char,
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::{tag, take_while},
+    character::complete::char,
     combinator::value,
 };
 
@@ -11,6 +14,7 @@ pub enum GenericMana {
     X,
     Y,
     Z,
+    Infinity,
 }
 
 impl Display for GenericMana {
@@ -20,6 +24,7 @@ impl Display for GenericMana {
             Self::X => f.write_char('X'),
             Self::Y => f.write_char('Y'),
             Self::Z => f.write_char('Z'),
+            Self::Infinity => f.write_char('∞'),
         }
     }
 }
@@ -29,8 +34,10 @@ impl GenericMana {
         let x = value(Self::X, char('X'));
         let y = value(Self::Y, char('Y'));
         let z = value(Self::Z, char('Z'));
+        // `INF` is accepted for sources which only use ASCII
+        let infinity = value(Self::Infinity, alt((tag("∞"), tag("INF"))));
         let number =
             take_while(|c: char| c.is_numeric()).map_res(|s: &str| s.parse().map(Self::Number));
-        alt((x, y, z, number)).parse(input)
+        alt((x, y, z, infinity, number)).parse(input)
     }
 }
@@ -0,0 +1,89 @@
+use std::fmt::Write;
+
+/// Options for displaying mana symbols as HTML.
+///
+/// Used by [`Mana::as_html_with`][crate::Mana::as_html_with] and
+/// [`Manas::as_html_with`][crate::Manas::as_html_with].
+///
+/// For default options, which are the same as
+/// [`Mana::as_html`][crate::Mana::as_html] without CSS, use
+/// [`HtmlOptions::default`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HtmlOptions {
+    /// Whether each SVG is written inline as an `<svg>` element, instead of as
+    /// an `<img>` element with a base64 data URI. Inline SVGs can be styled
+    /// using CSS.
+    pub inline: bool,
+
+    /// Whether to include a `style` attribute which sizes each image relative
+    /// to the surrounding text.
+    pub include_css: bool,
+
+    /// The `class` attribute of each image, if any. Can be used instead of
+    /// [`HtmlOptions::include_css`] to style the images.
+    pub class: Option<String>,
+
+    /// Whether images are loaded lazily, i.e. `loading="lazy"`. Only used when
+    /// the SVG is not [inline](HtmlOptions::inline).
+    pub lazy: bool,
+
+    /// Extra attributes of each image, as names and values. Attributes whose
+    /// names contain anything other than ASCII letters, digits, `-`, `_`, `.`
+    /// and `:` are skipped.
+    pub attributes: Vec<(String, String)>,
+
+    /// The alternative text of each image, instead of the text representation
+    /// of the mana symbol (e.g. `{U}`). Only used when the SVG is not
+    /// [inline](HtmlOptions::inline).
+    pub alt: Option<String>,
+
+    /// The title of each image, instead of the name of the mana symbol (e.g.
+    /// `Blue mana`). Only used when the SVG is not
+    /// [inline](HtmlOptions::inline).
+    pub title: Option<String>,
+
+    /// If set, each image is written as a `<picture>` element, where an SVG
+    /// source is used if supported and otherwise a PNG which is this many
+    /// pixels high, e.g. for e-mails and old web views. Only used when the SVG
+    /// is not [inline](HtmlOptions::inline).
+    ///
+    /// ```
+    /// use mana_symbols::{HtmlOptions, Mana, SVGConfig};
+    ///
+    /// let mana: Mana = "U".parse().unwrap();
+    /// let options = HtmlOptions { picture: Some(32), ..Default::default() };
+    /// let html = mana.as_html_with(&options, &SVGConfig::default());
+    /// assert!(html.starts_with(r#"<picture><source type="image/svg+xml""#));
+    /// assert!(html.contains(r#"src="data:image/png;base64,"#));
+    /// ```
+    #[cfg(feature = "png")]
+    pub picture: Option<u32>,
+}
+
+impl HtmlOptions {
+    /// The extra attributes which have valid names.
+    pub(crate) fn valid_attributes(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.attributes
+            .iter()
+            .filter(|(name, _)| {
+                !name.is_empty()
+                    && name.chars().all(|c| c.is_ascii_alphanumeric() || "-_.:".contains(c))
+            })
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+/// Write `value` so that it can be used inside a quoted HTML attribute.
+pub(crate) fn write_escaped<W: Write>(output: &mut W, value: &str) -> std::fmt::Result {
+    for c in value.chars() {
+        match c {
+            '&' => output.write_str("&amp;")?,
+            '"' => output.write_str("&quot;")?,
+            '\'' => output.write_str("&#39;")?,
+            '<' => output.write_str("&lt;")?,
+            '>' => output.write_str("&gt;")?,
+            c => output.write_char(c)?,
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use std::fmt::{self, Write};
+
+use crate::{GenericMana, Mana, SingleMana, SplitMana, color::HEX_C};
+
+/// The style shared by every symbol. Everything is inline, as e-mail clients
+/// often remove `<style>` elements.
+const STYLE: &str = "display: inline-block; border-radius: 50%; color: black; font-weight: bold; \
+                     text-align: center; vertical-align: middle";
+
+/// How the circle of a symbol is filled.
+enum Background {
+    Solid(&'static str),
+    /// Split diagonally, like hybrid mana.
+    Split(&'static str, &'static str),
+}
+
+/// Write `mana` as a `<span>` which is a colored circle with the text of the
+/// symbol inside, without any images. Half mana is the left half of a circle.
+pub(crate) fn write_html_span<W: Write>(output: &mut W, mana: &Mana) -> fmt::Result {
+    let (background, text) = match mana {
+        Mana::Single(SingleMana::Normal(color) | SingleMana::Half(color)) => {
+            (Background::Solid(color.hex()), color.char().to_string())
+        }
+        Mana::Single(SingleMana::Phyrexian(color)) => {
+            (Background::Solid(color.hex()), "Φ".to_string())
+        }
+        Mana::Generic(GenericMana::Number(n)) => (Background::Solid(HEX_C), n.to_string()),
+        Mana::Generic(GenericMana::X) => (Background::Solid(HEX_C), "X".to_string()),
+        Mana::Generic(GenericMana::Y) => (Background::Solid(HEX_C), "Y".to_string()),
+        Mana::Generic(GenericMana::Z) => (Background::Solid(HEX_C), "Z".to_string()),
+        Mana::Generic(GenericMana::Infinity) => (Background::Solid(HEX_C), "∞".to_string()),
+        Mana::Split(SplitMana::Colorless { color }) => {
+            (Background::Split(HEX_C, color.hex()), format!("C/{}", color.char()))
+        }
+        Mana::Split(SplitMana::Mono { value, color }) => {
+            (Background::Split(HEX_C, color.hex()), format!("{value}/{}", color.char()))
+        }
+        Mana::Split(SplitMana::Duo { a, b, phyrexian }) => {
+            let text =
+                if *phyrexian { "Φ/Φ".to_string() } else { format!("{}/{}", a.char(), b.char()) };
+            (Background::Split(a.hex(), b.hex()), text)
+        }
+        Mana::Colorless => (Background::Solid(HEX_C), "C".to_string()),
+        Mana::Snow => (Background::Solid(HEX_C), "❄".to_string()),
+        // Custom colors are not known without a registry
+        Mana::Custom(color) => (Background::Solid(HEX_C), color.letter().to_string()),
+    };
+
+    // Longer texts use a smaller font, so the sizes (relative to the font
+    // size) differ, but every circle is 1.2em wide in the surrounding text
+    let (font_size, diameter, radius) =
+        if text.chars().count() <= 2 { ("0.75", "1.6", "0.8") } else { ("0.5", "2.4", "1.2") };
+
+    // Names and texts only contain ASCII letters, digits and symbols which do
+    // not have to be escaped
+    write!(output, r#"<span title="{}" style="{STYLE}; "#, mana.name())?;
+    write!(output, "font-size: {font_size}em; height: {diameter}em; line-height: {diameter}em; ")?;
+    if let Mana::Single(SingleMana::Half(_)) = mana {
+        write!(output, "width: {radius}em; border-radius: {radius}em 0 0 {radius}em; ")?;
+    } else {
+        write!(output, "width: {diameter}em; ")?;
+    }
+    match background {
+        Background::Solid(color) => write!(output, "background: {color}")?,
+        // The solid color is used if gradients are not supported
+        Background::Split(a, b) => write!(
+            output,
+            "background: {b}; background: linear-gradient(135deg, {a} 50%, {b} 50%)"
+        )?,
+    }
+    write!(output, r#"">{text}</span>"#)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(mana: &str) -> String {
+        let mut out = String::new();
+        write_html_span(&mut out, &mana.parse().unwrap()).unwrap();
+        out
+    }
+
+    #[test]
+    fn symbols() {
+        assert_eq!(
+            span("U"),
+            r#"<span title="Blue mana" style="display: inline-block; border-radius: 50%; color: black; font-weight: bold; text-align: center; vertical-align: middle; font-size: 0.75em; height: 1.6em; line-height: 1.6em; width: 1.6em; background: #aae0fa">U</span>"#
+        );
+        let hybrid = span("W/U");
+        assert!(hybrid.contains("linear-gradient(135deg, #fffbd5 50%, #aae0fa 50%)"));
+        assert!(hybrid.ends_with(">W/U</span>"));
+        assert!(span("HR").contains("border-radius: 0.8em 0 0 0.8em"));
+    }
+}
@@ -0,0 +1,72 @@
+use iced_core::svg::Handle;
+use svg::node::element::SVG;
+
+use crate::{Mana, Manas, SVGConfig, canonical_svg};
+
+/// SVG handles of mana symbols for [iced](https://iced.rs), which can be shown
+/// using `iced::widget::svg`.
+///
+/// Handles are created the first time a symbol is requested and then reused,
+/// so that iced can cache the rendered images. Every handle is drawn using the
+/// same [`SVGConfig`].
+///
+/// ```
+/// use mana_symbols::{Manas, SVGConfig, SvgHandles};
+///
+/// let mut handles = SvgHandles::new(SVGConfig::default());
+/// let manas: Manas = "{1}{U}{U}".parse().unwrap();
+///
+/// let symbols = handles.get_all(&manas);
+/// assert_eq!(symbols.len(), 3);
+/// assert_eq!(symbols[1], symbols[2]);
+/// assert_eq!(handles.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SvgHandles {
+    config: SVGConfig,
+    handles: Vec<(Mana, Handle)>,
+}
+
+impl SvgHandles {
+    /// An empty cache, which draws symbols using `config`.
+    #[must_use]
+    pub const fn new(config: SVGConfig) -> Self {
+        Self { config, handles: Vec::new() }
+    }
+
+    /// The handle of `mana`.
+    pub fn get(&mut self, mana: &Mana) -> Handle {
+        if let Some((_, handle)) = self.handles.iter().find(|x| x.0 == *mana) {
+            return handle.clone();
+        }
+        let handle = svg_to_handle(&mana.as_svg(&self.config));
+        self.handles.push((*mana, handle.clone()));
+        handle
+    }
+
+    /// The handles of every symbol in `manas`, in order.
+    pub fn get_all(&mut self, manas: &Manas) -> Vec<Handle> {
+        manas.manas.iter().map(|mana| self.get(mana)).collect()
+    }
+
+    /// The number of cached handles.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Whether no handles have been cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Remove every cached handle.
+    pub fn clear(&mut self) {
+        self.handles.clear();
+    }
+}
+
+pub(crate) fn svg_to_handle(svg: &SVG) -> Handle {
+    Handle::from_memory(canonical_svg(svg).into_bytes())
+}
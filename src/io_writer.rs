@@ -0,0 +1,43 @@
+use std::{fmt, io};
+
+/// Lets functions which write to a [`fmt::Write`] write to an [`io::Write`]
+/// instead, keeping the [`io::Error`] which caused writing to fail.
+pub(crate) struct IoWriter<'a, W> {
+    inner: &'a mut W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+/// Call `write` with a [`fmt::Write`] which writes to `output`.
+pub(crate) fn write_io<W: io::Write>(
+    output: &mut W,
+    write: impl FnOnce(&mut IoWriter<'_, W>) -> fmt::Result,
+) -> io::Result<()> {
+    let mut writer = IoWriter { inner: output, error: None };
+    write(&mut writer).map_err(|fmt::Error| {
+        writer.error.take().unwrap_or_else(|| io::Error::other("formatter error"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Write;
+
+    use super::*;
+
+    #[test]
+    fn keeps_error() {
+        let mut output = [0u8; 4];
+        let result = write_io(&mut &mut output[..], |output| write!(output, "too long"));
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::WriteZero);
+        assert_eq!(&output, b"too ");
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt::{self, Write};
+
+use crate::{
+    GenericMana, Mana, SingleMana, SplitMana,
+    color::{HEX_C, hex_to_rgb},
+};
+
+/// The radius of a symbol, relative to the current font size.
+const RADIUS: &str = "0.5em";
+
+/// The text of a phyrexian symbol.
+const PHYREXIAN: &str = r"\ensuremath{\phi}";
+
+/// Write `mana` as a TikZ picture, which is a colored circle with the text of
+/// the symbol on top. Hybrid symbols are split diagonally into two halves.
+pub(crate) fn write_latex<W: Write>(output: &mut W, mana: &Mana) -> fmt::Result {
+    let colorless = hex_to_rgb(HEX_C);
+    write!(output, r"\tikz[baseline=-0.6ex]{{")?;
+    match mana {
+        Mana::Single(SingleMana::Normal(color)) => {
+            write_circle(output, color.rgb(), &color.char().to_string())?;
+        }
+        Mana::Single(SingleMana::Phyrexian(color)) => {
+            write_circle(output, color.rgb(), PHYREXIAN)?;
+        }
+        Mana::Single(SingleMana::Half(color)) => {
+            write_half_circle(output, color.rgb(), &color.char().to_string())?;
+        }
+        Mana::Generic(GenericMana::Number(n)) => write_circle(output, colorless, &n.to_string())?,
+        Mana::Generic(GenericMana::X) => write_circle(output, colorless, "X")?,
+        Mana::Generic(GenericMana::Y) => write_circle(output, colorless, "Y")?,
+        Mana::Generic(GenericMana::Z) => write_circle(output, colorless, "Z")?,
+        Mana::Generic(GenericMana::Infinity) => {
+            write_circle(output, colorless, r"\ensuremath{\infty}")?;
+        }
+        Mana::Split(SplitMana::Colorless { color }) => {
+            write_halves(output, (colorless, "C"), (color.rgb(), &color.char().to_string()))?;
+        }
+        Mana::Split(SplitMana::Mono { value, color }) => {
+            let a = value.to_string();
+            write_halves(output, (colorless, &a), (color.rgb(), &color.char().to_string()))?;
+        }
+        Mana::Split(SplitMana::Duo { a, b, phyrexian }) => {
+            let (text_a, text_b) = if *phyrexian {
+                (PHYREXIAN.to_string(), PHYREXIAN.to_string())
+            } else {
+                (a.char().to_string(), b.char().to_string())
+            };
+            write_halves(output, (a.rgb(), &text_a), (b.rgb(), &text_b))?;
+        }
+        Mana::Colorless => write_circle(output, colorless, "C")?,
+        Mana::Snow => write_circle(output, colorless, r"\ensuremath{\ast}")?,
+        Mana::Custom(color) => write_circle(output, colorless, &color.letter().to_string())?,
+    }
+    write!(output, "}}")
+}
+
+fn write_circle<W: Write>(output: &mut W, rgb: (u8, u8, u8), text: &str) -> fmt::Result {
+    write!(output, r"\fill[fill={}] (0,0) circle ({RADIUS});", Fill(rgb))?;
+    write!(output, r"\node[font=\scriptsize\bfseries] at (0,0) {{{text}}};")
+}
+
+/// The left half of a circle, with the text in its middle.
+fn write_half_circle<W: Write>(output: &mut W, rgb: (u8, u8, u8), text: &str) -> fmt::Result {
+    write!(
+        output,
+        r"\fill[fill={}] (0,0) -- (90:{RADIUS}) arc (90:270:{RADIUS}) -- cycle;",
+        Fill(rgb)
+    )?;
+    write!(output, r"\node[font=\tiny\bfseries] at (-0.25em,0) {{{text}}};")
+}
+
+fn write_halves<W: Write>(
+    output: &mut W,
+    (rgb_a, text_a): ((u8, u8, u8), &str),
+    (rgb_b, text_b): ((u8, u8, u8), &str),
+) -> fmt::Result {
+    write!(
+        output,
+        r"\fill[fill={}] (0,0) -- (45:{RADIUS}) arc (45:225:{RADIUS}) -- cycle;",
+        Fill(rgb_a)
+    )?;
+    write!(
+        output,
+        r"\fill[fill={}] (0,0) -- (225:{RADIUS}) arc (225:405:{RADIUS}) -- cycle;",
+        Fill(rgb_b)
+    )?;
+    write!(output, r"\node[font=\tiny\bfseries] at (135:0.25em) {{{text_a}}};")?;
+    write!(output, r"\node[font=\tiny\bfseries] at (-45:0.25em) {{{text_b}}};")
+}
+
+/// A color in the syntax of `xcolor`.
+struct Fill((u8, u8, u8));
+
+impl fmt::Display for Fill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (red, green, blue) = self.0;
+        write!(f, "{{rgb,255:red,{red};green,{green};blue,{blue}}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn latex(mana: &str) -> String {
+        let mut out = String::new();
+        write_latex(&mut out, &mana.parse().unwrap()).unwrap();
+        out
+    }
+
+    #[test]
+    fn symbols() {
+        assert_eq!(
+            latex("U"),
+            r"\tikz[baseline=-0.6ex]{\fill[fill={rgb,255:red,170;green,224;blue,250}] (0,0) circle (0.5em);\node[font=\scriptsize\bfseries] at (0,0) {U};}"
+        );
+        let hybrid = latex("W/U/P");
+        assert!(hybrid.contains(r"arc (45:225:0.5em)"));
+        assert_eq!(hybrid.matches(PHYREXIAN).count(), 2);
+    }
+}
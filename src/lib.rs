@@ -11,6 +11,50 @@
 //! - [Colored][mw:c] mana (including [phyrexian][mw:pm])
 //! - [Hybrid mana][mw:hm] (including generic, colorless and phyrexian)
 //! - [Snow mana][mw:sc]
+//! - Half mana and infinite generic mana from Un-sets (e.g. `{HW}` and `{∞}`)
+//!
+//! Symbols of rules text which are not mana are supported by [`Symbol`]: the
+//! [tap and untap symbols][mw:tap] (`{T}` and `{Q}`), the energy symbol
+//! (`{E}`), Unfinity's ticket symbol (`{TK}`), the chaos symbol (`{CHAOS}`)
+//! and loyalty costs (e.g. `[+1]`). [`Manas`] only contains mana, so these can
+//! not be part of a mana cost.
+//!
+//! ## Optional features
+//!
+//! - `svg` (enabled by default): Render mana symbols as SVG and HTML. Every
+//!   other way of rendering symbols also enables this feature. Without it,
+//!   mana costs can still be parsed, sorted and written as text.
+//! - `wasm`: Bindings for JavaScript using [`wasm-bindgen`], which export
+//!   the class `Manas` to parse, sort and render mana costs in the browser.
+//! - `png`: Render mana symbols as PNG images, using `Mana::as_png` and
+//!   `Manas::as_png`, and as HTML `<picture>` elements with a PNG fallback,
+//!   using `HtmlOptions::picture`.
+//! - `image`: Render mana symbols as an `RgbaImage` of the [`image`] crate,
+//!   using `Mana::as_rgba_image` and `Manas::as_rgba_image`.
+//! - `svgz`: Compress SVGs using gzip, using `Mana::as_svgz` and
+//!   `Manas::as_svgz`.
+//! - `pdf`: Render mana costs as vector graphics in PDFs, with an exact
+//!   physical size, using `Manas::as_pdf` and `Manas::write_pdf_xobject`.
+//! - `egui`: Draw mana symbols in [egui] without rasterizing them, using
+//!   `Mana::as_egui_mesh` and `Manas::as_egui_mesh`.
+//! - `iced`: Show mana symbols in [iced] using `Manas::as_iced_handle`, or
+//!   one symbol at a time using a cache of handles, `SvgHandles`.
+//! - `maud`: Implement `maud::Render` for [`Mana`] and [`Manas`], so that they
+//!   can be used directly in [`maud`] templates.
+//! - `tera` and `handlebars`: Helpers `mana_html` and `mana_svg` for [Tera] and
+//!   [Handlebars] templates, registered using `register_tera_filters` and
+//!   `register_handlebars_helpers`.
+//! - `askama`: The same filters for [Askama] templates, in the module
+//!   `askama_filters`.
+//!
+//! [`wasm-bindgen`]: https://docs.rs/wasm-bindgen
+//! [`image`]: https://docs.rs/image
+//! [`maud`]: https://maud.lambda.xyz
+//! [egui]: https://www.egui.rs
+//! [iced]: https://iced.rs
+//! [Tera]: https://keats.github.io/tera
+//! [Handlebars]: https://docs.rs/handlebars
+//! [Askama]: https://askama.rs
 //!
 //! [mw:mc]:  https://mtg.wiki/page/Mana_cost
 //! [mw:mv]:  https://mtg.wiki/page/Mana_value
@@ -20,6 +64,7 @@
 //! [mw:c]:   https://mtg.wiki/page/Color
 //! [mw:hm]:  https://mtg.wiki/page/Hybrid_mana
 //! [mw:sc]:  https://mtg.wiki/page/Snow#Snow_costs
+//! [mw:tap]: https://mtg.wiki/page/Tap
 //!
 //! [wp:mtg]:  https://en.wikipedia.org/wiki/Magic:_The_Gathering
 //! [wp:wotc]: https://en.wikipedia.org/wiki/Wizards_of_the_Coast
@@ -27,23 +72,135 @@
 //! [reddit:user]: https://www.reddit.com/user/Mean-Government1436
 //! [reddit:post]: https://www.reddit.com/r/custommagic/comments/1nhtr3w/guide_for_formatting_mana_costs/
 
+#[cfg(feature = "svg")]
+mod artwork;
+#[cfg(feature = "svg")]
+mod asset_writer;
+#[cfg(feature = "svg")]
+mod canonical;
 mod color;
+mod color_combination;
 mod color_set;
+mod color_set_display;
+#[cfg(feature = "svg")]
+mod compatible;
+mod custom_color;
+mod display_options;
+#[cfg(feature = "egui")]
+mod egui_mesh;
+mod emoji_map;
+#[cfg(feature = "svg")]
+mod export;
 mod generic_mana;
+#[cfg(feature = "svg")]
+mod html_options;
+mod html_spans;
+#[cfg(feature = "iced")]
+mod iced_handles;
+#[cfg(feature = "svg")]
+mod io_writer;
+mod latex;
+mod loyalty_cost;
 mod mana;
+mod mana_groups;
+mod mana_pool;
+mod mana_value;
+mod mana_visitor;
 mod manas;
+mod manas_builder;
+#[cfg(feature = "maud")]
+mod maud_render;
+#[cfg(feature = "svg")]
+mod minify;
+#[cfg(feature = "svg")]
+mod palette;
+#[cfg(feature = "svg")]
+mod path_segment;
+mod payment;
+#[cfg(feature = "pdf")]
+mod pdf;
+mod pip_policy;
+#[cfg(any(feature = "png", feature = "image"))]
+mod raster;
+#[cfg(feature = "svg")]
+mod render;
 mod single_mana;
+mod sort_style;
 mod split_mana;
+#[cfg(feature = "svg")]
 mod svg_config;
+#[cfg(any(feature = "png", feature = "image", feature = "pdf", feature = "egui"))]
+mod svg_tree;
+#[cfg(feature = "svgz")]
+mod svgz;
+mod symbol;
+#[cfg(feature = "svg")]
 mod symbols;
+#[cfg(any(feature = "tera", feature = "handlebars", feature = "askama"))]
+mod templates;
+#[cfg(feature = "svg")]
+mod theme;
+#[cfg(feature = "svg")]
+mod typst;
+mod unicode;
+mod violation;
+#[cfg(feature = "wasm")]
+mod wasm;
 
+#[cfg(feature = "svg")]
+pub use artwork::Artwork;
+#[cfg(feature = "svg")]
+pub use asset_writer::AssetWriter;
+#[cfg(feature = "svg")]
+pub use canonical::{canonical_svg, write_canonical_svg};
 pub use color::Color;
+pub use color_combination::{Guild, Shard, Wedge};
+pub use color_set::ColorSet;
+pub use color_set_display::{ColorSetDisplay, ColorSetStyle};
+pub use custom_color::{ColorRegistry, CustomColor};
+pub use display_options::{BraceStyle, DisplayOptions, ManasDisplay, PhyrexianStyle};
+#[cfg(feature = "egui")]
+pub use egui_mesh::SymbolMesh;
+pub use emoji_map::{EmojiMap, SlackEmojiMap};
+#[cfg(feature = "svg")]
+pub use export::{ExportOptions, export_symbols, stylesheet, write_stylesheet};
 pub(crate) use generic_mana::GenericMana;
+#[cfg(feature = "svg")]
+pub use html_options::HtmlOptions;
+#[cfg(feature = "iced")]
+pub use iced_handles::SvgHandles;
+pub use loyalty_cost::LoyaltyCost;
 pub use mana::Mana;
+pub use mana_groups::ManaGroups;
+pub use mana_pool::{ManaPool, ManaUnit};
+pub use mana_value::{FractionalManaValue, ManaValue};
+pub use mana_visitor::ManaVisitor;
 pub use manas::Manas;
+pub use manas_builder::{ManasBuilder, ManasBuilderError};
+#[cfg(feature = "svg")]
+pub use palette::Palette;
+#[cfg(feature = "svg")]
+pub use path_segment::PathSegment;
+pub use payment::{Payment, PaymentObjective, SymbolPayment};
+pub use pip_policy::PipPolicy;
 pub(crate) use single_mana::SingleMana;
+pub use sort_style::SortStyle;
 pub(crate) use split_mana::SplitMana;
-pub use svg_config::SVGConfig;
+#[cfg(feature = "svg")]
+pub use svg_config::{DropShadow, SVGConfig, ShapeRendering, Stroke};
+pub use symbol::{Symbol, TextPart};
+#[cfg(feature = "askama")]
+pub use templates::askama_filters;
+#[cfg(feature = "handlebars")]
+pub use templates::register_handlebars_helpers;
+#[cfg(feature = "tera")]
+pub use templates::register_tera_filters;
+#[cfg(feature = "svg")]
+pub use theme::{Fill, Foil, Glyph, Monochrome, Plate, Theme};
+pub use violation::Violation;
+#[cfg(feature = "wasm")]
+pub use wasm::JsManas;
 
+#[cfg(feature = "svg")]
 /// Each SVG is defined using coordinates in [0, 32.0]^2
 const SVG_WIDTH: f64 = 32.0;
@@ -0,0 +1,137 @@
+use std::fmt::Display;
+
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    character::complete::{char, digit1},
+    combinator::value,
+    sequence::{delimited, preceded},
+};
+#[cfg(feature = "svg")]
+use svg::{
+    Document,
+    node::element::{Path, SVG},
+};
+
+#[cfg(feature = "svg")]
+use crate::{
+    Glyph, SVG_WIDTH, SVGConfig, compatible::make_compatible, mana::with_description,
+    minify::minify, symbols::glyph_symbol,
+};
+
+/// The [loyalty cost](https://mtg.wiki/page/Loyalty_ability) of a
+/// planeswalker ability, written in brackets, e.g. `[+1]` or `[-X]`.
+///
+/// `−` (U+2212) is also accepted as a minus sign, but `-` is always written.
+///
+/// ```
+/// use mana_symbols::{LoyaltyCost, Symbol};
+///
+/// let symbol: Symbol = "[−3]".parse().unwrap();
+/// assert_eq!(symbol, Symbol::Loyalty(LoyaltyCost::Minus(3)));
+/// assert_eq!(symbol.to_string(), "[-3]");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoyaltyCost {
+    /// Adding loyalty counters, `[+N]`.
+    Plus(usize),
+    /// Removing loyalty counters, `[-N]`.
+    Minus(usize),
+    /// Removing a variable amount of loyalty counters, `[-X]`.
+    MinusX,
+    /// Neither adding nor removing loyalty counters, `[0]`.
+    Zero,
+}
+
+impl Display for LoyaltyCost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Plus(n) => write!(f, "[+{n}]"),
+            Self::Minus(n) => write!(f, "[-{n}]"),
+            Self::MinusX => f.write_str("[-X]"),
+            Self::Zero => f.write_str("[0]"),
+        }
+    }
+}
+
+impl LoyaltyCost {
+    /// Parse a `LoyaltyCost`, including its brackets, using [`nom`].
+    pub fn parse(input: &str) -> IResult<&str, Self> {
+        let number = || digit1.map_res(str::parse::<usize>);
+        let plus = preceded(char('+'), number()).map(Self::Plus);
+        let minus = preceded(
+            alt((char('-'), char('−'))),
+            alt((number().map(Self::Minus), value(Self::MinusX, char('X')))),
+        );
+        let zero = value(Self::Zero, char('0'));
+        delimited(char('['), alt((plus, minus, zero)), char(']')).parse(input)
+    }
+
+    #[cfg(feature = "svg")]
+    pub(crate) fn name(&self) -> String {
+        match self {
+            Self::Plus(n) => format!("Plus {n} loyalty"),
+            Self::Minus(n) => format!("Minus {n} loyalty"),
+            Self::MinusX => "Minus X loyalty".to_string(),
+            Self::Zero => "Zero loyalty".to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "svg")]
+impl LoyaltyCost {
+    /// Display the loyalty cost as an [SVG](https://en.wikipedia.org/wiki/SVG)
+    /// of a black shield with a white number, which points up when adding
+    /// loyalty and down when removing it. Only the size, margin and
+    /// `shape-rendering` of `config` are used.
+    #[must_use]
+    pub fn as_svg(&self, config: &SVGConfig) -> SVG {
+        let margin = config.margin();
+        let width = 2.0f64.mul_add(margin, SVG_WIDTH);
+        // Subtracting avoids writing `-0` when there is no margin
+        let origin = 0.0 - margin;
+        let mut document = Document::new().set("viewBox", (origin, origin, width, width));
+        if let Some((width, height)) = config.size(1.0) {
+            document = document.set("width", width).set("height", height);
+        }
+        if let Some(shape_rendering) = config.shape_rendering {
+            document = document.set("shape-rendering", shape_rendering.to_string());
+        }
+        document = with_description(document, &self.name(), &self.to_string());
+
+        let shield = match self {
+            Self::Plus(_) => "M16 1L30 8L30 31L2 31L2 8z",
+            Self::Minus(_) | Self::MinusX => "M2 1L30 1L30 24L16 31L2 24z",
+            Self::Zero => "M5 1L27 1L30 4L30 28L27 31L5 31L2 28L2 4z",
+        };
+        document = document.add(Path::new().set("fill", "black").set("d", shield));
+
+        let (glyph, sign) = match self {
+            Self::Plus(n) => (Glyph::Number(*n), Some("M5 16.5h6v2h-6zM7 14.5h2v6h-2z")),
+            Self::Minus(n) => (Glyph::Number(*n), Some("M5 13.5h6v2h-6z")),
+            Self::MinusX => (Glyph::Variable('X'), Some("M5 13.5h6v2h-6z")),
+            Self::Zero => (Glyph::Number(0), None),
+        };
+        // The number is moved to the right of the sign, and away from the
+        // point of the shield
+        let (x, y) = match self {
+            Self::Plus(_) => (11.5, 9.5),
+            Self::Minus(_) | Self::MinusX => (11.5, 6.5),
+            Self::Zero => (8.0, 8.0),
+        };
+        if let Some(sign) = sign {
+            document = document.add(Path::new().set("fill", "white").set("d", sign));
+        }
+        if let Some(symbol) = glyph_symbol(glyph) {
+            let symbol = symbol
+                .set("fill", "white")
+                .set("x", x)
+                .set("y", y)
+                .set("width", SVG_WIDTH / 2.0)
+                .set("height", SVG_WIDTH / 2.0);
+            document = document.add(symbol);
+        }
+
+        make_compatible(minify(document, config), config)
+    }
+}
@@ -1,34 +1,54 @@
 use std::{
-    f64,
+    cmp::Ordering,
     fmt::{Display, Write},
+    iter::Sum,
     str::FromStr,
 };
+#[cfg(feature = "svg")]
+use std::{f64, io};
 
+#[cfg(feature = "svg")]
 use base64::{Engine, prelude::BASE64_STANDARD};
 use nom::{
     Finish, IResult, Parser,
     branch::alt,
     character::complete::char,
     combinator::{eof, value},
-    sequence::{delimited, terminated},
+    sequence::{delimited, preceded, terminated},
 };
+#[cfg(feature = "svg")]
 use svg::{
     Document,
-    node::element::{Circle, Group, Mask, Path, SVG, path::Data},
+    node::{
+        Text as TextNode,
+        element::{
+            AnimateTransform, Circle, Definitions, Description, Filter, FilterEffectDropShadow,
+            Group, LinearGradient, Mask, Path, SVG, Stop, Title, path::Data,
+        },
+    },
 };
 
+#[cfg(feature = "svg")]
 use crate::{
-    Color, GenericMana, SVG_WIDTH, SVGConfig, SingleMana, SplitMana,
-    color::HEX_C,
-    symbols::{
-        color_symbol, colorless_symbol, number_symbol, phyrexian_symbol, snow_symbol, x_symbol,
-        y_symbol, z_symbol,
-    },
+    AssetWriter, DropShadow, Fill, Foil, Glyph, HtmlOptions, Palette, PathSegment, SVG_WIDTH,
+    SVGConfig, Symbol, Theme, canonical_svg,
+    compatible::make_compatible,
+    html_options::write_escaped,
+    io_writer::write_io,
+    minify::minify,
+    symbols::{glyph_segments, glyph_symbol},
+    write_canonical_svg,
+};
+use crate::{
+    Color, ColorRegistry, CustomColor, GenericMana, ManaValue, ManaVisitor, SingleMana, SplitMana,
+    Violation, color::ALL_COLORS, html_spans::write_html_span, unicode::write_unicode,
 };
 
 /// A mana symbol
 ///
 /// Any symbol that could be used as part of a [mana cost](https://mtg.wiki/page/Mana_cost).
+/// Symbols in costs which are not mana, like the tap symbol, are
+/// [`Symbol`](crate::Symbol)s instead.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Mana {
     Single(SingleMana),
@@ -36,6 +56,9 @@ pub enum Mana {
     Split(SplitMana),
     Colorless,
     Snow,
+    /// Mana of a [custom color](CustomColor), which can only be parsed using a
+    /// [`ColorRegistry`] (see [`Mana::parse_with`]).
+    Custom(CustomColor),
 }
 
 impl Display for Mana {
@@ -46,6 +69,7 @@ impl Display for Mana {
             Self::Split(split_mana) => split_mana.fmt(f),
             Self::Colorless => f.write_char('C'),
             Self::Snow => f.write_char('S'),
+            Self::Custom(color) => color.fmt(f),
         }
     }
 }
@@ -63,18 +87,241 @@ impl FromStr for Mana {
     }
 }
 
+/// Mana symbols are ordered by the groups used by [`Manas::sort`]
+/// (generic, hybrid generic, colorless, hybrid colorless, colored and snow).
+/// Within each group, numbers are ordered by value and colors are ordered as
+/// `W`, `U`, `B`, `R`, `G`, followed by custom colors ordered by letter.
+///
+/// [`Manas::sort`]: crate::Manas::sort
+impl Ord for Mana {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.order_key().cmp(&other.order_key())
+    }
+}
+
+impl PartialOrd for Mana {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The total [mana value](Mana::mana_value).
+///
+/// ```
+/// use mana_symbols::Mana;
+///
+/// let manas: Vec<Mana> = vec!["2".parse().unwrap(), "U".parse().unwrap()];
+/// let total: usize = manas.iter().sum();
+/// assert_eq!(total, 3);
+/// ```
+impl Sum<Mana> for usize {
+    fn sum<I: Iterator<Item = Mana>>(iter: I) -> Self {
+        iter.map(|x| x.mana_value()).fold(0, usize::saturating_add)
+    }
+}
+
+/// The total [mana value](Mana::mana_value).
+impl<'a> Sum<&'a Mana> for usize {
+    fn sum<I: Iterator<Item = &'a Mana>>(iter: I) -> Self {
+        iter.map(Mana::mana_value).fold(0, usize::saturating_add)
+    }
+}
+
+/// The total [mana value](Mana::exact_mana_value).
+impl Sum<Mana> for ManaValue {
+    fn sum<I: Iterator<Item = Mana>>(iter: I) -> Self {
+        iter.map(|x| x.exact_mana_value()).sum()
+    }
+}
+
+/// The total [mana value](Mana::exact_mana_value).
+impl<'a> Sum<&'a Mana> for ManaValue {
+    fn sum<I: Iterator<Item = &'a Mana>>(iter: I) -> Self {
+        iter.map(Mana::exact_mana_value).sum()
+    }
+}
+
 impl Mana {
-    /// The [mana value](https://mtg.wiki/page/Mana_value).
+    /// The [mana value](https://mtg.wiki/page/Mana_value). Half mana (e.g.
+    /// `{HW}`) is rounded down to 0, see [`Mana::exact_mana_value`], and `{∞}`
+    /// has a mana value of `usize::MAX`.
     #[must_use]
     pub const fn mana_value(&self) -> usize {
         match self {
             Self::Generic(GenericMana::Number(v)) => *v,
-            Self::Generic(GenericMana::X | GenericMana::Y | GenericMana::Z) => 0,
+            Self::Generic(GenericMana::Infinity) => usize::MAX,
+            Self::Generic(GenericMana::X | GenericMana::Y | GenericMana::Z)
+            | Self::Single(SingleMana::Half(_)) => 0,
             Self::Split(SplitMana::Mono { value, .. }) => *value,
             Self::Split(SplitMana::Duo { .. } | SplitMana::Colorless { .. })
             | Self::Single { .. }
             | Self::Colorless
-            | Self::Snow => 1,
+            | Self::Snow
+            | Self::Custom(_) => 1,
+        }
+    }
+
+    /// Approximate the mana symbol with Unicode characters, as a compact
+    /// fallback for plain text. Colors and generic mana use circled letters
+    /// and numbers (`Ⓤ`, `②`), snow mana uses `❄` and phyrexian mana uses
+    /// `Φ`. The halves of hybrid and phyrexian mana are separated by `/`.
+    ///
+    /// Generic mana above 50 has no circled number, so it is written in
+    /// parentheses, e.g. `(100)`.
+    ///
+    /// ```
+    /// use mana_symbols::Mana;
+    ///
+    /// let u: Mana = "U".parse().unwrap();
+    /// let two_w: Mana = "2/W".parse().unwrap();
+    /// let up: Mana = "U/P".parse().unwrap();
+    ///
+    /// assert_eq!(u.to_unicode(), "Ⓤ");
+    /// assert_eq!(two_w.to_unicode(), "②/Ⓦ");
+    /// assert_eq!(up.to_unicode(), "Ⓤ/Φ");
+    /// ```
+    #[must_use]
+    pub fn to_unicode(&self) -> String {
+        let mut out = String::new();
+        write_unicode(&mut out, self).unwrap();
+        out
+    }
+
+    /// Show the mana symbol as [HTML](https://en.wikipedia.org/wiki/HTML)
+    /// without any images, as a colored circle containing the text of the
+    /// symbol, using the default colors. Meant for places which remove images,
+    /// e.g. some e-mail clients and RSS readers, so all styles are inline.
+    ///
+    /// ```
+    /// use mana_symbols::Mana;
+    ///
+    /// let mana: Mana = "U".parse().unwrap();
+    /// let html = mana.to_html_span();
+    /// assert!(html.starts_with(r#"<span title="Blue mana""#));
+    /// assert!(html.ends_with(">U</span>"));
+    /// ```
+    #[must_use]
+    pub fn to_html_span(&self) -> String {
+        let mut out = String::new();
+        write_html_span(&mut out, self).unwrap();
+        out
+    }
+
+    /// Whether the mana symbol has been printed on a card, i.e. whether it is
+    /// one of [`Mana::all_printed`].
+    ///
+    /// ```
+    /// use mana_symbols::Mana;
+    ///
+    /// let wu: Mana = "W/U".parse().unwrap();
+    /// let uw: Mana = "U/W".parse().unwrap();
+    /// let three_w: Mana = "3/W".parse().unwrap();
+    ///
+    /// assert!(wu.is_printed());
+    /// assert!(!uw.is_printed());
+    /// assert!(!three_w.is_printed());
+    /// ```
+    #[must_use]
+    pub const fn is_printed(&self) -> bool {
+        match self {
+            Self::Generic(GenericMana::Number(n)) => *n <= 20 || *n == 100 || *n == 1_000_000,
+            Self::Split(SplitMana::Mono { value, .. }) => *value == 2,
+            Self::Split(SplitMana::Duo { a, b, .. }) => {
+                *b as usize == a.next(1) as usize || *b as usize == a.next(2) as usize
+            }
+            Self::Single(SingleMana::Half(color)) => {
+                matches!(color, Color::White | Color::Red)
+            }
+            Self::Single(SingleMana::Normal(_) | SingleMana::Phyrexian(_))
+            | Self::Generic(
+                GenericMana::X | GenericMana::Y | GenericMana::Z | GenericMana::Infinity,
+            )
+            | Self::Split(SplitMana::Colorless { .. })
+            | Self::Colorless
+            | Self::Snow => true,
+            Self::Custom(_) => false,
+        }
+    }
+
+    /// The [mana value](https://mtg.wiki/page/Mana_value) as a [`ManaValue`],
+    /// which can represent fractional mana values.
+    #[must_use]
+    pub const fn exact_mana_value(&self) -> ManaValue {
+        match self {
+            Self::Single(SingleMana::Half(_)) => ManaValue::from_halves(1),
+            Self::Generic(GenericMana::Infinity) => ManaValue::INFINITY,
+            _ => ManaValue::from_halves(self.mana_value().saturating_mul(2)),
+        }
+    }
+
+    /// Every mana symbol which has been printed on a card, i.e.
+    /// - Colored mana and phyrexian colored mana
+    /// - Hybrid mana of every pair of colors, both normal and phyrexian (with
+    ///   normalized sides, see [`Mana::normalize_hybrid`])
+    /// - Hybrid generic mana and hybrid colorless mana of every color
+    /// - Generic mana from `{0}` to `{20}`, `{100}`, `{1000000}` and `{∞}`,
+    ///   and the variables `{X}`, `{Y}` and `{Z}`
+    /// - Colorless mana and snow mana
+    /// - Half white and half red mana, `{HW}` and `{HR}`, from Un-sets
+    ///
+    /// ```
+    /// use mana_symbols::Mana;
+    ///
+    /// let u: Mana = "U".parse().unwrap();
+    /// assert!(Mana::all_printed().any(|x| x == u));
+    /// ```
+    pub fn all_printed() -> impl Iterator<Item = Self> {
+        let variables = [GenericMana::X, GenericMana::Y, GenericMana::Z];
+        let numbers = (0..=20)
+            .chain([100, 1_000_000])
+            .map(GenericMana::Number)
+            .chain([GenericMana::Infinity]);
+        let generic = variables.into_iter().chain(numbers).map(Self::Generic);
+
+        let generic_hybrid =
+            ALL_COLORS.map(|color| Self::Split(SplitMana::Mono { value: 2, color }));
+        let colorless_hybrid = ALL_COLORS.map(|color| Self::Split(SplitMana::Colorless { color }));
+
+        let colored = ALL_COLORS.into_iter().flat_map(|a| {
+            let single = [SingleMana::Normal(a), SingleMana::Phyrexian(a)].map(Self::Single);
+            let hybrid = [1, 2].into_iter().flat_map(move |i| {
+                let b = a.next(i);
+                [false, true].map(|phyrexian| Self::Split(SplitMana::Duo { a, b, phyrexian }))
+            });
+            single.into_iter().chain(hybrid)
+        });
+
+        generic
+            .chain(generic_hybrid)
+            .chain([Self::Colorless])
+            .chain(colorless_hybrid)
+            .chain(colored)
+            .chain([Color::White, Color::Red].map(|color| Self::Single(SingleMana::Half(color))))
+            .chain([Self::Snow])
+    }
+
+    /// Call the method of `visitor` corresponding to the kind of this mana
+    /// symbol (see [`ManaVisitor`]).
+    pub fn accept<V: ManaVisitor + ?Sized>(&self, visitor: &mut V) -> V::Output {
+        match *self {
+            Self::Single(SingleMana::Normal(color)) => visitor.visit_colored(color),
+            Self::Single(SingleMana::Phyrexian(color)) => visitor.visit_phyrexian(color),
+            Self::Single(SingleMana::Half(color)) => visitor.visit_half(color),
+            Self::Generic(GenericMana::Number(value)) => visitor.visit_generic(value),
+            Self::Generic(GenericMana::X) => visitor.visit_variable('X'),
+            Self::Generic(GenericMana::Y) => visitor.visit_variable('Y'),
+            Self::Generic(GenericMana::Z) => visitor.visit_variable('Z'),
+            Self::Generic(GenericMana::Infinity) => visitor.visit_infinity(),
+            Self::Split(SplitMana::Mono { value, color }) => {
+                visitor.visit_generic_hybrid(value, color)
+            }
+            Self::Split(SplitMana::Colorless { color }) => visitor.visit_colorless_hybrid(color),
+            Self::Split(SplitMana::Duo { a, b, phyrexian }) => {
+                visitor.visit_hybrid(a, b, phyrexian)
+            }
+            Self::Colorless => visitor.visit_colorless(),
+            Self::Snow => visitor.visit_snow(),
+            Self::Custom(color) => visitor.visit_custom(color),
         }
     }
 
@@ -83,7 +330,45 @@ impl Mana {
     pub const fn normalize_hybrid(&mut self) {
         match self {
             Self::Split(split_mana) => split_mana.normalize(),
-            Self::Single(_) | Self::Generic(_) | Self::Colorless | Self::Snow => {}
+            Self::Single(_) | Self::Generic(_) | Self::Colorless | Self::Snow | Self::Custom(_) => {
+            }
+        }
+    }
+
+    /// Check that the mana symbol is structurally valid.
+    ///
+    /// ```
+    /// use mana_symbols::{Color, Mana, Violation};
+    ///
+    /// let wu: Mana = "W/U".parse().unwrap();
+    /// let uw: Mana = "U/W".parse().unwrap();
+    /// let ww: Mana = "W/W".parse().unwrap();
+    ///
+    /// assert_eq!(wu.validate(), Ok(()));
+    /// assert_eq!(uw.validate(), Err(Violation::NonCanonicalHybrid(wu)));
+    /// assert_eq!(ww.validate(), Err(Violation::DuplicateHybridColor(Color::White)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason the mana symbol is not valid.
+    pub fn validate(&self) -> Result<(), Violation> {
+        match self {
+            Self::Split(SplitMana::Duo { a, b, .. }) if a == b => {
+                Err(Violation::DuplicateHybridColor(*a))
+            }
+            Self::Split(SplitMana::Mono { value: 0, color }) => {
+                Err(Violation::ZeroGenericHybrid(*color))
+            }
+            _ => {
+                let mut normalized = *self;
+                normalized.normalize_hybrid();
+                if normalized == *self {
+                    Ok(())
+                } else {
+                    Err(Violation::NonCanonicalHybrid(normalized))
+                }
+            }
         }
     }
 
@@ -105,7 +390,7 @@ impl Mana {
         match self {
             Self::Single(single_mana) => Some(single_mana.color()),
             Self::Split(split_mana) => split_mana.left_half_color(),
-            Self::Generic(_) | Self::Colorless | Self::Snow => None,
+            Self::Generic(_) | Self::Colorless | Self::Snow | Self::Custom(_) => None,
         }
     }
 
@@ -127,11 +412,34 @@ impl Mana {
         match self {
             Self::Single(single_mana) => Some(single_mana.color()),
             Self::Split(split_mana) => Some(split_mana.right_half_color()),
-            Self::Generic(_) | Self::Colorless | Self::Snow => None,
+            Self::Generic(_) | Self::Colorless | Self::Snow | Self::Custom(_) => None,
+        }
+    }
+
+    const fn order_key(&self) -> (u8, usize, Option<Color>, u8, Option<Color>) {
+        // Custom colors come after the colors of the color pie, but have no
+        // `Color`, so we order them by letter instead
+        match self {
+            Self::Generic(GenericMana::X) => (0, 0, None, 0, None),
+            Self::Generic(GenericMana::Y) => (1, 0, None, 0, None),
+            Self::Generic(GenericMana::Z) => (2, 0, None, 0, None),
+            Self::Generic(GenericMana::Infinity) => (3, 0, None, 0, None),
+            Self::Generic(GenericMana::Number(n)) => (4, *n, None, 0, None),
+            Self::Split(SplitMana::Mono { value, color }) => (5, *value, Some(*color), 0, None),
+            Self::Colorless => (6, 0, None, 0, None),
+            Self::Split(SplitMana::Colorless { color }) => (7, 0, Some(*color), 0, None),
+            Self::Single(SingleMana::Normal(color)) => (8, 0, Some(*color), 0, None),
+            Self::Single(SingleMana::Half(color)) => (8, 0, Some(*color), 1, None),
+            Self::Single(SingleMana::Phyrexian(color)) => (8, 0, Some(*color), 2, None),
+            Self::Split(SplitMana::Duo { a, b, phyrexian }) => {
+                (8, 0, Some(*a), if *phyrexian { 4 } else { 3 }, Some(*b))
+            }
+            Self::Custom(color) => (9, color.letter() as usize, None, 0, None),
+            Self::Snow => (10, 0, None, 0, None),
         }
     }
 
-    fn parse_inner(input: &str) -> IResult<&str, Self> {
+    pub(crate) fn parse_inner(input: &str) -> IResult<&str, Self> {
         let single = SingleMana::parse.map(Self::Single);
         let generic = GenericMana::parse.map(Self::Generic);
         let split = SplitMana::parse.map(Self::Split);
@@ -149,83 +457,453 @@ impl Mana {
         alt((brackets, Self::parse_inner)).parse(input)
     }
 
+    /// Parse `Mana` using [`nom`], where the custom colors of `registry` are
+    /// also recognized.
+    ///
+    /// ```
+    /// use mana_symbols::{ColorRegistry, Mana};
+    ///
+    /// let mut registry = ColorRegistry::new();
+    /// let purple = registry.register('O', "purple", "#c9a0dc").unwrap();
+    ///
+    /// let (_, mana) = Mana::parse_with("{O}", &registry).unwrap();
+    /// assert_eq!(mana, Mana::Custom(purple));
+    /// ```
+    pub fn parse_with<'a>(input: &'a str, registry: &ColorRegistry) -> IResult<&'a str, Self> {
+        let inner = |input| {
+            let custom = (|input| registry.parse(input)).map(Self::Custom);
+            alt((Self::parse_inner, custom)).parse(input)
+        };
+        let brackets = delimited(char('{'), inner, char('}'));
+        alt((brackets, inner)).parse(input)
+    }
+
+    /// Parse `Mana` written in the legacy encoding of old Oracle text dumps
+    /// (from before Eighth Edition), where every symbol starts with `o`, e.g.
+    /// `oW` for `{W}`, `o2` for `{2}` and `oX` for `{X}`. The tap symbol, `ocT`,
+    /// is not mana, see [`Symbol::parse_legacy`](crate::Symbol::parse_legacy).
+    ///
+    /// ```
+    /// use mana_symbols::Mana;
+    ///
+    /// let (rest, mana) = Mana::parse_legacy("o2oG").unwrap();
+    /// assert_eq!(mana, "2".parse().unwrap());
+    /// assert_eq!(rest, "oG");
+    /// ```
+    pub fn parse_legacy(input: &str) -> IResult<&str, Self> {
+        let generic = GenericMana::parse.map(Self::Generic);
+        let colored = Color::parse.map(|color| Self::Single(SingleMana::Normal(color)));
+        preceded(char('o'), alt((generic, colored))).parse(input)
+    }
+
+    pub(crate) fn name(&self) -> String {
+        match self {
+            Self::Single(SingleMana::Normal(color)) => format!("{} mana", color.name_capitalized()),
+            Self::Single(SingleMana::Phyrexian(color)) => {
+                format!("Phyrexian {} mana", color.name())
+            }
+            Self::Single(SingleMana::Half(color)) => format!("Half {} mana", color.name()),
+            Self::Generic(GenericMana::Number(n)) => format!("{n} generic mana"),
+            Self::Generic(GenericMana::X) => "X generic mana".to_string(),
+            Self::Generic(GenericMana::Y) => "Y generic mana".to_string(),
+            Self::Generic(GenericMana::Z) => "Z generic mana".to_string(),
+            Self::Generic(GenericMana::Infinity) => "Infinite generic mana".to_string(),
+            Self::Split(SplitMana::Mono { value, color }) => {
+                format!("Hybrid mana: {value} generic or {}", color.name())
+            }
+            Self::Split(SplitMana::Duo { a, b, phyrexian }) => {
+                if *phyrexian {
+                    format!("Phyrexian hybrid mana: {} or {}", a.name(), b.name())
+                } else {
+                    format!("Hybrid mana: {} or {}", a.name(), b.name())
+                }
+            }
+            Self::Split(SplitMana::Colorless { color }) => {
+                format!("Hybrid mana: colorless or {}", color.name())
+            }
+            Self::Colorless => "Colorless mana".to_string(),
+            Self::Snow => "Snow mana".to_string(),
+            Self::Custom(color) => format!("{color} mana"),
+        }
+    }
+
+    /// The name, where custom colors are called by their names in `palette`
+    /// (see [`ColorRegistry::extend_palette`]).
+    #[cfg(feature = "svg")]
+    pub(crate) fn name_with(&self, palette: &Palette) -> String {
+        match self {
+            Self::Custom(color) => match palette.custom_name(*color) {
+                Some(name) => {
+                    let mut chars = name.chars();
+                    let first = chars.next().map(|c| c.to_uppercase().to_string());
+                    format!("{}{} mana", first.unwrap_or_default(), chars.as_str())
+                }
+                None => self.name(),
+            },
+            _ => self.name(),
+        }
+    }
+}
+
+#[cfg(feature = "svg")]
+impl Mana {
     /// Display the mana symbol as an [SVG](https://en.wikipedia.org/wiki/SVG).
     #[must_use]
     pub fn as_svg(&self, config: &SVGConfig) -> SVG {
+        self.as_svg_with_theme(config, &config.palette)
+    }
+
+    /// Display the mana symbol as an [SVG](https://en.wikipedia.org/wiki/SVG), where the
+    /// circles and glyphs are drawn using `theme` instead of [`SVGConfig::palette`].
+    #[must_use]
+    pub fn as_svg_with_theme<T: Theme + ?Sized>(&self, config: &SVGConfig, theme: &T) -> SVG {
+        Self::symbol_svg(&Symbol::Mana(*self), config, theme)
+    }
+
+    /// Draw `item` like a mana symbol, as used by every kind of [`Symbol`].
+    pub(crate) fn symbol_svg<T: Theme + ?Sized>(
+        item: &Symbol,
+        config: &SVGConfig,
+        theme: &T,
+    ) -> SVG {
         let shadow_offset = config.shadow_offset;
-        let mut document = Document::new().set(
-            "viewBox",
-            (
-                -shadow_offset,
-                -shadow_offset,
-                2.0f64.mul_add(shadow_offset, SVG_WIDTH),
-                2.0f64.mul_add(shadow_offset, SVG_WIDTH),
-            ),
-        );
+        let margin = config.margin();
+        let width = 2.0f64.mul_add(margin, SVG_WIDTH);
+        // Subtracting avoids writing `-0` when there is no margin
+        let origin = 0.0 - margin;
+        // Half mana only shows the left half of the circle
+        let aspect_ratio = if item.mana().is_some_and(|mana| mana.is_half()) { 0.5 } else { 1.0 };
+        let mut document =
+            Document::new().set("viewBox", (origin, origin, width * aspect_ratio, width));
+        if let Some((width, height)) = config.size(aspect_ratio) {
+            document = document.set("width", width).set("height", height);
+        }
+        if let Some(shape_rendering) = config.shape_rendering {
+            document = document.set("shape-rendering", shape_rendering.to_string());
+        }
+        let name = item.name_with(&config.palette);
+        document = with_description(document, &name, &format!("{{{item}}}"));
         if config.shadow {
-            document = with_shadow(document, shadow_offset);
+            document = match &config.drop_shadow {
+                Some(drop_shadow) if !config.compatible => with_drop_shadow(document, drop_shadow),
+                _ => with_shadow(document, shadow_offset, &config.shadow_color),
+            };
         }
 
-        document = match self {
-            Self::Single(SingleMana::Normal(color)) => {
-                document = with_circle(document, color.hex());
-                with_symbol(document, color_symbol(*color), 0.8125)
+        let artwork = item.mana().and_then(|mana| config.symbol_override.and_then(|f| f(&mana)));
+        if let Some(artwork) = artwork {
+            let document = with_symbol(document, artwork, 1.0);
+            return make_compatible(minify(document, config), config);
+        }
+
+        let fill = |color| theme.circle_fill(Fill::Color(color));
+        let colorless = theme.circle_fill(Fill::Colorless);
+        let glyph_svg = |glyph| {
+            let symbol = themed_glyph(theme, glyph)?;
+            match &config.glyph_stroke {
+                Some(stroke) => Some(
+                    symbol
+                        .set("stroke", stroke.color.as_str())
+                        .set("stroke-width", stroke.width)
+                        .set("paint-order", "stroke"),
+                ),
+                None => Some(symbol),
             }
-            Self::Single(SingleMana::Phyrexian(color)) => {
-                let document = with_circle(document, color.hex());
-                with_symbol(document, phyrexian_symbol(), 0.8125)
+        };
+        let symbol = |document, glyph, size| match glyph_svg(glyph) {
+            Some(symbol) => with_symbol(document, symbol, size),
+            None => document,
+        };
+        let symbols = |document, left, right| match (glyph_svg(left), glyph_svg(right)) {
+            (Some(left), Some(right)) => with_symbols(document, left, right, 0.875),
+            _ => document,
+        };
+
+        document = match item {
+            Symbol::Mana(Self::Single(SingleMana::Normal(color) | SingleMana::Half(color))) => {
+                document = with_circle(document, &fill(*color));
+                symbol(document, Glyph::Color(*color), 0.8125)
             }
-            Self::Generic(GenericMana::Number(n)) => {
-                document = with_circle(document, HEX_C);
-                if let Some(symbol) = number_symbol(*n) {
-                    with_symbol(document, symbol, 0.70)
-                } else {
-                    document
-                }
+            Symbol::Mana(Self::Single(SingleMana::Phyrexian(color))) => {
+                document = with_circle(document, &fill(*color));
+                symbol(document, Glyph::Phyrexian, 0.8125)
             }
-            Self::Generic(GenericMana::X) => {
-                let document = with_circle(document, HEX_C);
-                with_symbol(document, x_symbol(), 0.8125)
+            Symbol::Mana(Self::Generic(GenericMana::Number(n))) => {
+                document = with_circle(document, &colorless);
+                symbol(document, Glyph::Number(*n), 0.70)
             }
-            Self::Generic(GenericMana::Y) => {
-                let document = with_circle(document, HEX_C);
-                with_symbol(document, y_symbol(), 0.8125)
+            Symbol::Mana(Self::Generic(GenericMana::X)) => {
+                document = with_circle(document, &colorless);
+                symbol(document, Glyph::Variable('X'), 0.8125)
             }
-            Self::Generic(GenericMana::Z) => {
-                let document = with_circle(document, HEX_C);
-                with_symbol(document, z_symbol(), 0.8125)
+            Symbol::Mana(Self::Generic(GenericMana::Y)) => {
+                document = with_circle(document, &colorless);
+                symbol(document, Glyph::Variable('Y'), 0.8125)
             }
-            Self::Split(SplitMana::Colorless { color }) => {
-                document = with_split_circle(document, HEX_C, color.hex());
-                with_symbols(document, colorless_symbol(), color_symbol(*color), 0.875)
+            Symbol::Mana(Self::Generic(GenericMana::Z)) => {
+                document = with_circle(document, &colorless);
+                symbol(document, Glyph::Variable('Z'), 0.8125)
             }
-            Self::Split(SplitMana::Mono { color, value }) => {
-                document = with_split_circle(document, HEX_C, color.hex());
-                if let Some(number) = number_symbol(*value) {
-                    with_symbols(document, number, color_symbol(*color), 0.875)
-                } else {
-                    document
-                }
+            Symbol::Mana(Self::Generic(GenericMana::Infinity)) => {
+                document = with_circle(document, &colorless);
+                symbol(document, Glyph::Infinity, 0.8125)
             }
-            Self::Split(SplitMana::Duo { a, b, phyrexian }) => {
-                document = with_split_circle(document, a.hex(), b.hex());
+            Symbol::Mana(Self::Split(SplitMana::Colorless { color })) => {
+                document =
+                    with_split_circle(document, &colorless, &fill(*color), config.compatible);
+                symbols(document, Glyph::Colorless, Glyph::Color(*color))
+            }
+            Symbol::Mana(Self::Split(SplitMana::Mono { color, value })) => {
+                document =
+                    with_split_circle(document, &colorless, &fill(*color), config.compatible);
+                symbols(document, Glyph::Number(*value), Glyph::Color(*color))
+            }
+            Symbol::Mana(Self::Split(SplitMana::Duo { a, b, phyrexian })) => {
+                document = with_split_circle(document, &fill(*a), &fill(*b), config.compatible);
                 if *phyrexian {
-                    with_symbols(document, phyrexian_symbol(), phyrexian_symbol(), 0.875)
+                    symbols(document, Glyph::Phyrexian, Glyph::Phyrexian)
                 } else {
-                    with_symbols(document, color_symbol(*a), color_symbol(*b), 0.875)
+                    symbols(document, Glyph::Color(*a), Glyph::Color(*b))
                 }
             }
-            Self::Colorless => {
-                document = with_circle(document, HEX_C);
-                with_symbol(document, colorless_symbol(), 0.8125)
+            Symbol::Mana(Self::Colorless) => {
+                document = with_circle(document, &colorless);
+                symbol(document, Glyph::Colorless, 0.8125)
+            }
+            Symbol::Mana(Self::Snow) => {
+                document = with_circle(document, &colorless);
+                symbol(document, Glyph::Snow, 1.0)
+            }
+            Symbol::Tap => {
+                document = with_circle(document, &colorless);
+                symbol(document, Glyph::Tap, 0.8125)
+            }
+            Symbol::Untap => {
+                document = with_circle(document, &colorless);
+                symbol(document, Glyph::Untap, 0.8125)
+            }
+            Symbol::Energy => {
+                document = with_circle(document, &colorless);
+                symbol(document, Glyph::Energy, 0.8125)
+            }
+            Symbol::Ticket => {
+                document = with_circle(document, &colorless);
+                symbol(document, Glyph::Ticket, 0.8125)
             }
-            Self::Snow => {
-                document = with_circle(document, HEX_C);
-                with_symbol(document, snow_symbol(), 1.0)
+            Symbol::Mana(Self::Custom(color)) => {
+                document = with_circle(document, &theme.circle_fill(Fill::Custom(*color)));
+                symbol(document, Glyph::Custom(*color), 0.8125)
             }
+            Symbol::Chaos => {
+                document = with_circle(document, &colorless);
+                symbol(document, Glyph::Chaos, 0.8125)
+            }
+            Symbol::Loyalty(_) => unreachable!("Loyalty costs are drawn as shields"),
         };
 
-        document
+        if let Some(foil) = theme.foil().filter(|_| !config.compatible) {
+            document = with_foil(document, &foil, config.shimmer);
+        }
+
+        let stroke = match &config.circle_stroke {
+            Some(stroke) => Some((stroke.color.clone(), stroke.width)),
+            None => theme.stroke(),
+        };
+        if let Some((stroke, width)) = stroke {
+            document = with_outline(document, &stroke, width, config.non_scaling_stroke);
+        }
+
+        make_compatible(minify(document, config), config)
+    }
+
+    /// Display the mana symbol as a gzip-compressed SVG (see [`Mana::as_svg`]),
+    /// as used by `.svgz` files. It can also be served directly with the HTTP
+    /// header `Content-Encoding: gzip`.
+    ///
+    /// ```
+    /// use mana_symbols::{Mana, SVGConfig};
+    ///
+    /// let mana: Mana = "U".parse().unwrap();
+    /// let svgz = mana.as_svgz(&SVGConfig::default());
+    /// assert_eq!(svgz[..2], [0x1f, 0x8b]);
+    /// ```
+    #[cfg(feature = "svgz")]
+    #[must_use]
+    pub fn as_svgz(&self, config: &SVGConfig) -> Vec<u8> {
+        crate::svgz::svg_to_svgz(&self.as_svg(config))
+    }
+
+    /// Tessellate the mana symbol (see [`Mana::as_svg`]), so that it can be
+    /// drawn by [egui](https://www.egui.rs). See [`SymbolMesh`](crate::SymbolMesh).
+    #[cfg(feature = "egui")]
+    #[must_use]
+    pub fn as_egui_mesh(&self, config: &SVGConfig) -> Option<crate::SymbolMesh> {
+        crate::egui_mesh::svg_to_mesh(&self.as_svg(config))
+    }
+
+    /// Display the mana symbol as a [PNG](https://en.wikipedia.org/wiki/PNG)
+    /// image which is `size` pixels wide and high, encoded as bytes. Returns
+    /// `None` if `size` is zero.
+    ///
+    /// ```
+    /// use mana_symbols::{Mana, SVGConfig};
+    ///
+    /// let mana: Mana = "U".parse().unwrap();
+    /// let png = mana.as_png(64, &SVGConfig::default()).unwrap();
+    /// assert!(png.starts_with(b"\x89PNG"));
+    /// ```
+    #[cfg(feature = "png")]
+    #[must_use]
+    pub fn as_png(&self, size: u32, config: &SVGConfig) -> Option<Vec<u8>> {
+        crate::raster::svg_to_png(&self.as_svg(config), size)
+    }
+
+    /// Display the mana symbol as an image which is `size` pixels wide and
+    /// high. Returns `None` if `size` is zero.
+    ///
+    /// ```
+    /// use mana_symbols::{Mana, SVGConfig};
+    ///
+    /// let mana: Mana = "U".parse().unwrap();
+    /// let image = mana.as_rgba_image(64, &SVGConfig::default()).unwrap();
+    /// assert_eq!(image.dimensions(), (64, 64));
+    /// ```
+    #[cfg(feature = "image")]
+    #[must_use]
+    pub fn as_rgba_image(&self, size: u32, config: &SVGConfig) -> Option<image::RgbaImage> {
+        crate::raster::svg_to_image(&self.as_svg(config), size)
+    }
+
+    /// Write the same SVG as [`Mana::as_svg`] to `output`, in the canonical
+    /// form described in [`write_canonical_svg`]. This is faster than
+    /// [`Mana::as_svg`], as the markup is written directly instead of building
+    /// a tree of nodes first, so it suits servers which draw many symbols.
+    ///
+    /// ```
+    /// use mana_symbols::{Mana, SVGConfig, canonical_svg};
+    ///
+    /// let mana: Mana = "U".parse().unwrap();
+    /// let config = SVGConfig::default();
+    /// let mut svg = String::new();
+    /// mana.render_svg_to(&mut svg, &config);
+    /// assert_eq!(svg, canonical_svg(&mana.as_svg(&config)));
+    /// ```
+    pub fn render_svg_to(&self, output: &mut String, config: &SVGConfig) {
+        if config.minify || config.precision.is_some() || config.compatible {
+            // Minifying and inlining glyphs needs the whole tree
+            write_canonical_svg(output, &self.as_svg(config)).unwrap();
+        } else {
+            crate::render::render_mana(output, self, config);
+        }
+    }
+
+    /// Write the mana symbol as an SVG (see [`Mana::as_svg`]) to `output`, e.g.
+    /// a file or the body of an HTTP response. The SVG is written in the
+    /// canonical form described in [`write_canonical_svg`].
+    ///
+    /// ```
+    /// use mana_symbols::{Mana, SVGConfig};
+    ///
+    /// let mana: Mana = "U".parse().unwrap();
+    /// let mut output = Vec::new();
+    /// mana.write_svg(&mut output, &SVGConfig::default()).unwrap();
+    /// assert!(output.starts_with(b"<svg"));
+    /// ```
+    pub fn write_svg<W: io::Write>(&self, output: &mut W, config: &SVGConfig) -> io::Result<()> {
+        write_io(output, |output| write_canonical_svg(output, &self.as_svg(config)))
+    }
+
+    /// The SVG of the mana symbol (see [`Mana::as_svg`]) as a base64
+    /// [data URI](https://developer.mozilla.org/en-US/docs/Web/URI/Reference/Schemes/data),
+    /// like the `src` of [`Mana::as_html`]. Useful for frameworks which build
+    /// their own elements, or for CSS.
+    ///
+    /// ```
+    /// use mana_symbols::{Mana, SVGConfig};
+    ///
+    /// let mana: Mana = "U".parse().unwrap();
+    /// let uri = mana.as_svg_data_uri(&SVGConfig::default());
+    /// assert!(uri.starts_with("data:image/svg+xml;base64,"));
+    /// ```
+    #[must_use]
+    pub fn as_svg_data_uri(&self, config: &SVGConfig) -> String {
+        svg_data_uri(&self.as_svg(config))
+    }
+
+    /// The outlines of the glyphs of the mana symbol, placed like in
+    /// [`Mana::as_svg`] with the circle at (0, 0) and 32 units wide, without
+    /// a margin. Can be used to draw mana symbols natively, e.g. in game
+    /// engines or with plotters, without parsing SVGs.
+    ///
+    /// Only the built-in artwork is used. Snow mana only includes its black
+    /// outline, the glyphs of half mana are not cut off, and custom colors
+    /// have no outline.
+    ///
+    /// ```
+    /// use mana_symbols::{Mana, PathSegment};
+    ///
+    /// let mana: Mana = "U".parse().unwrap();
+    /// let segments = mana.glyph_paths();
+    /// assert!(matches!(segments[0], PathSegment::MoveTo { .. }));
+    /// ```
+    #[must_use]
+    pub fn glyph_paths(&self) -> Vec<PathSegment> {
+        let one = |glyph| {
+            let size = match glyph {
+                Glyph::Number(_) => 0.70,
+                Glyph::Snow => 1.0,
+                _ => 0.8125,
+            };
+            let width = SVG_WIDTH * size;
+            let position = (SVG_WIDTH - width) / 2.0;
+            placed_segments(glyph, position, position, width)
+        };
+        // Placed like in `with_symbols`
+        let two = |left, right| {
+            let pi = f64::consts::PI;
+            let width = (SVG_WIDTH / 2.0) * 0.875;
+            let x_left = f64::cos(pi / 4.0 + pi) * (SVG_WIDTH / 4.0) + (SVG_WIDTH / 2.0);
+            let y_left = f64::sin(pi / 4.0 + pi) * (SVG_WIDTH / 4.0) + (SVG_WIDTH / 2.0);
+            let x_right = f64::cos(pi / 4.0) * (SVG_WIDTH / 4.0) + (SVG_WIDTH / 2.0);
+            let y_right = f64::sin(pi / 4.0) * (SVG_WIDTH / 4.0) + (SVG_WIDTH / 2.0);
+            let mut segments =
+                placed_segments(left, x_left - width / 2.0, y_left - width / 2.0, width);
+            segments.extend(placed_segments(
+                right,
+                x_right - width / 2.0,
+                y_right - width / 2.0,
+                width,
+            ));
+            segments
+        };
+
+        match *self {
+            Self::Single(SingleMana::Normal(color) | SingleMana::Half(color)) => {
+                one(Glyph::Color(color))
+            }
+            Self::Single(SingleMana::Phyrexian(_)) => one(Glyph::Phyrexian),
+            Self::Generic(GenericMana::Number(n)) => one(Glyph::Number(n)),
+            Self::Generic(GenericMana::X) => one(Glyph::Variable('X')),
+            Self::Generic(GenericMana::Y) => one(Glyph::Variable('Y')),
+            Self::Generic(GenericMana::Z) => one(Glyph::Variable('Z')),
+            Self::Generic(GenericMana::Infinity) => one(Glyph::Infinity),
+            Self::Split(SplitMana::Colorless { color }) => {
+                two(Glyph::Colorless, Glyph::Color(color))
+            }
+            Self::Split(SplitMana::Mono { color, value }) => {
+                two(Glyph::Number(value), Glyph::Color(color))
+            }
+            Self::Split(SplitMana::Duo { a, b, phyrexian }) => {
+                if phyrexian {
+                    two(Glyph::Phyrexian, Glyph::Phyrexian)
+                } else {
+                    two(Glyph::Color(a), Glyph::Color(b))
+                }
+            }
+            Self::Colorless => one(Glyph::Colorless),
+            Self::Snow => one(Glyph::Snow),
+            Self::Custom(_) => Vec::new(),
+        }
     }
 
     /// Display the mana symbol as a [`String`] of [HTML](https://en.wikipedia.org/wiki/HTML), where the image is an SVG (see [`Mana::as_svg`]).
@@ -243,50 +921,218 @@ impl Mana {
         include_css: bool,
         config: &SVGConfig,
     ) -> std::fmt::Result {
-        let svg = self.as_svg(config);
-        let base64 = BASE64_STANDARD.encode(svg.to_string());
-        let css = if include_css {
-            r#" style="height: 1.5em; width: 1.7em; vertical-align: middle""#
-        } else {
-            ""
-        };
+        let options = HtmlOptions { include_css, ..Default::default() };
+        self.write_html_with(output, &options, config)
+    }
 
-        write!(
-            output,
-            r#"<img{css} alt="{{{self}}}" title="{}" src="data:image/svg+xml;base64,{base64}">"#,
-            self.name()
-        )
+    /// Display the mana symbol as a [`String`] of [HTML](https://en.wikipedia.org/wiki/HTML), using the given [`HtmlOptions`].
+    ///
+    /// ```
+    /// use mana_symbols::{HtmlOptions, Mana, SVGConfig};
+    ///
+    /// let mana: Mana = "U".parse().unwrap();
+    /// let options =
+    ///     HtmlOptions { inline: true, class: Some("mana".to_string()), ..Default::default() };
+    ///
+    /// let html = mana.as_html_with(&options, &SVGConfig::default());
+    /// assert!(html.starts_with("<svg"));
+    /// assert!(html.contains(r#"class="mana""#));
+    ///
+    /// let options = HtmlOptions {
+    ///     lazy: true,
+    ///     attributes: vec![("data-cost".to_string(), "{U}".to_string())],
+    ///     title: Some("Island".to_string()),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let html = mana.as_html_with(&options, &SVGConfig::default());
+    /// assert!(html.starts_with(r#"<img loading="lazy" data-cost="{U}" alt="{U}" title="Island""#));
+    /// ```
+    #[must_use]
+    pub fn as_html_with(&self, options: &HtmlOptions, config: &SVGConfig) -> String {
+        let mut out = String::new();
+        self.write_html_with(&mut out, options, config).unwrap();
+        out
     }
 
-    fn name(&self) -> String {
-        match self {
-            Self::Single(SingleMana::Normal(color)) => format!("{} mana", color.name_capitalized()),
-            Self::Single(SingleMana::Phyrexian(color)) => {
-                format!("Phyrexian {} mana", color.name())
-            }
-            Self::Generic(GenericMana::Number(n)) => format!("{n} generic mana"),
-            Self::Generic(GenericMana::X) => "X generic mana".to_string(),
-            Self::Generic(GenericMana::Y) => "Y generic mana".to_string(),
-            Self::Generic(GenericMana::Z) => "Z generic mana".to_string(),
-            Self::Split(SplitMana::Mono { value, color }) => {
-                format!("Hybrid mana: {value} generic or {}", color.name())
+    /// Display the mana symbol as [HTML](https://en.wikipedia.org/wiki/HTML) written to an [`io::Write`], e.g. a file or the body of an HTTP response (see [`Mana::write_html_with`]).
+    pub fn write_html_io<W: io::Write>(
+        &self,
+        output: &mut W,
+        options: &HtmlOptions,
+        config: &SVGConfig,
+    ) -> io::Result<()> {
+        write_io(output, |output| self.write_html_with(output, options, config))
+    }
+
+    /// Display the mana symbol as [HTML](https://en.wikipedia.org/wiki/HTML) written to `output`, using the given [`HtmlOptions`] (see [`Mana::as_html_with`]).
+    pub fn write_html_with<W: Write>(
+        &self,
+        output: &mut W,
+        options: &HtmlOptions,
+        config: &SVGConfig,
+    ) -> std::fmt::Result {
+        let mut svg = self.as_svg(config);
+        if options.inline {
+            if let Some(class) = &options.class {
+                svg = svg.set("class", class.as_str());
             }
-            Self::Split(SplitMana::Duo { a, b, phyrexian }) => {
-                if *phyrexian {
-                    format!("Phyrexian hybrid mana: {} or {}", a.name(), b.name())
-                } else {
-                    format!("Hybrid mana: {} or {}", a.name(), b.name())
-                }
+            if options.include_css {
+                svg = svg.set("style", self.css());
             }
-            Self::Split(SplitMana::Colorless { color }) => {
-                format!("Hybrid mana: colorless or {}", color.name())
+            for (name, value) in options.valid_attributes() {
+                svg = svg.set(name, value);
             }
-            Self::Colorless => "Colorless mana".to_string(),
-            Self::Snow => "Snow mana".to_string(),
+            return write_canonical_svg(output, &svg);
+        }
+
+        let data_uri = svg_data_uri(&svg);
+
+        // The PNG is used by the `<img>` element, which is only shown if the
+        // SVG source isn't supported
+        #[cfg(feature = "png")]
+        let png = options
+            .picture
+            .and_then(|height| crate::raster::svg_to_png(&svg, height))
+            .map(|png| BASE64_STANDARD.encode(png));
+        #[cfg(feature = "png")]
+        if png.is_some() {
+            write!(output, "<picture>")?;
+            write!(output, r#"<source type="image/svg+xml" srcset="{data_uri}">"#)?;
+        }
+
+        #[cfg(feature = "png")]
+        if let Some(png) = png {
+            let src = format!("data:image/png;base64,{png}");
+            self.write_img(output, options, &config.palette, &src)?;
+            return write!(output, "</picture>");
+        }
+
+        self.write_img(output, options, &config.palette, &data_uri)
+    }
+
+    /// Display the mana symbol as a [`String`] of [HTML](https://en.wikipedia.org/wiki/HTML),
+    /// where the image refers to an SVG file written by `assets` instead of
+    /// containing the SVG. See [`Mana::write_html_with_assets`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the SVG file could not be written.
+    pub fn as_html_with_assets(
+        &self,
+        options: &HtmlOptions,
+        assets: &mut AssetWriter,
+    ) -> io::Result<String> {
+        let mut out = String::new();
+        self.write_html_with_assets(&mut out, options, assets)?;
+        Ok(out)
+    }
+
+    /// Display the mana symbol as an `<img>` element written to `output`
+    /// (see [`Mana::write_html_with`]), where `src` is the URL of an SVG file
+    /// written by `assets`, which is far better for caching than a data URI
+    /// when the same symbols are shown many times. [`HtmlOptions::inline`]
+    /// and `HtmlOptions::picture` are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the SVG file could not be written, or if writing
+    /// to `output` failed.
+    pub fn write_html_with_assets<W: Write>(
+        &self,
+        output: &mut W,
+        options: &HtmlOptions,
+        assets: &mut AssetWriter,
+    ) -> io::Result<()> {
+        let src = assets.asset(self)?;
+        self.write_img(output, options, &assets.config().palette, &src)
+            .map_err(|std::fmt::Error| io::Error::other("formatter error"))
+    }
+
+    /// Write an `<img>` element showing the image at `src`, drawn using
+    /// `palette`.
+    fn write_img<W: Write>(
+        &self,
+        output: &mut W,
+        options: &HtmlOptions,
+        palette: &Palette,
+        src: &str,
+    ) -> std::fmt::Result {
+        write!(output, "<img")?;
+        if let Some(class) = &options.class {
+            write_attribute(output, "class", class)?;
+        }
+        if options.include_css {
+            write!(output, r#" style="{}""#, self.css())?;
         }
+        if options.lazy {
+            write!(output, r#" loading="lazy""#)?;
+        }
+        for (name, value) in options.valid_attributes() {
+            write_attribute(output, name, value)?;
+        }
+
+        // The defaults are escaped as well, as custom colors may be written
+        // using any letter in the future
+        match &options.alt {
+            Some(alt) => write_attribute(output, "alt", alt)?,
+            None => write_attribute(output, "alt", &format!("{{{self}}}"))?,
+        }
+        match &options.title {
+            Some(title) => write_attribute(output, "title", title)?,
+            None => write_attribute(output, "title", &self.name_with(palette))?,
+        }
+
+        write_attribute(output, "src", src)?;
+        output.write_char('>')
+    }
+
+    /// Whether the mana symbol is half of a colored mana symbol, e.g. `{HW}`,
+    /// which is drawn as the left half of a circle.
+    pub(crate) const fn is_half(&self) -> bool {
+        matches!(self, Self::Single(SingleMana::Half(_)))
+    }
+
+    /// The style of the HTML element when CSS is included.
+    const fn css(&self) -> &'static str {
+        if self.is_half() { HALF_CSS } else { CSS }
     }
 }
 
+/// The outline of `glyph`, scaled to `width` and moved to (`x`, `y`).
+#[cfg(feature = "svg")]
+fn placed_segments(glyph: Glyph, x: f64, y: f64, width: f64) -> Vec<PathSegment> {
+    let scale = width / SVG_WIDTH;
+    let segments = glyph_segments(glyph).unwrap_or_default();
+    segments
+        .into_iter()
+        .map(|segment| segment.map(|px, py| (px * scale + x, py * scale + y)))
+        .collect()
+}
+
+/// `svg` in its canonical form as a base64 data URI.
+#[cfg(feature = "svg")]
+pub(crate) fn svg_data_uri(svg: &SVG) -> String {
+    let base64 = BASE64_STANDARD.encode(canonical_svg(svg));
+    format!("data:image/svg+xml;base64,{base64}")
+}
+
+#[cfg(feature = "svg")]
+fn write_attribute<W: Write>(output: &mut W, name: &str, value: &str) -> std::fmt::Result {
+    write!(output, r#" {name}=""#)?;
+    write_escaped(output, value)?;
+    output.write_char('"')
+}
+
+/// The style used by [`Mana::as_html`] when CSS is included
+#[cfg(feature = "svg")]
+const CSS: &str = "height: 1.5em; width: 1.7em; vertical-align: middle";
+
+/// The style used by [`Mana::as_html`] for half mana when CSS is included
+#[cfg(feature = "svg")]
+const HALF_CSS: &str = "height: 1.5em; width: 0.85em; vertical-align: middle";
+
+#[cfg(feature = "svg")]
 #[must_use]
 fn with_symbol(document: SVG, symbol: SVG, size: f64) -> SVG {
     let symbol_width = SVG_WIDTH * size;
@@ -300,6 +1146,7 @@ fn with_symbol(document: SVG, symbol: SVG, size: f64) -> SVG {
     document.add(symbol)
 }
 
+#[cfg(feature = "svg")]
 #[must_use]
 fn with_symbols(mut document: SVG, symbol_left: SVG, symbol_right: SVG, size: f64) -> SVG {
     let pi = f64::consts::PI;
@@ -327,6 +1174,29 @@ fn with_symbols(mut document: SVG, symbol_left: SVG, symbol_right: SVG, size: f6
     document.add(symbol)
 }
 
+/// Describe the image for screen readers, where `label` is read aloud and
+/// `description` is a longer (or more technical) description.
+#[cfg(feature = "svg")]
+#[must_use]
+pub(crate) fn with_description(document: SVG, label: &str, description: &str) -> SVG {
+    document
+        .set("role", "img")
+        .set("aria-label", label)
+        .add(Title::new(label))
+        .add(Description::new().add(TextNode::new(description)))
+}
+
+/// The artwork of `glyph` from `theme`, or the built-in artwork
+#[cfg(feature = "svg")]
+fn themed_glyph<T: Theme + ?Sized>(theme: &T, glyph: Glyph) -> Option<SVG> {
+    let symbol = theme.glyph(glyph).or_else(|| glyph_symbol(glyph))?;
+    match theme.glyph_fill(glyph) {
+        Some(fill) => Some(symbol.set("fill", fill)),
+        None => Some(symbol),
+    }
+}
+
+#[cfg(feature = "svg")]
 #[must_use]
 fn with_circle(document: SVG, fill: &str) -> SVG {
     let circle = Circle::new()
@@ -338,30 +1208,157 @@ fn with_circle(document: SVG, fill: &str) -> SVG {
     document.add(circle)
 }
 
+#[cfg(feature = "svg")]
+#[must_use]
+fn with_outline(document: SVG, stroke: &str, width: f64, non_scaling: bool) -> SVG {
+    let mut circle = Circle::new()
+        .set("fill", "none")
+        .set("stroke", stroke)
+        .set("stroke-width", width)
+        .set("r", (SVG_WIDTH - width) / 2.0)
+        .set("cx", SVG_WIDTH / 2.0)
+        .set("cy", SVG_WIDTH / 2.0);
+    if non_scaling {
+        circle = circle.set("vector-effect", "non-scaling-stroke");
+    }
+    document.add(circle)
+}
+
+/// An id of a definition (e.g. a gradient) in an SVG, which is `name`
+/// followed by a hash of `key`, the parameters of the definition. Several SVGs
+/// in the same HTML page then only share ids if their definitions are equal.
+#[cfg(feature = "svg")]
+fn definition_id(name: &str, key: &str) -> String {
+    // FNV-1a, which unlike `DefaultHasher` is the same in every Rust version
+    let hash = key
+        .bytes()
+        .fold(0x811c_9dc5_u32, |hash, byte| (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193));
+    format!("{name}-{hash:08x}")
+}
+
+#[cfg(feature = "svg")]
+pub(crate) fn foil_id(foil: &Foil, shimmer: Option<f64>) -> String {
+    definition_id("foil", &format!("{:?} {shimmer:?}", foil.colors))
+}
+
+#[cfg(feature = "svg")]
+pub(crate) fn drop_shadow_id(drop_shadow: &DropShadow) -> String {
+    let DropShadow { dx, dy, blur, color, opacity } = drop_shadow;
+    definition_id("drop_shadow", &format!("{dx} {dy} {blur} {color} {opacity}"))
+}
+
+#[cfg(feature = "svg")]
+pub(crate) fn circle_mask_id() -> String {
+    definition_id("circle_mask", &(SVG_WIDTH / 2.0).to_string())
+}
+
+#[cfg(feature = "svg")]
+#[must_use]
+fn with_foil(document: SVG, foil: &Foil, shimmer: Option<f64>) -> SVG {
+    let id = foil_id(foil, shimmer);
+    let mut gradient = LinearGradient::new()
+        .set("id", id.as_str())
+        .set("x1", 0)
+        .set("y1", 0)
+        .set("x2", 1)
+        .set("y2", 1);
+    let n = foil.colors.len();
+    for (i, color) in foil.colors.iter().enumerate() {
+        let offset = if n > 1 { i as f64 / (n - 1) as f64 } else { 0.0 };
+        gradient =
+            gradient.add(Stop::new().set("offset", offset).set("stop-color", color.as_str()));
+    }
+    if let Some(duration) = shimmer {
+        // Move the gradient from the top left to the bottom right. Outside of
+        // the gradient, its first and last colors are used.
+        gradient = gradient.add(
+            AnimateTransform::new()
+                .set("attributeName", "gradientTransform")
+                .set("type", "translate")
+                .set("from", "-1 -1")
+                .set("to", "1 1")
+                .set("dur", format!("{duration}s"))
+                .set("repeatCount", "indefinite"),
+        );
+    }
+
+    let circle = Circle::new()
+        .set("fill", format!("url(#{id})"))
+        .set("stroke", "none")
+        .set("r", SVG_WIDTH / 2.0)
+        .set("cx", SVG_WIDTH / 2.0)
+        .set("cy", SVG_WIDTH / 2.0);
+    document.add(Definitions::new().add(gradient)).add(circle)
+}
+
+/// We draw a circle with the shadow filter, which is then covered by the main
+/// circle
+#[cfg(feature = "svg")]
 #[must_use]
-fn with_shadow(document: SVG, offset: f64) -> SVG {
+fn with_drop_shadow(document: SVG, drop_shadow: &DropShadow) -> SVG {
+    let id = drop_shadow_id(drop_shadow);
+    let effect = FilterEffectDropShadow::new()
+        .set("dx", drop_shadow.dx)
+        .set("dy", drop_shadow.dy)
+        .set("stdDeviation", drop_shadow.blur)
+        .set("flood-color", drop_shadow.color.as_str())
+        .set("flood-opacity", drop_shadow.opacity);
+    let filter = Filter::new()
+        .set("id", id.as_str())
+        .set("x", "-50%")
+        .set("y", "-50%")
+        .set("width", "200%")
+        .set("height", "200%")
+        .add(effect);
+
     let circle = Circle::new()
         .set("fill", "black")
         .set("stroke", "none")
+        .set("filter", format!("url(#{id})"))
+        .set("r", SVG_WIDTH / 2.0)
+        .set("cx", SVG_WIDTH / 2.0)
+        .set("cy", SVG_WIDTH / 2.0);
+    document.add(Definitions::new().add(filter)).add(circle)
+}
+
+#[cfg(feature = "svg")]
+#[must_use]
+fn with_shadow(document: SVG, offset: f64, fill: &str) -> SVG {
+    let circle = Circle::new()
+        .set("fill", fill)
+        .set("stroke", "none")
         .set("r", SVG_WIDTH / 2.0)
         .set("cx", SVG_WIDTH / 2.0 - offset)
         .set("cy", SVG_WIDTH / 2.0 + offset);
     document.add(circle)
 }
 
+#[cfg(feature = "svg")]
 #[must_use]
-fn with_split_circle(mut document: SVG, fill_left: &str, fill_right: &str) -> SVG {
+fn with_split_circle(
+    mut document: SVG,
+    fill_left: &str,
+    fill_right: &str,
+    compatible: bool,
+) -> SVG {
+    if compatible {
+        document = with_circle(document, fill_right);
+        let path = Path::new().set("d", half_circle_path()).set("fill", fill_left);
+        return document.add(path);
+    }
+
     let circle_mask = Circle::new()
         .set("fill", "white")
         .set("stroke", "none")
         .set("r", SVG_WIDTH / 2.0)
         .set("cx", SVG_WIDTH / 2.0)
         .set("cy", SVG_WIDTH / 2.0);
-    let mask = Mask::new().set("id", "circle_mask").set("mask-type", "luminance").add(circle_mask);
+    let id = circle_mask_id();
+    let mask = Mask::new().set("id", id.as_str()).set("mask-type", "luminance").add(circle_mask);
 
     document = document.add(mask);
 
-    let mut group = Group::new().set("mask", "url(#circle_mask)");
+    let mut group = Group::new().set("mask", format!("url(#{id})"));
 
     let data = Data::new()
         .move_to((0.0, 0.0))
@@ -384,6 +1381,15 @@ fn with_split_circle(mut document: SVG, fill_left: &str, fill_right: &str) -> SV
     document.add(group)
 }
 
+/// The top left half of the circle, which is split along the same diagonal as
+/// in `with_split_circle`.
+#[cfg(feature = "svg")]
+fn half_circle_path() -> String {
+    let r = SVG_WIDTH / 2.0;
+    let d = r * f64::consts::FRAC_1_SQRT_2;
+    format!("M{},{} A{r},{r} 0 0 0 {},{} z", r + d, r - d, r - d, r + d)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -408,4 +1414,228 @@ mod tests {
     fn parse_with_brackets() {
         assert!(Mana::from_str("{U}").is_ok());
     }
+
+    #[test]
+    fn all_printed() {
+        let all: Vec<Mana> = Mana::all_printed().collect();
+        assert_eq!(all.len(), 26 + 5 + 1 + 5 + 30 + 2 + 1 + 1);
+
+        for (i, mana) in all.iter().enumerate() {
+            assert!(mana.is_printed());
+            assert!(!all[..i].contains(mana));
+            assert_eq!(Mana::from_str(&mana.to_string()), Ok(*mana));
+
+            let mut normalized = *mana;
+            normalized.normalize_hybrid();
+            assert_eq!(normalized, *mana);
+        }
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn html_escaped() {
+        let mana = Mana::from_str("U").unwrap();
+        let options = HtmlOptions {
+            alt: Some(r#"<"Blue">"#.to_string()),
+            title: Some("Blue's & mana".to_string()),
+            attributes: vec![("data-name".to_string(), "\"><script>".to_string())],
+            ..Default::default()
+        };
+        let html = mana.as_html_with(&options, &SVGConfig::default());
+        assert!(html.contains(r#" alt="&lt;&quot;Blue&quot;&gt;" "#));
+        assert!(html.contains(r#" title="Blue&#39;s &amp; mana" "#));
+        assert!(html.contains(r#" data-name="&quot;&gt;&lt;script&gt;" "#));
+        assert!(!html.contains("<script>"));
+
+        let options = HtmlOptions {
+            attributes: vec![(r#"onload="alert()" x"#.to_string(), String::new())],
+            ..Default::default()
+        };
+        assert!(!mana.as_html_with(&options, &SVGConfig::default()).contains("onload"));
+    }
+
+    #[test]
+    fn half() {
+        let mana = Mana::from_str("HW").unwrap();
+        assert_eq!(mana, Mana::Single(SingleMana::Half(Color::White)));
+        assert_eq!(mana.to_string(), "HW");
+        assert_eq!(mana.mana_value(), 0);
+        assert_eq!(mana.exact_mana_value(), ManaValue::from_halves(1));
+        assert_eq!(mana.left_half_color(), Some(Color::White));
+        assert!(mana.is_printed());
+        assert!(!Mana::from_str("HU").unwrap().is_printed());
+    }
+
+    #[test]
+    fn infinity() {
+        let infinity = Mana::from_str("{∞}").unwrap();
+        assert_eq!(infinity, Mana::Generic(GenericMana::Infinity));
+        assert_eq!(Mana::from_str("{INF}"), Ok(infinity));
+        assert_eq!(infinity.to_string(), "∞");
+        assert_eq!(infinity.mana_value(), usize::MAX);
+        assert_eq!(infinity.exact_mana_value(), ManaValue::INFINITY);
+        assert_eq!(infinity.name(), "Infinite generic mana");
+        assert!(infinity.is_printed());
+        assert_eq!(
+            [infinity, Mana::Generic(GenericMana::Number(7))].iter().sum::<usize>(),
+            usize::MAX
+        );
+    }
+
+    #[test]
+    fn phi() {
+        let phyrexian = Mana::Single(SingleMana::Phyrexian(Color::Blue));
+        assert_eq!(Mana::from_str("{UΦ}"), Ok(phyrexian));
+        assert_eq!(Mana::from_str("U/Φ"), Ok(phyrexian));
+        assert_eq!(phyrexian.to_string(), "U/P");
+        let hybrid = Mana::from_str("{B/GΦ}").unwrap();
+        assert_eq!(hybrid, Mana::from_str("B/G/P").unwrap());
+        assert_eq!(Mana::from_str("{Φ}"), Err(()));
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn custom_name() {
+        let mut registry = ColorRegistry::new();
+        let purple = registry.register('O', "purple", "#c9a0dc").unwrap();
+        let mana = Mana::Custom(purple);
+        assert_eq!(mana.name(), "O mana");
+
+        let mut config = SVGConfig::default();
+        registry.extend_palette(&mut config.palette);
+        assert_eq!(mana.name_with(&config.palette), "Purple mana");
+        assert!(mana.as_svg(&config).to_string().contains("<title>Purple mana</title>"));
+        let html = mana.as_html_with(&HtmlOptions::default(), &config);
+        assert!(html.contains(r#"title="Purple mana""#));
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn half_svg() {
+        let mana = Mana::from_str("HW").unwrap();
+        let config = SVGConfig { height: Some(35.0), ..Default::default() };
+        let svg = mana.as_svg(&config).to_string();
+        assert!(svg.contains(r#"viewBox="-1.5 -1.5 17.5 35""#));
+        assert!(svg.contains(r#"width="17.5""#));
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn glyph_paths() {
+        use crate::PathSegment;
+
+        let points = |mana: &str| {
+            let segments = Mana::from_str(mana).unwrap().glyph_paths();
+            assert!(matches!(segments.first(), Some(PathSegment::MoveTo { .. })), "{mana}");
+            segments.into_iter().filter_map(|segment| match segment {
+                PathSegment::MoveTo { x, y } | PathSegment::LineTo { x, y } => Some((x, y)),
+                PathSegment::QuadTo { x, y, .. } | PathSegment::CubicTo { x, y, .. } => {
+                    Some((x, y))
+                }
+                PathSegment::Close => None,
+            })
+        };
+        for mana in ["W", "U", "B", "R", "G", "C", "S", "X", "12", "123", "W/U", "2/B", "C/R"] {
+            for (x, y) in points(mana) {
+                assert!((0.0..=32.0).contains(&x) && (0.0..=32.0).contains(&y), "{mana}");
+            }
+        }
+        // The glyphs of hybrid mana are in opposite corners
+        let (left, right): (Vec<_>, Vec<_>) = points("W/U").partition(|&(x, y)| x + y < 32.0);
+        assert!(!left.is_empty() && !right.is_empty());
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn shimmer() {
+        let mana = Mana::from_str("U").unwrap();
+        let config = SVGConfig { shimmer: Some(2.0), ..Default::default() };
+        assert!(!mana.as_svg(&config).to_string().contains("animateTransform"));
+
+        let palette = Palette { foil: Some(Foil::rainbow()), ..Default::default() };
+        let config = SVGConfig { palette, ..config };
+        let svg = mana.as_svg(&config).to_string();
+        assert!(svg.contains(r#"attributeName="gradientTransform""#));
+        assert!(svg.contains(r#"dur="2s""#));
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn inline_ids() {
+        let attributes = |html: &str, prefix: &str, end: char| -> Vec<String> {
+            let parts = html.split(prefix).skip(1);
+            parts.map(|x| x[..x.find(end).unwrap()].to_string()).collect()
+        };
+
+        // Two symbols inlined in the same page, with different foils and
+        // drop shadows
+        let mana = Mana::from_str("W/U").unwrap();
+        let options = HtmlOptions { inline: true, ..Default::default() };
+        let rainbow = SVGConfig {
+            palette: Palette { foil: Some(Foil::rainbow()), ..Default::default() },
+            drop_shadow: Some(DropShadow::default()),
+            ..Default::default()
+        };
+        let gold = SVGConfig {
+            palette: Palette {
+                foil: Some(Foil { colors: vec!["#ffd70080".to_string(), "#ffffff00".to_string()] }),
+                ..Default::default()
+            },
+            drop_shadow: Some(DropShadow { dx: 2.0, ..Default::default() }),
+            ..Default::default()
+        };
+        let a = mana.as_html_with(&options, &rainbow);
+        let b = mana.as_html_with(&options, &gold);
+
+        let ids_a = attributes(&a, r#" id=""#, '"');
+        let ids_b = attributes(&b, r#" id=""#, '"');
+        assert_eq!(ids_a.len(), 3);
+        for url in attributes(&a, "url(#", ')') {
+            assert!(ids_a.contains(&url));
+        }
+        for url in attributes(&b, "url(#", ')') {
+            assert!(ids_b.contains(&url));
+        }
+        for name in ["foil-", "drop_shadow-"] {
+            let a = ids_a.iter().find(|id| id.starts_with(name)).unwrap();
+            assert!(!ids_b.contains(a));
+        }
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn compatible() {
+        let palette = Palette { foil: Some(Foil::rainbow()), ..Default::default() };
+        let drop_shadow = Some(DropShadow::default());
+        let glyph_stroke = Some(crate::Stroke { color: "white".to_string(), width: 1.0 });
+        let config = SVGConfig {
+            palette,
+            drop_shadow,
+            glyph_stroke,
+            non_scaling_stroke: true,
+            compatible: true,
+            ..Default::default()
+        };
+        for mana in Mana::all_printed() {
+            let svg = mana.as_svg(&config).to_string();
+            // Only the outermost SVG is left
+            assert_eq!(svg.matches("<svg").count(), 1, "{mana}");
+            for feature in
+                ["<mask", "<filter", "Gradient", "vector-effect", "paint-order", "aria-", "role"]
+            {
+                assert!(!svg.contains(feature), "{mana} uses {feature}");
+            }
+        }
+    }
+
+    #[test]
+    fn order() {
+        let ordered = [
+            "X", "Y", "Z", "∞", "2", "10", "2/W", "2/U", "C", "C/B", "W", "W/P", "W/U", "W/B/P",
+            "U", "S",
+        ];
+        let manas: Vec<Mana> = ordered.iter().map(|s| s.parse().unwrap()).collect();
+        assert!(manas.is_sorted());
+        assert!(manas.windows(2).all(|w| w[0] < w[1]));
+    }
 }
@@ -0,0 +1,41 @@
+use crate::Mana;
+
+/// The groups of a sorted [`Manas`][crate::Manas], in the order they appear.
+///
+/// Returned by [`Manas::groups`][crate::Manas::groups].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ManaGroups<'a> {
+    /// Generic mana, including `X`, `Y` and `Z`.
+    pub generic: &'a [Mana],
+
+    /// Hybrid generic mana, e.g. `{2/W}`.
+    pub generic_hybrid: &'a [Mana],
+
+    /// Colorless mana.
+    pub colorless: &'a [Mana],
+
+    /// Hybrid colorless mana, e.g. `{C/W}`.
+    pub colorless_hybrid: &'a [Mana],
+
+    /// Colored mana, including phyrexian and hybrid mana of two colors, and
+    /// mana of custom colors.
+    pub colored: &'a [Mana],
+
+    /// Snow mana.
+    pub snow: &'a [Mana],
+}
+
+impl<'a> ManaGroups<'a> {
+    /// The groups in the order they appear, some of which may be empty.
+    #[must_use]
+    pub const fn as_array(&self) -> [&'a [Mana]; 6] {
+        [
+            self.generic,
+            self.generic_hybrid,
+            self.colorless,
+            self.colorless_hybrid,
+            self.colored,
+            self.snow,
+        ]
+    }
+}
@@ -0,0 +1,402 @@
+use std::fmt::Display;
+
+use crate::{Color, Manas, Payment, PaymentObjective, color::ALL_COLORS, payment};
+
+/// A unit of mana in a [`ManaPool`], which is either of a [color](Color) or
+/// colorless, and which may have been produced by a
+/// [snow](https://mtg.wiki/page/Snow) source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ManaUnit {
+    /// The color of the mana, or `None` if it is colorless.
+    pub color: Option<Color>,
+    /// If the mana was produced by a snow source, so it can pay for `{S}`.
+    pub snow: bool,
+}
+
+impl ManaUnit {
+    /// Colorless mana, not produced by a snow source.
+    pub const COLORLESS: Self = Self { color: None, snow: false };
+
+    /// Mana of `color`, not produced by a snow source.
+    #[must_use]
+    pub const fn colored(color: Color) -> Self {
+        Self { color: Some(color), snow: false }
+    }
+
+    pub(crate) const fn index(self) -> usize {
+        let color = match self.color {
+            Some(color) => color as usize,
+            None => 5,
+        };
+        color * 2 + self.snow as usize
+    }
+
+    pub(crate) const fn from_index(i: usize) -> Self {
+        let color = if i / 2 == 5 { None } else { Some(ALL_COLORS[i / 2]) };
+        Self { color, snow: i % 2 == 1 }
+    }
+}
+
+impl Display for ManaUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.snow {
+            f.write_str("snow ")?;
+        }
+        match self.color {
+            Some(color) => write!(f, "{{{color}}}"),
+            None => f.write_str("{C}"),
+        }
+    }
+}
+
+/// The [mana pool](https://mtg.wiki/page/Mana_pool) of a player, i.e. the
+/// amount of each [`ManaUnit`] which can be spent.
+///
+/// When displayed, the amounts are written in WUBRG order, followed by
+/// colorless mana, e.g. `2 {W}, 1 snow {G}, 3 {C}`. An empty pool is written
+/// as an empty string.
+///
+/// ```
+/// use mana_symbols::{Color, ManaPool, ManaUnit};
+///
+/// let mut pool = ManaPool::new();
+/// pool.add(ManaUnit::colored(Color::Green), 2);
+/// pool.add(ManaUnit { color: None, snow: true }, 1);
+/// assert_eq!(pool.to_string(), "2 {G}, 1 snow {C}");
+/// assert_eq!(pool.total(), 3);
+///
+/// assert!(pool.remove(ManaUnit::colored(Color::Green), 1));
+/// assert!(!pool.remove(ManaUnit::COLORLESS, 1));
+/// assert_eq!(pool.to_string(), "1 {G}, 1 snow {C}");
+///
+/// pool.clear();
+/// assert!(pool.is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ManaPool {
+    /// The amount of each unit, indexed by `ManaUnit::index`.
+    amounts: [usize; 12],
+}
+
+impl ManaPool {
+    /// An empty mana pool.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { amounts: [0; 12] }
+    }
+
+    /// Add `amount` units of `unit` to the pool. The amount of each unit
+    /// saturates at [`usize::MAX`].
+    pub const fn add(&mut self, unit: ManaUnit, amount: usize) {
+        let i = unit.index();
+        self.amounts[i] = self.amounts[i].saturating_add(amount);
+    }
+
+    /// Remove `amount` units of `unit` from the pool. Returns `false`, without
+    /// removing anything, if the pool does not contain that many.
+    pub const fn remove(&mut self, unit: ManaUnit, amount: usize) -> bool {
+        let available = &mut self.amounts[unit.index()];
+        if *available < amount {
+            return false;
+        }
+        *available -= amount;
+        true
+    }
+
+    /// Remove all mana from the pool, like when a step or phase ends.
+    pub const fn clear(&mut self) {
+        self.amounts = [0; 12];
+    }
+
+    /// The amount of `unit` in the pool.
+    #[must_use]
+    pub const fn amount(&self, unit: ManaUnit) -> usize {
+        self.amounts[unit.index()]
+    }
+
+    /// The amount of mana of `color` in the pool (or of colorless mana if
+    /// `color` is `None`), whether it is snow or not.
+    #[must_use]
+    pub const fn amount_of(&self, color: Option<Color>) -> usize {
+        let normal = self.amount(ManaUnit { color, snow: false });
+        normal.saturating_add(self.amount(ManaUnit { color, snow: true }))
+    }
+
+    /// The amount of mana in the pool which was produced by snow sources.
+    #[must_use]
+    pub fn snow(&self) -> usize {
+        self.units()
+            .filter(|(unit, _)| unit.snow)
+            .map(|(_, amount)| amount)
+            .fold(0, usize::saturating_add)
+    }
+
+    /// The total amount of mana in the pool, saturating at [`usize::MAX`].
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.amounts.iter().copied().fold(0, usize::saturating_add)
+    }
+
+    /// Returns `true` if the pool does not contain any mana.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+
+    /// Returns `true` if the mana in the pool can pay for `cost`, see
+    /// [`ManaPool::solve_payment`].
+    ///
+    /// ```
+    /// use mana_symbols::{Color, ManaPool, ManaUnit, Manas};
+    ///
+    /// let mut pool = ManaPool::new();
+    /// pool.add(ManaUnit::colored(Color::White), 1);
+    /// pool.add(ManaUnit::colored(Color::Blue), 1);
+    ///
+    /// let cost: Manas = "{W/U}{U}".parse().unwrap();
+    /// assert!(pool.can_pay(&cost));
+    ///
+    /// let cost: Manas = "{C}{W}".parse().unwrap();
+    /// assert!(!pool.can_pay(&cost));
+    /// ```
+    #[must_use]
+    pub fn can_pay(&self, cost: &Manas) -> bool {
+        self.solve_payment(cost).is_some()
+    }
+
+    /// Find a way of paying for `cost` using the mana in the pool, i.e. which
+    /// mana pays for each symbol. Returns `None` if the pool can not pay for
+    /// `cost`.
+    ///
+    /// Colorless symbols (`{C}`) can only be paid using colorless mana and snow
+    /// symbols (`{S}`) using mana from snow sources. For hybrid symbols, like
+    /// `{W/U}` and `{2/W}`, every way of paying them is considered. Variables
+    /// (like `{X}`) are chosen to be 0. Infinite generic mana and mana of custom
+    /// colors can never be paid.
+    ///
+    /// Phyrexian symbols are paid using mana when possible, and otherwise
+    /// using 2 life each. Among those payments, one which spends as little mana
+    /// as possible is chosen. To choose the payment differently, use
+    /// [`ManaPool::solve_payment_with`].
+    ///
+    /// ```
+    /// use mana_symbols::{Color, ManaPool, ManaUnit, Manas, SymbolPayment};
+    ///
+    /// let snow_green = ManaUnit { color: Some(Color::Green), snow: true };
+    /// let mut pool = ManaPool::new();
+    /// pool.add(snow_green, 1);
+    /// pool.add(ManaUnit::colored(Color::Black), 2);
+    ///
+    /// let cost: Manas = "{S}{B/P}{2/B}".parse().unwrap();
+    /// let payment = pool.solve_payment(&cost).unwrap();
+    /// assert_eq!(payment.spent().to_string(), "2 {B}, 1 snow {G}");
+    /// assert_eq!(payment.life(), 0);
+    ///
+    /// let (mana, paid) = &payment.symbols()[0];
+    /// assert_eq!(mana.to_string(), "S");
+    /// assert_eq!(paid, &SymbolPayment::Mana([snow_green].into_iter().collect()));
+    /// ```
+    #[must_use]
+    pub fn solve_payment(&self, cost: &Manas) -> Option<Payment> {
+        let objectives = [PaymentObjective::MinimizeLife, PaymentObjective::MinimizeMana];
+        self.solve_payment_with(cost, &objectives)
+    }
+
+    /// Find the best way of paying for `cost` using the mana in the pool, like
+    /// [`ManaPool::solve_payment`], where payments are compared using each
+    /// objective in turn. Later objectives are only used to choose between
+    /// payments which are equally good according to the earlier ones.
+    ///
+    /// Every payment is considered, so unlike paying greedily, a hybrid symbol
+    /// never uses mana which is needed later.
+    ///
+    /// ```
+    /// use mana_symbols::{Color, ColorSet, ManaPool, ManaUnit, Manas, PaymentObjective};
+    ///
+    /// let mut pool = ManaPool::new();
+    /// pool.add(ManaUnit::colored(Color::White), 1);
+    /// pool.add(ManaUnit::colored(Color::Blue), 2);
+    /// pool.add(ManaUnit::COLORLESS, 1);
+    ///
+    /// let cost: Manas = "{1}{W/U}".parse().unwrap();
+    /// let blue: ColorSet = [Color::Blue].into_iter().collect();
+    /// let objectives = [PaymentObjective::Preserve(blue), PaymentObjective::MinimizeColored];
+    /// let payment = pool.solve_payment_with(&cost, &objectives).unwrap();
+    /// assert_eq!(payment.spent().to_string(), "1 {W}, 1 {C}");
+    ///
+    /// // Pay life instead of mana
+    /// let cost: Manas = "{U/P}".parse().unwrap();
+    /// let payment = pool.solve_payment_with(&cost, &[PaymentObjective::MinimizeMana]).unwrap();
+    /// assert_eq!(payment.life(), 2);
+    /// ```
+    #[must_use]
+    pub fn solve_payment_with(
+        &self,
+        cost: &Manas,
+        objectives: &[PaymentObjective],
+    ) -> Option<Payment> {
+        payment::solve(self, cost, objectives)
+    }
+
+    /// Iterate over each unit in the pool together with its amount, in the
+    /// order they are displayed. Units which are not in the pool are skipped.
+    pub fn units(&self) -> impl Iterator<Item = (ManaUnit, usize)> + '_ {
+        self.amounts
+            .iter()
+            .enumerate()
+            .filter(|(_, amount)| **amount != 0)
+            .map(|(i, amount)| (ManaUnit::from_index(i), *amount))
+    }
+}
+
+impl FromIterator<ManaUnit> for ManaPool {
+    fn from_iter<T: IntoIterator<Item = ManaUnit>>(iter: T) -> Self {
+        let mut pool = Self::new();
+        pool.extend(iter);
+        pool
+    }
+}
+
+impl Extend<ManaUnit> for ManaPool {
+    fn extend<T: IntoIterator<Item = ManaUnit>>(&mut self, iter: T) {
+        for unit in iter {
+            self.add(unit, 1);
+        }
+    }
+}
+
+impl Display for ManaPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (unit, amount)) in self.units().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{amount} {unit}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index() {
+        for i in 0..12 {
+            assert_eq!(ManaUnit::from_index(i).index(), i);
+        }
+    }
+
+    #[test]
+    fn add_and_remove() {
+        let snow_u = ManaUnit { color: Some(Color::Blue), snow: true };
+        let mut pool: ManaPool =
+            [snow_u, ManaUnit::colored(Color::Blue), snow_u, ManaUnit::COLORLESS]
+                .into_iter()
+                .collect();
+        assert_eq!(pool.to_string(), "1 {U}, 2 snow {U}, 1 {C}");
+        assert_eq!(pool.amount(snow_u), 2);
+        assert_eq!(pool.amount_of(Some(Color::Blue)), 3);
+        assert_eq!(pool.amount_of(None), 1);
+        assert_eq!(pool.snow(), 2);
+
+        assert!(!pool.remove(snow_u, 3));
+        assert_eq!(pool.amount(snow_u), 2);
+        assert!(pool.remove(snow_u, 2));
+        assert_eq!(pool.snow(), 0);
+        assert_eq!(pool.total(), 2);
+
+        pool.clear();
+        assert_eq!(pool, ManaPool::new());
+        assert_eq!(pool.to_string(), "");
+    }
+
+    #[test]
+    fn saturating_amounts() {
+        let snow_g = ManaUnit { color: Some(Color::Green), snow: true };
+        let mut pool = ManaPool::new();
+        pool.add(ManaUnit::COLORLESS, usize::MAX);
+        pool.add(ManaUnit::COLORLESS, 1);
+        pool.add(ManaUnit::colored(Color::Green), usize::MAX);
+        pool.add(snow_g, usize::MAX);
+        pool.add(ManaUnit { color: None, snow: true }, 1);
+
+        assert_eq!(pool.amount(ManaUnit::COLORLESS), usize::MAX);
+        assert_eq!(pool.amount_of(Some(Color::Green)), usize::MAX);
+        assert_eq!(pool.snow(), usize::MAX);
+        assert_eq!(pool.total(), usize::MAX);
+    }
+
+    fn pool(units: &[(Option<Color>, bool, usize)]) -> ManaPool {
+        let mut pool = ManaPool::new();
+        for &(color, snow, amount) in units {
+            pool.add(ManaUnit { color, snow }, amount);
+        }
+        pool
+    }
+
+    fn can_pay(pool: &ManaPool, cost: &str) -> bool {
+        pool.can_pay(&cost.parse().unwrap())
+    }
+
+    #[test]
+    fn can_pay_colored_and_generic() {
+        let pool = pool(&[(Some(Color::White), false, 2), (Some(Color::Green), false, 1)]);
+        assert!(can_pay(&pool, ""));
+        assert!(can_pay(&pool, "{1}{W}{W}"));
+        assert!(can_pay(&pool, "{2}{G}"));
+        assert!(!can_pay(&pool, "{2}{W}{W}"));
+        assert!(!can_pay(&pool, "{G}{G}"));
+        assert!(can_pay(&pool, "{X}{W}"));
+        assert!(!can_pay(&pool, "{∞}"));
+    }
+
+    #[test]
+    fn can_pay_colorless() {
+        let colored = pool(&[(Some(Color::Blue), false, 3)]);
+        assert!(!can_pay(&colored, "{C}"));
+        assert!(can_pay(&colored, "{C/U}"));
+        let colorless = pool(&[(None, false, 2)]);
+        assert!(can_pay(&colorless, "{C}{1}"));
+        assert!(can_pay(&colorless, "{C/U}"));
+        assert!(!can_pay(&colorless, "{U}"));
+    }
+
+    #[test]
+    fn can_pay_hybrid() {
+        // The blue mana has to pay for `{U}`, so `{W/U}` has to use white
+        let pool = pool(&[(Some(Color::White), false, 1), (Some(Color::Blue), false, 1)]);
+        assert!(can_pay(&pool, "{W/U}{U}"));
+        assert!(can_pay(&pool, "{U}{W/U}"));
+        assert!(!can_pay(&pool, "{W/U}{W/U}{W/U}"));
+        assert!(!can_pay(&pool, "{B/R}"));
+        assert!(can_pay(&pool, "{U/B}{W/B}"));
+    }
+
+    #[test]
+    fn can_pay_mono_hybrid() {
+        let pool = pool(&[(Some(Color::White), false, 1), (Some(Color::Red), false, 2)]);
+        assert!(can_pay(&pool, "{2/W}{2/W}"));
+        assert!(can_pay(&pool, "{2/W}{W}"));
+        assert!(!can_pay(&pool, "{2/W}{2/W}{W}"));
+        assert!(can_pay(&pool, "{2/R}{2/R}{2/W}"));
+        assert!(!can_pay(&pool, "{2/R}{2/W}{2/W}"));
+    }
+
+    #[test]
+    fn can_pay_snow() {
+        let pool = pool(&[(Some(Color::Green), true, 1), (None, false, 1)]);
+        assert!(can_pay(&pool, "{S}{1}"));
+        assert!(!can_pay(&pool, "{S}{G}"));
+        assert!(can_pay(&pool, "{S}{C}"));
+        assert!(!can_pay(&pool, "{S}{S}"));
+    }
+
+    #[test]
+    fn can_pay_phyrexian() {
+        let pool = pool(&[(Some(Color::Black), false, 1)]);
+        assert!(can_pay(&pool, "{B/P}{B/P}{B}"));
+        assert!(can_pay(&pool, "{G/W/P}{1}"));
+    }
+}
@@ -0,0 +1,192 @@
+use std::{
+    fmt::Display,
+    iter::Sum,
+    ops::{Add, AddAssign, Mul},
+};
+
+/// A [mana value](https://mtg.wiki/page/Mana_value) which may be fractional.
+///
+/// Some cards from [Un-sets](https://mtg.wiki/page/Un-set) have mana symbols
+/// worth half a mana, so mana values are stored as a number of halves.
+///
+/// ```
+/// use mana_symbols::ManaValue;
+///
+/// let a = ManaValue::from(3);
+/// let b = ManaValue::from_halves(1);
+///
+/// assert_eq!((a + b).to_string(), "3.5");
+/// assert_eq!((a + b).floor(), 3);
+/// assert_eq!(usize::try_from(a), Ok(3));
+/// assert!(usize::try_from(a + b).is_err());
+/// ```
+///
+/// The mana value of `{∞}` is [`ManaValue::INFINITY`], which stays infinite
+/// when other mana values are added to it. Arithmetic saturates, so a finite
+/// mana value which is too large to be stored also becomes infinite, just
+/// like [`Manas::mana_value`](crate::Manas::mana_value) saturates at
+/// `usize::MAX`.
+///
+/// ```
+/// use mana_symbols::ManaValue;
+///
+/// let infinity = ManaValue::INFINITY + ManaValue::from(7);
+/// assert!(infinity.is_infinite());
+/// assert_eq!(infinity.to_string(), "∞");
+/// assert_eq!(infinity.floor(), usize::MAX);
+///
+/// let huge = ManaValue::from(usize::MAX / 2) + ManaValue::from(1);
+/// assert!(huge.is_infinite());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ManaValue {
+    halves: usize,
+}
+
+/// Error when converting a fractional [`ManaValue`] to an integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FractionalManaValue;
+
+impl Display for FractionalManaValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("mana value is not an integer")
+    }
+}
+
+impl std::error::Error for FractionalManaValue {}
+
+impl ManaValue {
+    /// A mana value of zero.
+    pub const ZERO: Self = Self { halves: 0 };
+
+    /// An infinite mana value, e.g. of `{∞}`. It is not an integer and
+    /// rounding it gives `usize::MAX`. It is also the result of any
+    /// arithmetic which would overflow.
+    pub const INFINITY: Self = Self { halves: usize::MAX };
+
+    /// A mana value of `n / 2`.
+    #[must_use]
+    pub const fn from_halves(n: usize) -> Self {
+        Self { halves: n }
+    }
+
+    /// The number of halves in the mana value, i.e. the mana value times two.
+    #[must_use]
+    pub const fn halves(self) -> usize {
+        self.halves
+    }
+
+    /// Whether the mana value is an integer.
+    #[must_use]
+    pub const fn is_integer(self) -> bool {
+        self.halves.is_multiple_of(2)
+    }
+
+    /// Whether the mana value is [`ManaValue::INFINITY`].
+    #[must_use]
+    pub const fn is_infinite(self) -> bool {
+        self.halves == usize::MAX
+    }
+
+    /// The mana value rounded down, as used by the rules when a card asks
+    /// for a mana value (see rule 107.1a).
+    #[must_use]
+    pub const fn floor(self) -> usize {
+        if self.is_infinite() { usize::MAX } else { self.halves / 2 }
+    }
+
+    /// The mana value rounded up.
+    #[must_use]
+    pub const fn ceil(self) -> usize {
+        if self.is_infinite() { usize::MAX } else { self.halves.div_ceil(2) }
+    }
+
+    /// The mana value as a floating point number.
+    #[must_use]
+    pub fn as_f64(self) -> f64 {
+        if self.is_infinite() { f64::INFINITY } else { self.halves as f64 / 2.0 }
+    }
+}
+
+impl Display for ManaValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_infinite() {
+            f.write_str("∞")
+        } else if self.is_integer() {
+            self.floor().fmt(f)
+        } else {
+            write!(f, "{}.5", self.floor())
+        }
+    }
+}
+
+impl From<usize> for ManaValue {
+    fn from(value: usize) -> Self {
+        Self { halves: value.saturating_mul(2) }
+    }
+}
+
+impl TryFrom<ManaValue> for usize {
+    type Error = FractionalManaValue;
+
+    fn try_from(value: ManaValue) -> Result<Self, Self::Error> {
+        if value.is_integer() { Ok(value.floor()) } else { Err(FractionalManaValue) }
+    }
+}
+
+impl Add for ManaValue {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self { halves: self.halves.saturating_add(rhs.halves) }
+    }
+}
+
+impl AddAssign for ManaValue {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Mul<usize> for ManaValue {
+    type Output = Self;
+
+    fn mul(self, rhs: usize) -> Self::Output {
+        Self { halves: self.halves.saturating_mul(rhs) }
+    }
+}
+
+impl Sum for ManaValue {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Self> for ManaValue {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saturates_to_infinity() {
+        let half = ManaValue::from_halves(1);
+        assert!(!ManaValue::from(usize::MAX / 2).is_infinite());
+        assert!(ManaValue::from(usize::MAX / 2 + 1).is_infinite());
+        assert!((ManaValue::from(usize::MAX / 2) + half).is_infinite());
+        assert!((ManaValue::from(usize::MAX / 4 + 1) * 2).is_infinite());
+        assert_eq!(
+            [ManaValue::from(usize::MAX / 2), half].into_iter().sum::<ManaValue>(),
+            ManaValue::INFINITY
+        );
+        assert_eq!(ManaValue::from(usize::MAX).to_string(), "∞");
+        assert_eq!(
+            ManaValue::from_halves(usize::MAX - 1).to_string(),
+            (usize::MAX / 2).to_string()
+        );
+    }
+}
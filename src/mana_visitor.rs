@@ -0,0 +1,83 @@
+use crate::{Color, CustomColor};
+
+/// Visitor over every kind of [`Mana`][crate::Mana] symbol.
+///
+/// Used by [`Mana::accept`][crate::Mana::accept]. Every kind of mana symbol has
+/// its own method, so adding a new kind of mana symbol to this crate will
+/// result in a compile error for implementors, instead of being silently
+/// ignored.
+///
+/// # Example
+///
+/// ```
+/// use mana_symbols::{Color, CustomColor, Manas, ManaVisitor};
+///
+/// /// Whether a mana symbol can only be paid with colored mana.
+/// struct ColoredOnly;
+///
+/// impl ManaVisitor for ColoredOnly {
+///     type Output = bool;
+///
+///     fn visit_colored(&mut self, _color: Color) -> bool { true }
+///     fn visit_phyrexian(&mut self, _color: Color) -> bool { false }
+///     fn visit_generic(&mut self, _value: usize) -> bool { false }
+///     fn visit_variable(&mut self, _name: char) -> bool { false }
+///     fn visit_infinity(&mut self) -> bool { false }
+///     fn visit_generic_hybrid(&mut self, _value: usize, _color: Color) -> bool { false }
+///     fn visit_colorless_hybrid(&mut self, _color: Color) -> bool { false }
+///     fn visit_hybrid(&mut self, _a: Color, _b: Color, phyrexian: bool) -> bool { !phyrexian }
+///     fn visit_colorless(&mut self) -> bool { false }
+///     fn visit_snow(&mut self) -> bool { false }
+///     fn visit_custom(&mut self, _color: CustomColor) -> bool { true }
+/// }
+///
+/// let manas: Manas = "{2}{U}{U/B}{R/P}".parse().unwrap();
+/// let manas: Vec<_> = manas.into();
+/// let count = manas.iter().filter(|x| x.accept(&mut ColoredOnly)).count();
+/// assert_eq!(count, 2);
+/// ```
+pub trait ManaVisitor {
+    /// The value returned when visiting a mana symbol.
+    type Output;
+
+    /// Colored mana, e.g. `{U}`.
+    fn visit_colored(&mut self, color: Color) -> Self::Output;
+
+    /// Phyrexian colored mana, e.g. `{U/P}`.
+    fn visit_phyrexian(&mut self, color: Color) -> Self::Output;
+
+    /// Half of a colored mana symbol, e.g. `{HU}`. Visits it as colored mana
+    /// unless overridden.
+    fn visit_half(&mut self, color: Color) -> Self::Output {
+        self.visit_colored(color)
+    }
+
+    /// Generic mana of a fixed amount, e.g. `{2}`.
+    fn visit_generic(&mut self, value: usize) -> Self::Output;
+
+    /// Generic mana of a variable amount, where `name` is `'X'`, `'Y'` or
+    /// `'Z'`.
+    fn visit_variable(&mut self, name: char) -> Self::Output;
+
+    /// Infinite generic mana, `{∞}`.
+    fn visit_infinity(&mut self) -> Self::Output;
+
+    /// Hybrid generic mana, e.g. `{2/U}`.
+    fn visit_generic_hybrid(&mut self, value: usize, color: Color) -> Self::Output;
+
+    /// Hybrid colorless mana, e.g. `{C/U}`.
+    fn visit_colorless_hybrid(&mut self, color: Color) -> Self::Output;
+
+    /// Hybrid mana of two colors, e.g. `{U/B}` or `{U/B/P}`.
+    fn visit_hybrid(&mut self, a: Color, b: Color, phyrexian: bool) -> Self::Output;
+
+    /// Colorless mana, `{C}`.
+    fn visit_colorless(&mut self) -> Self::Output;
+
+    /// Snow mana, `{S}`.
+    fn visit_snow(&mut self) -> Self::Output;
+
+    /// Mana of a custom color, e.g. `{O}` (see
+    /// [`ColorRegistry`][crate::ColorRegistry]).
+    fn visit_custom(&mut self, color: CustomColor) -> Self::Output;
+}
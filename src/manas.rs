@@ -1,13 +1,29 @@
-use std::{
-    fmt::{Display, Write},
-    str::FromStr,
-};
+use std::{cmp::Ordering, fmt::Display, iter::Sum, str::FromStr};
+#[cfg(feature = "svg")]
+use std::{fmt::Write, io};
 
 use nom::{Finish, IResult, Parser, combinator::eof, multi::many0, sequence::terminated};
-use svg::{Document, node::element::SVG};
+#[cfg(feature = "svg")]
+use svg::{
+    Document,
+    node::element::{Rectangle, SVG},
+};
 
+#[cfg(feature = "svg")]
+use crate::{
+    AssetWriter, HtmlOptions, Plate, SVG_WIDTH, SVGConfig, Theme,
+    compatible::make_compatible,
+    io_writer::write_io,
+    mana::{svg_data_uri, with_description},
+    minify::minify,
+    typst::write_typst,
+    write_canonical_svg,
+};
 use crate::{
-    Color, GenericMana, Mana, SVG_WIDTH, SVGConfig, SingleMana, SplitMana, color_set::ColorSet,
+    Color, ColorRegistry, CustomColor, DisplayOptions, EmojiMap, GenericMana, Mana, ManaGroups,
+    ManaPool, ManaValue, ManasBuilder, ManasDisplay, PaymentObjective, PipPolicy, SingleMana,
+    SlackEmojiMap, SortStyle, SplitMana, Violation, color_set::ColorSet,
+    html_spans::write_html_span, latex::write_latex, unicode::write_unicode,
 };
 
 /// Collection of mana symbols
@@ -38,7 +54,7 @@ use crate::{
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Manas {
-    manas: Vec<Mana>,
+    pub(crate) manas: Vec<Mana>,
 }
 
 impl Display for Manas {
@@ -50,6 +66,74 @@ impl Display for Manas {
     }
 }
 
+/// Collections are ordered by their total [mana value](Manas::mana_value)
+/// first. Collections with equal mana value are then compared symbol by symbol
+/// (see the ordering of [`Mana`]), where a shorter collection comes before any
+/// longer collection that it is a prefix of.
+///
+/// This makes the order consistent with [`Eq`], so `{U}{W}` and `{W}{U}` are
+/// still different. Use [`Manas::sort`] first if the order of the symbols
+/// should not matter.
+///
+/// ```
+/// use std::collections::BTreeSet;
+///
+/// use mana_symbols::Manas;
+///
+/// let costs: BTreeSet<Manas> =
+///     ["{3}", "{W}{U}", "{1}{U}", "{U}"].iter().map(|s| s.parse().unwrap()).collect();
+/// let costs: Vec<String> = costs.iter().map(Manas::to_string).collect();
+///
+/// assert_eq!(costs, ["{U}", "{1}{U}", "{W}{U}", "{3}"]);
+/// ```
+impl Ord for Manas {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.mana_value().cmp(&other.mana_value()).then_with(|| self.manas.cmp(&other.manas))
+    }
+}
+
+impl PartialOrd for Manas {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The total [mana value](Manas::mana_value) of several collections.
+///
+/// ```
+/// use mana_symbols::Manas;
+///
+/// let deck: Vec<Manas> = vec!["{2}{U}".parse().unwrap(), "{X}{R}{R}".parse().unwrap()];
+/// let total: usize = deck.iter().sum();
+/// assert_eq!(total, 5);
+/// ```
+impl Sum<Manas> for usize {
+    fn sum<I: Iterator<Item = Manas>>(iter: I) -> Self {
+        iter.map(|x| x.mana_value()).fold(0, usize::saturating_add)
+    }
+}
+
+/// The total [mana value](Manas::mana_value) of several collections.
+impl<'a> Sum<&'a Manas> for usize {
+    fn sum<I: Iterator<Item = &'a Manas>>(iter: I) -> Self {
+        iter.map(Manas::mana_value).fold(0, usize::saturating_add)
+    }
+}
+
+/// The total [mana value](Manas::exact_mana_value) of several collections.
+impl Sum<Manas> for ManaValue {
+    fn sum<I: Iterator<Item = Manas>>(iter: I) -> Self {
+        iter.map(|x| x.exact_mana_value()).sum()
+    }
+}
+
+/// The total [mana value](Manas::exact_mana_value) of several collections.
+impl<'a> Sum<&'a Manas> for ManaValue {
+    fn sum<I: Iterator<Item = &'a Manas>>(iter: I) -> Self {
+        iter.map(Manas::exact_mana_value).sum()
+    }
+}
+
 impl FromStr for Manas {
     type Err = ();
 
@@ -64,11 +148,640 @@ impl FromStr for Manas {
 }
 
 impl Manas {
+    /// Create a [`ManasBuilder`] to construct mana symbols programmatically.
+    #[must_use]
+    pub fn builder() -> ManasBuilder {
+        ManasBuilder::default()
+    }
+
     /// The total [mana value](https://mtg.wiki/page/Mana_value) (see
-    /// [`Mana::mana_value`]).
+    /// [`Mana::mana_value`]). Costs with `{∞}` have a mana value of
+    /// `usize::MAX`.
     #[must_use]
     pub fn mana_value(&self) -> usize {
-        self.manas.iter().map(Mana::mana_value).sum()
+        self.manas.iter().map(Mana::mana_value).fold(0, usize::saturating_add)
+    }
+
+    /// The total amount of generic mana with a fixed value, i.e. excluding
+    /// `X`, `Y`, `Z` and hybrid generic mana.
+    ///
+    /// ```
+    /// use mana_symbols::Manas;
+    ///
+    /// let manas: Manas = "{X}{2}{1}{2/W}{W}".parse().unwrap();
+    /// assert_eq!(manas.generic_amount(), 3);
+    /// ```
+    #[must_use]
+    pub fn generic_amount(&self) -> usize {
+        self.manas
+            .iter()
+            .map(|x| if let Mana::Generic(GenericMana::Number(n)) = x { *n } else { 0 })
+            .fold(0, usize::saturating_add)
+    }
+
+    /// Returns a copy without any generic mana symbols, including `X`, `Y` and
+    /// `Z`. Hybrid generic mana is kept.
+    ///
+    /// ```
+    /// use mana_symbols::Manas;
+    ///
+    /// let manas: Manas = "{X}{2}{2/W}{W}".parse().unwrap();
+    /// assert_eq!(manas.without_generic().to_string(), "{2/W}{W}");
+    /// ```
+    #[must_use]
+    pub fn without_generic(&self) -> Self {
+        let manas = self.manas.iter().filter(|x| !matches!(x, Mana::Generic(_))).copied().collect();
+        Self { manas }
+    }
+
+    /// The number of colored pips of each color, indexed by [`Color`] (i.e.
+    /// in the order `W`, `U`, `B`, `R`, `G`). Which symbols are counted is
+    /// decided by `policy`.
+    ///
+    /// ```
+    /// use mana_symbols::{Color, Manas, PipPolicy};
+    ///
+    /// let manas: Manas = "{2}{U}{U}{U/B}{B/P}".parse().unwrap();
+    ///
+    /// let pips = manas.pip_count(PipPolicy::default());
+    /// assert_eq!(pips, [0, 3, 2, 0, 0]);
+    /// assert_eq!(pips[Color::Blue as usize], 3);
+    ///
+    /// let pips = manas.pip_count(PipPolicy { hybrid: false, phyrexian: false });
+    /// assert_eq!(pips, [0, 2, 0, 0, 0]);
+    /// ```
+    #[must_use]
+    pub fn pip_count(&self, policy: PipPolicy) -> [usize; 5] {
+        let mut pips = [0; 5];
+        for mana in &self.manas {
+            let counted = match mana {
+                Mana::Single(SingleMana::Normal(_) | SingleMana::Half(_)) => true,
+                Mana::Single(SingleMana::Phyrexian(_)) => policy.phyrexian,
+                Mana::Split(SplitMana::Duo { phyrexian: true, .. }) => {
+                    policy.hybrid && policy.phyrexian
+                }
+                Mana::Split(_) => policy.hybrid,
+                Mana::Generic(_) | Mana::Colorless | Mana::Snow | Mana::Custom(_) => false,
+            };
+            if !counted {
+                continue;
+            }
+
+            let right = mana.right_half_color().unwrap();
+            pips[right as usize] += 1;
+            if let Some(left) = mana.left_half_color()
+                && left != right
+            {
+                pips[left as usize] += 1;
+            }
+        }
+        pips
+    }
+
+    /// How much each color is needed, indexed by [`Color`] (i.e. in the order
+    /// `W`, `U`, `B`, `R`, `G`).
+    ///
+    /// Colored mana (including phyrexian mana) contributes `1.0` to its color,
+    /// while each half of a hybrid mana symbol contributes `0.5`. Halves which
+    /// are not colored, such as the `2` in `{2/W}`, contribute nothing.
+    ///
+    /// ```
+    /// use mana_symbols::Manas;
+    ///
+    /// let manas: Manas = "{2}{U}{U/B}{2/B}".parse().unwrap();
+    /// assert_eq!(manas.color_intensity(), [0.0, 1.5, 1.0, 0.0, 0.0]);
+    /// ```
+    #[must_use]
+    pub fn color_intensity(&self) -> [f64; 5] {
+        let mut intensity = [0.0; 5];
+        for mana in &self.manas {
+            match mana {
+                Mana::Single(single) => intensity[single.color() as usize] += 1.0,
+                Mana::Split(split) => {
+                    if let Some(left) = split.left_half_color() {
+                        intensity[left as usize] += 0.5;
+                    }
+                    intensity[split.right_half_color() as usize] += 0.5;
+                }
+                Mana::Generic(_) | Mana::Colorless | Mana::Snow | Mana::Custom(_) => {}
+            }
+        }
+        intensity
+    }
+
+    /// The colors of every mana symbol, as used for [color identity][mw:ci]
+    /// (see rule 903.4). Both halves of hybrid mana symbols and phyrexian
+    /// mana symbols contribute their colors.
+    ///
+    /// A [`ColorSet`] only contains the five colors of the color pie, so
+    /// custom colors are not included (see [`Manas::custom_colors`]).
+    ///
+    /// ```
+    /// use mana_symbols::{Color, Manas};
+    ///
+    /// let manas: Manas = "{2}{W/U}{B/P}".parse().unwrap();
+    /// let identity = manas.color_identity();
+    ///
+    /// assert!(identity.contains(Color::White));
+    /// assert!(identity.contains(Color::Blue));
+    /// assert!(identity.contains(Color::Black));
+    /// assert!(!identity.contains(Color::Red));
+    /// ```
+    ///
+    /// [mw:ci]: https://mtg.wiki/page/Color_identity
+    #[must_use]
+    pub fn color_identity(&self) -> ColorSet {
+        let mut colors = ColorSet::new();
+        for mana in &self.manas {
+            if let Some(left) = mana.left_half_color() {
+                colors.insert(left);
+            }
+            if let Some(right) = mana.right_half_color() {
+                colors.insert(right);
+            }
+        }
+        colors
+    }
+
+    /// The [custom colors](CustomColor) of every mana symbol, sorted and
+    /// without duplicates.
+    ///
+    /// ```
+    /// use mana_symbols::{ColorRegistry, Manas};
+    ///
+    /// let mut registry = ColorRegistry::new();
+    /// let purple = registry.register('O', "purple", "#c9a0dc").unwrap();
+    ///
+    /// let manas = Manas::from_str_with("{O}{O}{W}", &registry).unwrap();
+    /// assert_eq!(manas.custom_colors(), [purple]);
+    /// assert_eq!(manas.color_identity().to_string(), "W");
+    /// ```
+    #[must_use]
+    pub fn custom_colors(&self) -> Vec<CustomColor> {
+        let mut customs: Vec<CustomColor> = (self.manas.iter())
+            .filter_map(|mana| if let Mana::Custom(color) = mana { Some(*color) } else { None })
+            .collect();
+        customs.sort_unstable();
+        customs.dedup();
+        customs
+    }
+
+    /// The smallest set of colors with which every mana symbol can be paid.
+    ///
+    /// Hybrid mana symbols are paid with their cheapest half, so hybrid
+    /// generic mana (e.g. `{2/W}`) and hybrid colorless mana (e.g. `{C/W}`)
+    /// never need any colors. Phyrexian mana symbols are paid with life. If
+    /// several sets of colors are equally small, the same one is always
+    /// chosen.
+    ///
+    /// Mana of a custom color can only be paid with mana of that color, which
+    /// a [`ColorSet`] cannot contain, so it is not included (see
+    /// [`Manas::custom_colors`]).
+    ///
+    /// ```
+    /// use mana_symbols::{Color, Manas};
+    ///
+    /// let manas: Manas = "{U}{W/U}{U/B}{2/R}{G/P}".parse().unwrap();
+    /// let colors = manas.minimum_colors();
+    ///
+    /// assert!(colors.contains(Color::Blue));
+    /// assert!(!colors.contains(Color::White));
+    /// assert!(!colors.contains(Color::Red));
+    /// assert!(!colors.contains(Color::Green));
+    /// ```
+    #[must_use]
+    pub fn minimum_colors(&self) -> ColorSet {
+        let mut required = ColorSet::new();
+        let mut hybrids = Vec::new();
+        for mana in &self.manas {
+            match mana {
+                Mana::Single(SingleMana::Normal(color) | SingleMana::Half(color)) => {
+                    required.insert(*color);
+                }
+                Mana::Split(SplitMana::Duo { a, b, phyrexian: false }) => hybrids.push((*a, *b)),
+                _ => {}
+            }
+        }
+
+        (0..32)
+            .map(ColorSet::from_bits)
+            .filter(|set| required.is_subset(set))
+            .filter(|set| hybrids.iter().all(|(a, b)| set.contains(*a) || set.contains(*b)))
+            .min_by_key(ColorSet::len)
+            .unwrap()
+    }
+
+    /// Whether the mana symbols can be paid using only mana of the given
+    /// `colors` and colorless mana. If `life` is true, phyrexian mana symbols
+    /// may be paid with life instead.
+    ///
+    /// Generic mana, colorless mana and the colorless half of hybrid mana
+    /// symbols (e.g. `{2/W}` or `{C/W}`) can always be paid with colorless
+    /// mana. Snow mana is assumed to be paid from a snow source, while mana of
+    /// a custom color can never be paid.
+    ///
+    /// ```
+    /// use mana_symbols::{Color, ColorSet, Manas};
+    ///
+    /// let mut colors = ColorSet::new();
+    /// colors.insert(Color::Blue);
+    ///
+    /// let manas: Manas = "{2}{U}{U/B}{C/W}".parse().unwrap();
+    /// assert!(manas.can_be_paid_with(colors, false));
+    ///
+    /// let manas: Manas = "{1}{B/P}".parse().unwrap();
+    /// assert!(!manas.can_be_paid_with(colors, false));
+    /// assert!(manas.can_be_paid_with(colors, true));
+    /// ```
+    #[must_use]
+    pub fn can_be_paid_with(&self, colors: ColorSet, life: bool) -> bool {
+        self.manas.iter().all(|mana| match *mana {
+            Mana::Single(SingleMana::Normal(color) | SingleMana::Half(color)) => {
+                colors.contains(color)
+            }
+            Mana::Single(SingleMana::Phyrexian(color)) => life || colors.contains(color),
+            Mana::Split(SplitMana::Duo { a, b, phyrexian }) => {
+                (phyrexian && life) || colors.contains(a) || colors.contains(b)
+            }
+            Mana::Split(SplitMana::Mono { .. } | SplitMana::Colorless { .. })
+            | Mana::Generic(_)
+            | Mana::Colorless
+            | Mana::Snow => true,
+            Mana::Custom(_) => false,
+        })
+    }
+
+    /// The least life which has to be paid for phyrexian mana symbols when
+    /// paying with the mana of `pool`, or `None` if `pool` can not pay for
+    /// the symbols even when paying life. See [`ManaPool::solve_payment`].
+    ///
+    /// ```
+    /// use mana_symbols::{Color, ManaPool, ManaUnit, Manas};
+    ///
+    /// let mut pool = ManaPool::new();
+    /// pool.add(ManaUnit::colored(Color::Black), 2);
+    ///
+    /// let manas: Manas = "{1}{B/P}{B/P}".parse().unwrap();
+    /// assert_eq!(manas.min_life_cost(&pool), Some(2));
+    /// assert_eq!(manas.max_life_cost(), 4);
+    ///
+    /// let manas: Manas = "{3}{B/P}".parse().unwrap();
+    /// assert_eq!(manas.min_life_cost(&pool), None);
+    /// ```
+    #[must_use]
+    pub fn min_life_cost(&self, pool: &ManaPool) -> Option<usize> {
+        let payment = pool.solve_payment_with(self, &[PaymentObjective::MinimizeLife])?;
+        Some(payment.life())
+    }
+
+    /// The most life which can be paid for phyrexian mana symbols, i.e. 2 life
+    /// for each of them.
+    #[must_use]
+    pub fn max_life_cost(&self) -> usize {
+        let phyrexian = self
+            .manas
+            .iter()
+            .filter(|mana| {
+                matches!(
+                    mana,
+                    Mana::Single(SingleMana::Phyrexian(_))
+                        | Mana::Split(SplitMana::Duo { phyrexian: true, .. })
+                )
+            })
+            .count();
+        2 * phyrexian
+    }
+
+    /// Whether every mana pool which can pay for `self` can also pay for
+    /// `other`, i.e. `other` is never harder to pay for than `self`.
+    ///
+    /// Mana pools contain mana of each color, colorless mana, snow mana and
+    /// mana of each custom color, where `{S}` can only be paid with snow mana
+    /// and each custom color can only be paid with mana of that color.
+    /// Phyrexian mana symbols are paid with mana, not life, while `X`, `Y` and
+    /// `Z` are zero. No mana pool can pay for `{∞}`, so a cost containing it
+    /// is at least as hard as every cost.
+    ///
+    /// ```
+    /// use mana_symbols::Manas;
+    ///
+    /// let a: Manas = "{1}{U}{U}".parse().unwrap();
+    /// let b: Manas = "{2}{U}".parse().unwrap();
+    /// let c: Manas = "{1}{U/B}{U}".parse().unwrap();
+    ///
+    /// assert!(a.is_at_least_as_hard_as(&b));
+    /// assert!(!b.is_at_least_as_hard_as(&a));
+    /// assert!(a.is_at_least_as_hard_as(&c));
+    /// assert!(!c.is_at_least_as_hard_as(&a));
+    /// ```
+    #[must_use]
+    pub fn is_at_least_as_hard_as(&self, other: &Self) -> bool {
+        let mut customs = self.custom_colors();
+        customs.extend(other.custom_colors());
+        customs.sort_unstable();
+        customs.dedup();
+        let kinds = 7 + customs.len();
+        let Some(requirements) = self.requirements(&customs) else {
+            return true;
+        };
+        let Some(others) = other.requirements(&customs) else {
+            return false;
+        };
+
+        requirements.into_iter().all(|(required, total)| {
+            let generic = total - required.iter().sum::<usize>();
+
+            // Any extra mana beyond these amounts will not help pay for `other`
+            let caps: Vec<usize> = (0..kinds)
+                .map(|i| {
+                    let needed = others.iter().map(|(b, _)| b[i].saturating_sub(required[i]));
+                    needed.max().unwrap_or(0).min(generic)
+                })
+                .collect();
+
+            // Go through every way to pay for the generic mana, up to the caps
+            let mut extra = vec![0; kinds];
+            loop {
+                let spent: usize = extra.iter().sum();
+                let saturated = (0..kinds).any(|i| extra[i] == caps[i]);
+                if spent == generic || (spent < generic && saturated) {
+                    let payable = others.iter().any(|(b, other_total)| {
+                        *other_total <= total && (0..kinds).all(|i| b[i] <= required[i] + extra[i])
+                    });
+                    if !payable {
+                        return false;
+                    }
+                }
+
+                let Some(i) = (0..kinds).find(|&i| extra[i] < caps[i]) else {
+                    return true;
+                };
+                extra[i] += 1;
+                extra[..i].fill(0);
+            }
+        })
+    }
+
+    /// Every combination of mana needed to pay for the mana symbols, as the
+    /// amount of each color (indexed by [`Color`]), colorless (index 5), snow
+    /// (index 6) and each of `customs` (from index 7), together with the total
+    /// amount of mana, or `None` if no mana pool can pay for them.
+    fn requirements(&self, customs: &[CustomColor]) -> Option<Vec<(Vec<usize>, usize)>> {
+        const COLORLESS: usize = 5;
+        const SNOW: usize = 6;
+        const CUSTOM: usize = 7;
+
+        let mut requirements = vec![(vec![0; CUSTOM + customs.len()], 0)];
+        for mana in &self.manas {
+            let choices: &[(Option<usize>, usize)] = match *mana {
+                Mana::Single(single) => &[(Some(single.color() as usize), 1)],
+                Mana::Split(SplitMana::Mono { value, color }) => {
+                    &[(None, value), (Some(color as usize), 1)]
+                }
+                Mana::Split(SplitMana::Colorless { color }) => {
+                    &[(Some(COLORLESS), 1), (Some(color as usize), 1)]
+                }
+                Mana::Split(SplitMana::Duo { a, b, .. }) => {
+                    &[(Some(a as usize), 1), (Some(b as usize), 1)]
+                }
+                Mana::Generic(GenericMana::Number(n)) => &[(None, n)],
+                Mana::Generic(GenericMana::Infinity) => return None,
+                Mana::Generic(_) => &[(None, 0)],
+                Mana::Colorless => &[(Some(COLORLESS), 1)],
+                Mana::Snow => &[(Some(SNOW), 1)],
+                Mana::Custom(color) => {
+                    let i = customs.iter().position(|x| *x == color).map(|i| CUSTOM + i);
+                    &[(i, 1)]
+                }
+            };
+
+            requirements = requirements
+                .iter()
+                .flat_map(|(required, total)| {
+                    choices.iter().map(move |(kind, amount)| {
+                        let mut required = required.clone();
+                        if let Some(kind) = kind {
+                            required[*kind] += amount;
+                        }
+                        (required, total + amount)
+                    })
+                })
+                .collect();
+            requirements.sort_unstable();
+            requirements.dedup();
+        }
+        Some(requirements)
+    }
+
+    /// Every cost obtainable by choosing how to pay each hybrid and phyrexian
+    /// mana symbol, without duplicates (see [`Manas::semantically_eq`]).
+    ///
+    /// Hybrid mana symbols are replaced by one of their halves. Phyrexian mana
+    /// symbols are either replaced by their color, or removed when they are
+    /// paid with life.
+    ///
+    /// ```
+    /// use mana_symbols::Manas;
+    ///
+    /// let manas: Manas = "{1}{2/W}{U/P}".parse().unwrap();
+    /// let costs: Vec<String> = manas.expand_hybrids().map(|x| x.to_string()).collect();
+    ///
+    /// assert_eq!(costs, ["{1}{2}{U}", "{1}{2}", "{1}{W}{U}", "{1}{W}"]);
+    /// ```
+    pub fn expand_hybrids(&self) -> impl Iterator<Item = Self> {
+        let normal = |color| Some(Mana::Single(SingleMana::Normal(color)));
+
+        let mut costs = vec![Self { manas: Vec::new() }];
+        for mana in &self.manas {
+            let choices = match *mana {
+                Mana::Single(SingleMana::Phyrexian(color)) => vec![normal(color), None],
+                Mana::Split(SplitMana::Mono { value, color }) => {
+                    vec![Some(Mana::Generic(GenericMana::Number(value))), normal(color)]
+                }
+                Mana::Split(SplitMana::Colorless { color }) => {
+                    vec![Some(Mana::Colorless), normal(color)]
+                }
+                Mana::Split(SplitMana::Duo { a, b, phyrexian }) => {
+                    if phyrexian {
+                        vec![normal(a), normal(b), None]
+                    } else {
+                        vec![normal(a), normal(b)]
+                    }
+                }
+                Mana::Single(SingleMana::Normal(_) | SingleMana::Half(_))
+                | Mana::Generic(_)
+                | Mana::Colorless
+                | Mana::Snow
+                | Mana::Custom(_) => vec![Some(*mana)],
+            };
+
+            costs = costs
+                .iter()
+                .flat_map(|cost| {
+                    choices.iter().map(|choice| {
+                        let mut cost = cost.clone();
+                        cost.manas.extend(*choice);
+                        cost
+                    })
+                })
+                .collect();
+        }
+
+        let mut unique: Vec<Self> = Vec::new();
+        for cost in costs {
+            if !unique.iter().any(|x| x.semantically_eq(&cost)) {
+                unique.push(cost);
+            }
+        }
+        unique.into_iter()
+    }
+
+    /// Whether a card with these mana symbols can be in a deck of the given
+    /// color identity, e.g. in [Commander](https://mtg.wiki/page/Commander).
+    ///
+    /// Both colors of a hybrid mana symbol must be in `identity`, even if
+    /// only one of them is needed to pay for it. The same is true for phyrexian
+    /// mana symbols, even though they can be paid with life (see
+    /// [`Manas::color_identity`]).
+    ///
+    /// A [`ColorSet`] cannot contain custom colors, so mana symbols with mana
+    /// of a custom color never fit any identity.
+    ///
+    /// ```
+    /// use mana_symbols::{Color, ColorSet, Manas};
+    ///
+    /// let mut identity = ColorSet::new();
+    /// identity.insert(Color::White);
+    ///
+    /// let w: Manas = "{1}{W}".parse().unwrap();
+    /// let wu: Manas = "{1}{W/U}".parse().unwrap();
+    /// let u_phyrexian: Manas = "{1}{U/P}".parse().unwrap();
+    ///
+    /// assert!(w.fits_identity(identity));
+    /// assert!(!wu.fits_identity(identity));
+    /// assert!(!u_phyrexian.fits_identity(identity));
+    /// ```
+    #[must_use]
+    pub fn fits_identity(&self, identity: ColorSet) -> bool {
+        self.custom_colors().is_empty() && self.color_identity().is_subset(&identity)
+    }
+
+    /// Split the mana symbols into generic mana (including `X`, `Y` and `Z`)
+    /// and all other mana symbols, keeping their order.
+    ///
+    /// ```
+    /// use mana_symbols::{Mana, Manas};
+    ///
+    /// let manas: Manas = "{X}{3}{2/W}{W}".parse().unwrap();
+    /// let (generic, colored) = manas.split_generic_colored();
+    /// assert_eq!(generic.to_string(), "{X}{3}");
+    /// assert_eq!(colored.to_string(), "{2/W}{W}");
+    ///
+    /// // Reduce the cost by {2} and recombine the parts
+    /// let generic = generic.with_x(0).generic_amount().saturating_sub(2);
+    /// let mut reduced: Vec<Mana> = vec![format!("{generic}").parse().unwrap()];
+    /// reduced.extend(Vec::from(colored));
+    /// assert_eq!(Manas::from(reduced).to_string(), "{1}{2/W}{W}");
+    /// ```
+    #[must_use]
+    pub fn split_generic_colored(&self) -> (Self, Self) {
+        let (generic, colored) = self.manas.iter().partition(|x| matches!(x, Mana::Generic(_)));
+        (Self { manas: generic }, Self { manas: colored })
+    }
+
+    /// Returns a copy where every `{X}` is replaced by `x` generic mana, which
+    /// is merged with the rest of the generic mana.
+    ///
+    /// ```
+    /// use mana_symbols::Manas;
+    ///
+    /// let manas: Manas = "{X}{X}{1}{R}".parse().unwrap();
+    /// assert_eq!(manas.with_x(3).to_string(), "{7}{R}");
+    /// assert_eq!(manas.with_x(0).to_string(), "{1}{R}");
+    ///
+    /// let manas: Manas = "{X}{R}".parse().unwrap();
+    /// assert_eq!(manas.with_x(2).to_string(), "{2}{R}");
+    /// assert_eq!(manas.with_x(0).to_string(), "{R}");
+    /// ```
+    #[must_use]
+    pub fn with_x(&self, x: usize) -> Self {
+        self.with_variable(GenericMana::X, x)
+    }
+
+    /// Returns a copy where every `{Y}` is replaced by `y` generic mana (see
+    /// [`Manas::with_x`]).
+    #[must_use]
+    pub fn with_y(&self, y: usize) -> Self {
+        self.with_variable(GenericMana::Y, y)
+    }
+
+    /// Returns a copy where every `{Z}` is replaced by `z` generic mana (see
+    /// [`Manas::with_x`]).
+    #[must_use]
+    pub fn with_z(&self, z: usize) -> Self {
+        self.with_variable(GenericMana::Z, z)
+    }
+
+    fn with_variable(&self, variable: GenericMana, value: usize) -> Self {
+        let variable = Mana::Generic(variable);
+        let count = self.manas.iter().filter(|x| **x == variable).count();
+        if count == 0 {
+            return self.clone();
+        }
+        let amount = count.saturating_mul(value);
+
+        let first = self.manas.iter().position(|x| *x == variable).unwrap();
+        let mut manas: Vec<Mana> = self.manas.iter().filter(|x| **x != variable).copied().collect();
+
+        if let Some(Mana::Generic(GenericMana::Number(n))) =
+            manas.iter_mut().find(|x| matches!(x, Mana::Generic(GenericMana::Number(_))))
+        {
+            *n = n.saturating_add(amount);
+        } else if amount != 0 || manas.is_empty() {
+            manas.insert(first, Mana::Generic(GenericMana::Number(amount)));
+        }
+
+        Self { manas }
+    }
+
+    /// The total [mana value](https://mtg.wiki/page/Mana_value) as a
+    /// [`ManaValue`], which can represent fractional mana values (see
+    /// [`Mana::exact_mana_value`]).
+    ///
+    /// ```
+    /// use mana_symbols::{ManaValue, Manas};
+    ///
+    /// // Little Girl
+    /// let manas: Manas = "{HW}".parse().unwrap();
+    /// assert_eq!(manas.mana_value(), 0);
+    /// assert_eq!(manas.exact_mana_value(), ManaValue::from_halves(1));
+    /// ```
+    #[must_use]
+    pub fn exact_mana_value(&self) -> ManaValue {
+        self.manas.iter().map(Mana::exact_mana_value).sum()
+    }
+
+    /// The total [mana value](https://mtg.wiki/page/Mana_value) when `X`, `Y`
+    /// and `Z` have been given values, e.g. for a spell on the stack.
+    ///
+    /// ```
+    /// use mana_symbols::Manas;
+    ///
+    /// let manas: Manas = "{X}{X}{R}".parse().unwrap();
+    /// assert_eq!(manas.mana_value(), 1);
+    /// assert_eq!(manas.mana_value_with(5, 0, 0), 11);
+    /// ```
+    #[must_use]
+    pub fn mana_value_with(&self, x: usize, y: usize, z: usize) -> usize {
+        self.manas
+            .iter()
+            .map(|mana| match mana {
+                Mana::Generic(GenericMana::X) => x,
+                Mana::Generic(GenericMana::Y) => y,
+                Mana::Generic(GenericMana::Z) => z,
+                _ => mana.mana_value(),
+            })
+            .fold(0, usize::saturating_add)
     }
 
     /// Normalize left/right side of hybrid mana symbols (see
@@ -79,6 +792,113 @@ impl Manas {
         }
     }
 
+    /// Check that the mana symbols are structurally valid (see
+    /// [`Mana::validate`]), and that generic mana is written as a single
+    /// symbol.
+    ///
+    /// ```
+    /// use mana_symbols::{Manas, Violation};
+    ///
+    /// let valid: Manas = "{3}{W/U}".parse().unwrap();
+    /// assert_eq!(valid.validate(), Ok(()));
+    ///
+    /// let invalid: Manas = "{2}{U/W}{1}".parse().unwrap();
+    /// let errors = invalid.validate().unwrap_err();
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(errors[0].0, 1);
+    /// assert_eq!(errors[1], (2, Violation::SplitGeneric));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns every violation, together with the index of the mana symbol it
+    /// concerns.
+    pub fn validate(&self) -> Result<(), Vec<(usize, Violation)>> {
+        let mut violations = Vec::new();
+        let mut seen_number = false;
+        for (i, mana) in self.manas.iter().enumerate() {
+            if let Err(violation) = mana.validate() {
+                violations.push((i, violation));
+            }
+
+            if let Mana::Generic(GenericMana::Number(n)) = mana {
+                if *n == 0 && self.manas.len() > 1 {
+                    violations.push((i, Violation::RedundantZero));
+                } else if seen_number {
+                    violations.push((i, Violation::SplitGeneric));
+                }
+                seen_number = true;
+            }
+        }
+
+        if violations.is_empty() { Ok(()) } else { Err(violations) }
+    }
+
+    /// Check that every mana symbol has been printed on a card (see
+    /// [`Mana::is_printed`]).
+    ///
+    /// ```
+    /// use mana_symbols::Manas;
+    ///
+    /// let printed: Manas = "{2}{W/U}{G/W/P}".parse().unwrap();
+    /// assert_eq!(printed.validate_printed(), Ok(()));
+    ///
+    /// let fabricated: Manas = "{2}{U/W}{3/G}".parse().unwrap();
+    /// assert_eq!(fabricated.validate_printed(), Err(vec![1, 2]));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the indices of every mana symbol which has not been printed.
+    pub fn validate_printed(&self) -> Result<(), Vec<usize>> {
+        let unprinted: Vec<usize> = self
+            .manas
+            .iter()
+            .enumerate()
+            .filter(|(_, mana)| !mana.is_printed())
+            .map(|(i, _)| i)
+            .collect();
+
+        if unprinted.is_empty() { Ok(()) } else { Err(unprinted) }
+    }
+
+    /// Whether both collections contain the same mana symbols, ignoring their
+    /// order and the left/right orientation of hybrid mana symbols.
+    ///
+    /// ```
+    /// use mana_symbols::Manas;
+    ///
+    /// let a: Manas = "{U}{W}{U/W}".parse().unwrap();
+    /// let b: Manas = "{W/U}{W}{U}".parse().unwrap();
+    ///
+    /// assert_ne!(a, b);
+    /// assert!(a.semantically_eq(&b));
+    /// ```
+    #[must_use]
+    pub fn semantically_eq(&self, other: &Self) -> bool {
+        if self.manas.len() != other.manas.len() {
+            return false;
+        }
+
+        let mut remaining = other.manas.clone();
+        for mana in &mut remaining {
+            mana.normalize_hybrid();
+        }
+
+        for mana in &self.manas {
+            let mut mana = *mana;
+            mana.normalize_hybrid();
+            match remaining.iter().position(|x| *x == mana) {
+                Some(i) => {
+                    remaining.swap_remove(i);
+                }
+                None => return false,
+            }
+        }
+
+        true
+    }
+
     /// Sorts the mana symbols in groups, then sorts those groups, in the
     /// following order:
     /// 1. Generic mana
@@ -94,57 +914,103 @@ impl Manas {
     /// 6. Snow mana
     ///
     /// This algorithm was proposed by [`/u/Mean-Government1436`][reddit:user]
-    /// in [a post on `/r/custommagic`][reddit:post].
+    /// in [a post on `/r/custommagic`][reddit:post]. To use a different order,
+    /// see [`Manas::sort_with`].
     ///
     /// [reddit:user]: https://www.reddit.com/user/Mean-Government1436
     /// [reddit:post]: https://www.reddit.com/r/custommagic/comments/1nhtr3w/guide_for_formatting_mana_costs/
     pub fn sort(&mut self) {
+        self.sort_with(SortStyle::WheelCanonical);
+    }
+
+    /// Sorts the mana symbols using the given [`SortStyle`].
+    ///
+    /// ```
+    /// use mana_symbols::{Manas, SortStyle};
+    ///
+    /// let mut manas: Manas = "{G}{2}{W}".parse().unwrap();
+    ///
+    /// manas.sort_with(SortStyle::WheelCanonical);
+    /// assert_eq!(manas.to_string(), "{2}{G}{W}");
+    ///
+    /// manas.sort_with(SortStyle::FixedWUBRG);
+    /// assert_eq!(manas.to_string(), "{2}{W}{G}");
+    /// ```
+    pub fn sort_with(&mut self, style: SortStyle) {
+        if style == SortStyle::Scryfall {
+            self.manas.sort_by_key(|x| {
+                let group = match x {
+                    Mana::Generic(GenericMana::X) => 0,
+                    Mana::Generic(GenericMana::Y) => 1,
+                    Mana::Generic(GenericMana::Z) => 2,
+                    Mana::Generic(GenericMana::Infinity) => 3,
+                    Mana::Generic(GenericMana::Number(_)) => 4,
+                    Mana::Split(SplitMana::Duo { phyrexian: false, .. }) => 5,
+                    Mana::Split(SplitMana::Duo { phyrexian: true, .. }) => 6,
+                    Mana::Split(SplitMana::Colorless { .. }) => 7,
+                    Mana::Split(SplitMana::Mono { .. }) => 8,
+                    Mana::Single(SingleMana::Phyrexian(_)) => 9,
+                    Mana::Single(SingleMana::Normal(_) | SingleMana::Half(_)) => 10,
+                    Mana::Custom(_) => 11,
+                    Mana::Colorless => 12,
+                    Mana::Snow => 13,
+                };
+                let custom = if let Mana::Custom(color) = x { Some(*color) } else { None };
+                (group, x.left_half_color(), x.right_half_color(), custom)
+            });
+            return;
+        }
+
         self.manas.sort_by_key(|k| match k {
             Mana::Generic(GenericMana::X) => 0,
             Mana::Generic(GenericMana::Y) => 1,
             Mana::Generic(GenericMana::Z) => 2,
-            Mana::Generic(GenericMana::Number(_)) => 3,
-            Mana::Split(SplitMana::Mono { .. }) => 4,
-            Mana::Colorless => 5,
-            Mana::Split(SplitMana::Colorless { .. }) => 6,
-            Mana::Single(_) | Mana::Split(SplitMana::Duo { .. }) => 7,
-            Mana::Snow => 8,
+            Mana::Generic(GenericMana::Infinity) => 3,
+            Mana::Generic(GenericMana::Number(_)) => 4,
+            Mana::Split(SplitMana::Mono { .. }) => 5,
+            Mana::Colorless => 6,
+            Mana::Split(SplitMana::Colorless { .. }) => 7,
+            Mana::Single(_) | Mana::Split(SplitMana::Duo { .. }) => 8,
+            Mana::Custom(_) => 9,
+            Mana::Snow => 10,
         });
 
         let rest = skip(&mut self.manas, |x| matches!(x, Mana::Generic(_)));
 
         let (generic_hybrid, rest) =
             take_while(rest, |x| matches!(x, Mana::Split(SplitMana::Mono { .. })));
-        sort_by_colors(generic_hybrid, |x| x.right_half_color().unwrap());
+        sort_by_colors(style, generic_hybrid, |x| x.right_half_color().unwrap());
 
         let rest = skip(rest, |x| matches!(x, Mana::Colorless));
 
         let (colorless_hybrid, rest) =
             take_while(rest, |x| matches!(x, Mana::Split(SplitMana::Colorless { .. })));
 
-        sort_by_colors(colorless_hybrid, |x| x.right_half_color().unwrap());
+        sort_by_colors(style, colorless_hybrid, |x| x.right_half_color().unwrap());
 
-        let (colored, snow) =
+        let (colored, rest) =
             take_while(rest, |x| matches!(x, Mana::Single(_) | Mana::Split(SplitMana::Duo { .. })));
 
-        sort_by_colors(colored, |x| x.left_half_color().unwrap());
+        sort_by_colors(style, colored, |x| x.left_half_color().unwrap());
 
         // Go through each run of equal colors
         for chunk in colored.chunk_by_mut(|a, b| a.left_half_color() == b.left_half_color()) {
             chunk.sort_by_key(|x| match x {
                 Mana::Single(SingleMana::Normal(_)) => 0,
-                Mana::Single(SingleMana::Phyrexian(_)) => 1,
+                Mana::Single(SingleMana::Half(_)) => 1,
+                Mana::Single(SingleMana::Phyrexian(_)) => 2,
                 Mana::Split(SplitMana::Duo { phyrexian, .. }) => {
                     if *phyrexian {
-                        3
+                        4
                     } else {
-                        2
+                        3
                     }
                 }
                 Mana::Split(SplitMana::Colorless { .. } | SplitMana::Mono { .. })
                 | Mana::Colorless
                 | Mana::Generic(_)
-                | Mana::Snow => unreachable!(),
+                | Mana::Snow
+                | Mana::Custom(_) => unreachable!(),
             });
 
             // Discard non-hybrid mana
@@ -158,48 +1024,636 @@ impl Manas {
                 }
             });
 
-            sort_by_colors(hybrid_non_phyrexian, |x| x.right_half_color().unwrap());
-            sort_by_colors(hybrid_phyrexian, |x| x.right_half_color().unwrap());
+            sort_by_colors(style, hybrid_non_phyrexian, |x| x.right_half_color().unwrap());
+            sort_by_colors(style, hybrid_phyrexian, |x| x.right_half_color().unwrap());
         }
 
+        let (custom, snow) = take_while(rest, |x| matches!(x, Mana::Custom(_)));
+        custom.sort();
+
         for mana in snow {
             debug_assert_eq!(*mana, Mana::Snow);
         }
     }
 
-    /// Parse `Manas` using [`nom`]. If you just want to parse normally, use
-    /// [`Manas::from_str`].
-    pub fn parse(input: &str) -> IResult<&str, Self> {
-        let (rest, res) = many0(Mana::parse).parse(input)?;
-        Ok((rest, Self { manas: res }))
-    }
-
-    /// Display the mana symbols as an [SVG](https://en.wikipedia.org/wiki/SVG). See [`Mana::as_svg`].
+    /// Returns a sorted copy of the mana symbols (see [`Manas::sort`]).
+    ///
+    /// ```
+    /// use mana_symbols::Manas;
+    ///
+    /// let manas: Manas = "{U}{2}".parse().unwrap();
+    /// assert_eq!(manas.sorted().to_string(), "{2}{U}");
+    /// assert_eq!(manas.to_string(), "{U}{2}");
+    /// ```
+    #[must_use]
+    pub fn sorted(&self) -> Self {
+        let mut manas = self.clone();
+        manas.sort();
+        manas
+    }
+
+    /// Whether the mana symbols are already sorted, i.e. [`Manas::sort`] would
+    /// not change them.
+    ///
+    /// ```
+    /// use mana_symbols::Manas;
+    ///
+    /// let sorted: Manas = "{2}{U}".parse().unwrap();
+    /// let unsorted: Manas = "{U}{2}".parse().unwrap();
+    /// assert!(sorted.is_sorted());
+    /// assert!(!unsorted.is_sorted());
+    /// ```
+    #[must_use]
+    pub fn is_sorted(&self) -> bool {
+        self.sorted() == *self
+    }
+
+    /// Split the mana symbols into the groups used by [`Manas::sort`].
+    ///
+    /// Returns `None` if the groups are not in order, which will not happen
+    /// after sorting with [`SortStyle::WheelCanonical`] or
+    /// [`SortStyle::FixedWUBRG`].
+    ///
+    /// ```
+    /// use mana_symbols::Manas;
+    ///
+    /// let manas: Manas = "{X}{2}{C}{U}{U/B}{S}".parse().unwrap();
+    /// let groups = manas.groups().unwrap();
+    ///
+    /// assert_eq!(groups.generic.len(), 2);
+    /// assert_eq!(groups.generic_hybrid.len(), 0);
+    /// assert_eq!(groups.colored.len(), 2);
+    ///
+    /// let unsorted: Manas = "{U}{2}".parse().unwrap();
+    /// assert!(unsorted.groups().is_none());
+    /// ```
+    #[must_use]
+    pub fn groups(&self) -> Option<ManaGroups<'_>> {
+        let group = |x: &Mana| match x {
+            Mana::Generic(_) => 0,
+            Mana::Split(SplitMana::Mono { .. }) => 1,
+            Mana::Colorless => 2,
+            Mana::Split(SplitMana::Colorless { .. }) => 3,
+            Mana::Single(_) | Mana::Split(SplitMana::Duo { .. }) | Mana::Custom(_) => 4,
+            Mana::Snow => 5,
+        };
+
+        if !self.manas.is_sorted_by_key(group) {
+            return None;
+        }
+
+        let mut rest = self.manas.as_slice();
+        let mut groups = [[].as_slice(); 6];
+        for (i, slot) in groups.iter_mut().enumerate() {
+            let (current, next) = rest.split_at(rest.partition_point(|x| group(x) == i));
+            *slot = current;
+            rest = next;
+        }
+        debug_assert!(rest.is_empty());
+
+        let [generic, generic_hybrid, colorless, colorless_hybrid, colored, snow] = groups;
+        Some(ManaGroups { generic, generic_hybrid, colorless, colorless_hybrid, colored, snow })
+    }
+
+    /// Display the mana symbols as text, using the given [`DisplayOptions`].
+    ///
+    /// ```
+    /// use mana_symbols::{BraceStyle, DisplayOptions, Manas, PhyrexianStyle};
+    ///
+    /// let manas: Manas = "{1}{U}{U}".parse().unwrap();
+    ///
+    /// let options = DisplayOptions { separator: " ".to_string(), ..Default::default() };
+    /// assert_eq!(manas.display_with(&options).to_string(), "{1} {U} {U}");
+    ///
+    /// let options = DisplayOptions {
+    ///     separator: ", ".to_string(),
+    ///     braces: BraceStyle::None,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(manas.display_with(&options).to_string(), "1, U, U");
+    ///
+    /// let manas: Manas = "{1}{U/P}".parse().unwrap();
+    /// let options = DisplayOptions { phyrexian: PhyrexianStyle::Phi, ..Default::default() };
+    /// assert_eq!(manas.display_with(&options).to_string(), "{1}{UΦ}");
+    /// ```
+    #[must_use]
+    pub fn display_with<'a>(&'a self, options: &'a DisplayOptions) -> ManasDisplay<'a> {
+        ManasDisplay { manas: &self.manas, options }
+    }
+
+    /// Show the mana symbols as custom [Discord](https://discord.com) emojis,
+    /// e.g. `<:mana2:1234><:manaU:5678>`. Symbols without an emoji in `emojis`
+    /// are written as text, e.g. `{U}`.
+    ///
+    /// ```
+    /// use mana_symbols::{EmojiMap, Manas};
+    ///
+    /// let mut emojis = EmojiMap::new();
+    /// emojis.insert("U".parse().unwrap(), 1234);
+    ///
+    /// let manas: Manas = "{1}{U}{U}".parse().unwrap();
+    /// assert_eq!(manas.to_discord(&emojis), "{1}<:manaU:1234><:manaU:1234>");
+    /// ```
+    #[must_use]
+    pub fn to_discord(&self, emojis: &EmojiMap) -> String {
+        let mut out = String::new();
+        for mana in &self.manas {
+            emojis.write(&mut out, mana).unwrap();
+        }
+        out
+    }
+
+    /// Show the mana symbols as custom [Slack](https://slack.com) emojis, e.g.
+    /// `:mana-2::mana-u:`, with the names given by `emojis`.
+    ///
+    /// ```
+    /// use mana_symbols::{Manas, SlackEmojiMap};
+    ///
+    /// let manas: Manas = "{1}{U}{U}".parse().unwrap();
+    /// assert_eq!(manas.to_slack(&SlackEmojiMap::new()), ":mana-1::mana-u::mana-u:");
+    /// ```
+    #[must_use]
+    pub fn to_slack(&self, emojis: &SlackEmojiMap) -> String {
+        let mut out = String::new();
+        for mana in &self.manas {
+            emojis.write(&mut out, mana).unwrap();
+        }
+        out
+    }
+
+    /// Show the mana symbols as LaTeX, where each symbol is drawn by a
+    /// self-contained TikZ picture using the default colors. The document
+    /// needs to load the `tikz` package.
+    ///
+    /// ```
+    /// use mana_symbols::Manas;
+    ///
+    /// let manas: Manas = "{1}{U}".parse().unwrap();
+    /// let latex = manas.to_latex();
+    /// assert_eq!(latex.matches(r"\tikz").count(), 2);
+    /// ```
+    #[must_use]
+    pub fn to_latex(&self) -> String {
+        let mut out = String::new();
+        for mana in &self.manas {
+            write_latex(&mut out, mana).unwrap();
+        }
+        out
+    }
+
+    /// Show the mana symbols as [Typst](https://typst.app) markup, where each
+    /// symbol is an inline SVG image (see [`Mana::as_svg`]) as high as the
+    /// surrounding text. Requires Typst 0.13 or later.
+    ///
+    /// ```
+    /// use mana_symbols::{Manas, SVGConfig};
+    ///
+    /// let manas: Manas = "{1}{U}".parse().unwrap();
+    /// let typst = manas.to_typst(&SVGConfig::default());
+    /// assert!(typst.starts_with(r#"#box(baseline: 15%, image(bytes("<svg "#));
+    /// assert_eq!(typst.matches("#box").count(), 2);
+    /// ```
+    #[cfg(feature = "svg")]
+    #[must_use]
+    pub fn to_typst(&self, config: &SVGConfig) -> String {
+        let mut out = String::new();
+        for mana in &self.manas {
+            write_typst(&mut out, mana, config).unwrap();
+        }
+        out
+    }
+
+    /// Approximate the mana symbols with Unicode characters, e.g. `②Ⓤ` for
+    /// `{2}{U}`, as a compact fallback for plain text. See
+    /// [`Mana::to_unicode`].
+    ///
+    /// ```
+    /// use mana_symbols::Manas;
+    ///
+    /// let manas: Manas = "{2}{W/U}{G/P}{S}".parse().unwrap();
+    /// assert_eq!(manas.to_unicode(), "②Ⓦ/ⓊⒼ/Φ❄");
+    /// ```
+    #[must_use]
+    pub fn to_unicode(&self) -> String {
+        let mut out = String::new();
+        for mana in &self.manas {
+            write_unicode(&mut out, mana).unwrap();
+        }
+        out
+    }
+
+    /// Show the mana symbols as [HTML](https://en.wikipedia.org/wiki/HTML)
+    /// without any images, where each symbol is a colored `<span>`. See
+    /// [`Mana::to_html_span`].
+    ///
+    /// ```
+    /// use mana_symbols::Manas;
+    ///
+    /// let manas: Manas = "{1}{U}".parse().unwrap();
+    /// let html = manas.to_html_spans();
+    /// assert!(html.starts_with(r#"<span class="mana_symbols"><span title="1 generic mana""#));
+    /// assert_eq!(html.matches("<span").count(), 3);
+    /// ```
+    #[must_use]
+    pub fn to_html_spans(&self) -> String {
+        let mut out = String::from(r#"<span class="mana_symbols">"#);
+        for mana in &self.manas {
+            write_html_span(&mut out, mana).unwrap();
+        }
+        out.push_str("</span>");
+        out
+    }
+
+    /// Parse `Manas` using [`nom`]. If you just want to parse normally, use
+    /// [`Manas::from_str`].
+    pub fn parse(input: &str) -> IResult<&str, Self> {
+        let (rest, res) = many0(Mana::parse).parse(input)?;
+        Ok((rest, Self { manas: res }))
+    }
+
+    /// Parse `Manas` using [`nom`], where the custom colors of `registry` are
+    /// also recognized. If you just want to parse normally, use
+    /// [`Manas::from_str_with`].
+    pub fn parse_with<'a>(input: &'a str, registry: &ColorRegistry) -> IResult<&'a str, Self> {
+        let (rest, res) = many0(|input| Mana::parse_with(input, registry)).parse(input)?;
+        Ok((rest, Self { manas: res }))
+    }
+
+    /// Parse `Manas` written in the legacy encoding of old Oracle text dumps,
+    /// see [`Mana::parse_legacy`]. If you just want to parse normally, use
+    /// [`Manas::from_legacy_str`].
+    pub fn parse_legacy(input: &str) -> IResult<&str, Self> {
+        let (rest, res) = many0(Mana::parse_legacy).parse(input)?;
+        Ok((rest, Self { manas: res }))
+    }
+
+    /// Parse `Manas` written in the legacy encoding of old Oracle text dumps,
+    /// see [`Mana::parse_legacy`]. Returns `None` if `s` is not a valid
+    /// sequence of legacy mana symbols.
+    ///
+    /// ```
+    /// use mana_symbols::Manas;
+    ///
+    /// let manas = Manas::from_legacy_str("o3oWoW").unwrap();
+    /// assert_eq!(manas.to_string(), "{3}{W}{W}");
+    ///
+    /// assert!(Manas::from_legacy_str("{3}{W}{W}").is_none());
+    /// ```
+    #[must_use]
+    pub fn from_legacy_str(s: &str) -> Option<Self> {
+        let p = terminated(Self::parse_legacy, eof).parse(s).finish();
+
+        match p {
+            Ok((_, mana)) => Some(mana),
+            Err(_) => None,
+        }
+    }
+
+    /// Parse `Manas` like [`Manas::from_str`], where the custom colors of
+    /// `registry` are also recognized. Returns `None` if `s` is not a valid
+    /// sequence of mana symbols.
+    ///
+    /// ```
+    /// use mana_symbols::{ColorRegistry, Manas};
+    ///
+    /// let mut registry = ColorRegistry::new();
+    /// registry.register('O', "purple", "#c9a0dc").unwrap();
+    ///
+    /// let mut manas = Manas::from_str_with("{O}{U}{2}", &registry).unwrap();
+    /// manas.sort();
+    /// assert_eq!(manas.to_string(), "{2}{U}{O}");
+    /// assert_eq!(manas.mana_value(), 4);
+    ///
+    /// assert!("{O}".parse::<Manas>().is_err());
+    /// ```
+    #[must_use]
+    pub fn from_str_with(s: &str, registry: &ColorRegistry) -> Option<Self> {
+        let p = terminated(|input| Self::parse_with(input, registry), eof).parse(s).finish();
+
+        match p {
+            Ok((_, mana)) => Some(mana),
+            Err(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "svg")]
+impl Manas {
+    /// Display the mana symbols as a single horizontal [SVG](https://en.wikipedia.org/wiki/SVG),
+    /// where the symbols are separated by [`SVGConfig::spacing`]. See [`Mana::as_svg`].
     #[must_use]
     pub fn as_svg(&self, config: &SVGConfig) -> SVG {
-        let n = self.manas.len();
-        if n == 0 {
+        self.as_svg_with_theme(config, &config.palette)
+    }
+
+    /// Display the mana symbols as a single horizontal [SVG](https://en.wikipedia.org/wiki/SVG),
+    /// where the circles and glyphs are drawn using `theme`. See [`Mana::as_svg_with_theme`].
+    #[must_use]
+    pub fn as_svg_with_theme<T: Theme + ?Sized>(&self, config: &SVGConfig, theme: &T) -> SVG {
+        self.rows_as_svg(&[&self.manas], config, theme)
+    }
+
+    /// Display the mana symbols as an [SVG](https://en.wikipedia.org/wiki/SVG)
+    /// with several rows, where each row is at most `max_width` wide (where
+    /// the width of a mana symbol is 32, including [`SVGConfig::padding`]).
+    /// Both the symbols within a row and the rows themselves are separated by
+    /// [`SVGConfig::spacing`], and every row holds at least one symbol. See
+    /// [`Manas::as_svg`].
+    ///
+    /// ```
+    /// use mana_symbols::{Manas, SVGConfig};
+    ///
+    /// let manas: Manas = "{W}{W}{U}{U}{B}{B}{R}{R}{G}{G}".parse().unwrap();
+    /// let config = SVGConfig { padding: Some(0.0), spacing: 4.0, ..Default::default() };
+    ///
+    /// // Four symbols fit in each row
+    /// let svg = manas.as_svg_wrapped(140.0, &config).to_string();
+    /// assert!(svg.contains(r#"viewBox="0 0 140 104""#));
+    /// ```
+    #[must_use]
+    pub fn as_svg_wrapped(&self, max_width: f64, config: &SVGConfig) -> SVG {
+        let margin = config.margin();
+        let width_single = 2.0f64.mul_add(margin, SVG_WIDTH);
+        let half = width_single / 2.0;
+
+        let mut rows = Vec::new();
+        let mut row_start = 0;
+        let mut row_width = -config.spacing;
+        for (i, mana) in self.manas.iter().enumerate() {
+            let width = if mana.is_half() { half } else { width_single };
+            let wider = row_width + config.spacing + width;
+            if i > row_start && wider > max_width {
+                rows.push(&self.manas[row_start..i]);
+                row_start = i;
+                row_width = width;
+            } else {
+                row_width = wider;
+            }
+        }
+        if row_start < self.manas.len() {
+            rows.push(&self.manas[row_start..]);
+        }
+
+        self.rows_as_svg(&rows, config, &config.palette)
+    }
+
+    /// Draw each of `rows` like [`Manas::as_svg_with_theme`], one below the
+    /// other. The SVG describes all of `self`.
+    fn rows_as_svg<T: Theme + ?Sized>(
+        &self,
+        rows: &[&[Mana]],
+        config: &SVGConfig,
+        theme: &T,
+    ) -> SVG {
+        if self.manas.is_empty() {
             return Document::new();
         }
 
-        let shadow_offset = 1.5;
-        let width_single = 2.0f64.mul_add(shadow_offset, SVG_WIDTH);
-        let width_total = width_single * (n as f64);
+        let margin = config.margin();
+        let width_single = 2.0f64.mul_add(margin, SVG_WIDTH);
+        let step = width_single + config.spacing;
+        // Half mana symbols are half as wide
+        let half = width_single / 2.0;
+        let row_width = |row: &[Mana]| {
+            let halves = row.iter().filter(|mana| mana.is_half()).count();
+            step.mul_add(row.len() as f64, -config.spacing) - half * halves as f64
+        };
+        let width_total = rows.iter().map(|row| row_width(row)).fold(0.0, f64::max);
+        let height_total = step.mul_add((rows.len() - 1) as f64, width_single);
+        // Subtracting avoids writing `-0` when there is no margin
+        let origin = 0.0 - margin;
 
-        let mut document = Document::new()
-            .set("viewBox", (-shadow_offset, -shadow_offset, width_total, width_single));
+        let mut document =
+            Document::new().set("viewBox", (origin, origin, width_total, height_total));
 
-        for (i, mana) in self.manas.iter().enumerate() {
-            let mana_svg = mana
-                .as_svg(config)
-                .set("x", width_single * (i as f64) - shadow_offset)
-                .set("y", -shadow_offset)
-                .set("width", width_single)
-                .set("height", width_single);
-            document = document.add(mana_svg);
+        let names: Vec<String> =
+            self.manas.iter().map(|mana| mana.name_with(&config.palette)).collect();
+        document = with_description(document, &names.join(", "), &self.to_string());
+        if let Some((width, height)) = config.size(width_total / height_total) {
+            document = document.set("width", width).set("height", height);
+        }
+        if let Some(shape_rendering) = config.shape_rendering {
+            document = document.set("shape-rendering", shape_rendering.to_string());
+        }
+        if let Some(plate) = theme.plate() {
+            for (r, row) in rows.iter().enumerate() {
+                let y = step.mul_add(r as f64, origin);
+                document = with_plate(document, &plate, origin, y, row_width(row), width_single);
+            }
+        }
+
+        // Each mana symbol sets its own size, so we do not pass on the size.
+        // The whole SVG is minified at the end.
+        let inner_config = &SVGConfig {
+            width: None,
+            height: None,
+            precision: None,
+            minify: false,
+            ..config.clone()
+        };
+        for (r, row) in rows.iter().enumerate() {
+            let y = step.mul_add(r as f64, origin);
+            let mut halves_before = 0.0;
+            for (i, mana) in row.iter().enumerate() {
+                let width = if mana.is_half() { half } else { width_single };
+                let mut mana_svg = mana
+                    .as_svg_with_theme(inner_config, theme)
+                    .set("x", step * (i as f64) - margin - half * halves_before)
+                    .set("y", y)
+                    .set("width", width)
+                    .set("height", width_single)
+                    .set("aria-hidden", "true");
+                if mana.is_half() && config.compatible {
+                    // Keeps the right half of the circle from being drawn
+                    mana_svg = mana_svg.set("overflow", "hidden");
+                }
+                document = document.add(mana_svg);
+                if mana.is_half() {
+                    halves_before += 1.0;
+                }
+            }
+        }
+
+        make_compatible(minify(document, config), config)
+    }
+
+    /// Display the mana symbols as a gzip-compressed SVG (see
+    /// [`Manas::as_svg`]), as used by `.svgz` files. See [`Mana::as_svgz`].
+    #[cfg(feature = "svgz")]
+    #[must_use]
+    pub fn as_svgz(&self, config: &SVGConfig) -> Vec<u8> {
+        crate::svgz::svg_to_svgz(&self.as_svg(config))
+    }
+
+    /// Display the mana symbols as a [PDF](https://en.wikipedia.org/wiki/PDF)
+    /// with a single page, which is exactly `height_mm` millimeters high and as
+    /// wide as the symbols (see [`Manas::as_svg`]). The symbols are drawn as
+    /// vector graphics, so they can be printed at any resolution, except for
+    /// drop shadows and foils, which are embedded as images. Returns
+    /// `None` if there are no mana symbols or `height_mm` is not positive.
+    ///
+    /// ```
+    /// use mana_symbols::{Manas, SVGConfig};
+    ///
+    /// let manas: Manas = "{2}{U}{U}".parse().unwrap();
+    /// let pdf = manas.as_pdf(3.5, &SVGConfig::default()).unwrap();
+    /// assert!(pdf.starts_with(b"%PDF"));
+    /// ```
+    #[cfg(feature = "pdf")]
+    #[must_use]
+    pub fn as_pdf(&self, height_mm: f32, config: &SVGConfig) -> Option<Vec<u8>> {
+        if self.manas.is_empty() {
+            return None;
+        }
+        crate::pdf::svg_to_pdf(&self.as_svg(config), height_mm)
+    }
+
+    /// Write the mana symbols (see [`Manas::as_svg`]) to `chunk` as a PDF
+    /// form XObject with the ID `id`, so that they can be placed on a page
+    /// created using [`pdf_writer`](https://docs.rs/pdf-writer).
+    ///
+    /// The XObject fills a unit square, so it has to be scaled when placed. To
+    /// draw symbols which are `h` points high, the width is `h` times the
+    /// returned aspect ratio. Returns the next unused ID together with the
+    /// aspect ratio (width divided by height), or `None` if there are no mana
+    /// symbols.
+    ///
+    /// ```
+    /// use mana_symbols::{Manas, SVGConfig};
+    /// use pdf_writer::{Chunk, Content, Name, Ref};
+    ///
+    /// let manas: Manas = "{2}{U}{U}".parse().unwrap();
+    /// let mut chunk = Chunk::new();
+    /// let (_next, aspect) =
+    ///     manas.write_pdf_xobject(&mut chunk, Ref::new(10), &SVGConfig::default()).unwrap();
+    ///
+    /// // A cost which is 10 points high, placed at (72, 72)
+    /// let mut content = Content::new();
+    /// content.transform([10.0 * aspect, 0.0, 0.0, 10.0, 72.0, 72.0]);
+    /// content.x_object(Name(b"Cost"));
+    /// ```
+    #[cfg(feature = "pdf")]
+    pub fn write_pdf_xobject(
+        &self,
+        chunk: &mut pdf_writer::Chunk,
+        id: pdf_writer::Ref,
+        config: &SVGConfig,
+    ) -> Option<(pdf_writer::Ref, f32)> {
+        if self.manas.is_empty() {
+            return None;
+        }
+        crate::pdf::svg_to_pdf_xobject(&self.as_svg(config), chunk, id)
+    }
+
+    /// Tessellate the mana symbols (see [`Manas::as_svg`]), so that they can be
+    /// drawn by [egui](https://www.egui.rs). Returns `None` if there are no
+    /// mana symbols. See [`SymbolMesh`](crate::SymbolMesh).
+    #[cfg(feature = "egui")]
+    #[must_use]
+    pub fn as_egui_mesh(&self, config: &SVGConfig) -> Option<crate::SymbolMesh> {
+        if self.manas.is_empty() {
+            return None;
+        }
+        crate::egui_mesh::svg_to_mesh(&self.as_svg(config))
+    }
+
+    /// Display the mana symbols as a single horizontal SVG (see
+    /// [`Manas::as_svg`]), as a handle for `iced::widget::svg`. To show the
+    /// symbols one by one, use [`SvgHandles`](crate::SvgHandles).
+    #[cfg(feature = "iced")]
+    #[must_use]
+    pub fn as_iced_handle(&self, config: &SVGConfig) -> iced_core::svg::Handle {
+        crate::iced_handles::svg_to_handle(&self.as_svg(config))
+    }
+
+    /// Display the mana symbols as a single horizontal
+    /// [PNG](https://en.wikipedia.org/wiki/PNG) image which is `height` pixels
+    /// high, encoded as bytes (see [`Manas::as_svg`]). Returns `None` if there
+    /// are no mana symbols or `height` is zero.
+    #[cfg(feature = "png")]
+    #[must_use]
+    pub fn as_png(&self, height: u32, config: &SVGConfig) -> Option<Vec<u8>> {
+        if self.manas.is_empty() {
+            return None;
+        }
+        crate::raster::svg_to_png(&self.as_svg(config), height)
+    }
+
+    /// Display the mana symbols as a single horizontal PNG image for printing,
+    /// which is `height_mm` millimeters high at `dpi` dots per inch, with
+    /// `spacing_mm` millimeters between the symbols. This replaces
+    /// [`SVGConfig::spacing`]. The resolution is stored in the image, so it is
+    /// printed at the right size.
+    ///
+    /// The height includes the margin left for the drop shadow, so with the
+    /// default [`SVGConfig`] each circle is only 32/35 of `height_mm` wide.
+    ///
+    /// Returns `None` if there are no mana symbols or the image would be
+    /// empty.
+    ///
+    /// ```
+    /// use mana_symbols::{Manas, SVGConfig};
+    ///
+    /// let manas: Manas = "{2}{U}{U}".parse().unwrap();
+    /// // A strip 3.5 mm high, i.e. circles 3.2 mm wide, printed at 600 DPI
+    /// let png = manas.render_strip_png(600, 0.2, 3.5, &SVGConfig::default()).unwrap();
+    /// assert!(png.starts_with(b"\x89PNG"));
+    /// ```
+    #[cfg(feature = "png")]
+    #[must_use]
+    pub fn render_strip_png(
+        &self,
+        dpi: u32,
+        spacing_mm: f64,
+        height_mm: f64,
+        config: &SVGConfig,
+    ) -> Option<Vec<u8>> {
+        if self.manas.is_empty() || height_mm.is_nan() || height_mm <= 0.0 {
+            return None;
+        }
+        let width_single = 2.0f64.mul_add(config.margin(), SVG_WIDTH);
+        let config =
+            &SVGConfig { spacing: spacing_mm / height_mm * width_single, ..config.clone() };
+        let height = (height_mm / 25.4 * f64::from(dpi)).round() as u32;
+        let mut png = crate::raster::svg_to_png(&self.as_svg(config), height)?;
+        crate::raster::set_png_dpi(&mut png, dpi);
+        Some(png)
+    }
+
+    /// Display the mana symbols as a single horizontal image which is `height`
+    /// pixels high (see [`Manas::as_svg`]). Returns `None` if there are no
+    /// mana symbols or `height` is zero.
+    #[cfg(feature = "image")]
+    #[must_use]
+    pub fn as_rgba_image(&self, height: u32, config: &SVGConfig) -> Option<image::RgbaImage> {
+        if self.manas.is_empty() {
+            return None;
+        }
+        crate::raster::svg_to_image(&self.as_svg(config), height)
+    }
+
+    /// Write the same SVG as [`Manas::as_svg`] to `output`, without building a
+    /// tree of nodes first. See [`Mana::render_svg_to`].
+    pub fn render_svg_to(&self, output: &mut String, config: &SVGConfig) {
+        if config.minify || config.precision.is_some() || config.compatible {
+            // Minifying and inlining glyphs needs the whole tree
+            write_canonical_svg(output, &self.as_svg(config)).unwrap();
+        } else {
+            crate::render::render_manas(output, self, config);
         }
+    }
+
+    /// Write the mana symbols as a single SVG (see [`Manas::as_svg`]) to
+    /// `output`. See [`Mana::write_svg`].
+    pub fn write_svg<W: io::Write>(&self, output: &mut W, config: &SVGConfig) -> io::Result<()> {
+        write_io(output, |output| write_canonical_svg(output, &self.as_svg(config)))
+    }
 
-        document
+    /// The SVG of the mana symbols (see [`Manas::as_svg`]) as a base64 data
+    /// URI. See [`Mana::as_svg_data_uri`].
+    #[must_use]
+    pub fn as_svg_data_uri(&self, config: &SVGConfig) -> String {
+        svg_data_uri(&self.as_svg(config))
     }
 
     /// Display the mana symbols as a [`String`] of [HTML](https://en.wikipedia.org/wiki/HTML), where
@@ -218,15 +1672,88 @@ impl Manas {
         output: &mut W,
         include_css: bool,
         config: &SVGConfig,
+    ) -> std::fmt::Result {
+        let options = HtmlOptions { include_css, ..Default::default() };
+        self.write_html_with(output, &options, config)
+    }
+
+    /// Display the mana symbols as a [`String`] of [HTML](https://en.wikipedia.org/wiki/HTML),
+    /// using the given [`HtmlOptions`]. See [`Mana::as_html_with`].
+    #[must_use]
+    pub fn as_html_with(&self, options: &HtmlOptions, config: &SVGConfig) -> String {
+        let mut out = String::new();
+        self.write_html_with(&mut out, options, config).unwrap();
+        out
+    }
+
+    /// Display the mana symbols as [HTML](https://en.wikipedia.org/wiki/HTML) written to an
+    /// [`io::Write`]. See [`Mana::write_html_io`].
+    pub fn write_html_io<W: io::Write>(
+        &self,
+        output: &mut W,
+        options: &HtmlOptions,
+        config: &SVGConfig,
+    ) -> io::Result<()> {
+        write_io(output, |output| self.write_html_with(output, options, config))
+    }
+
+    /// Display the mana symbols as [HTML](https://en.wikipedia.org/wiki/HTML) written to `output`,
+    /// using the given [`HtmlOptions`]. See [`Mana::write_html_with`].
+    pub fn write_html_with<W: Write>(
+        &self,
+        output: &mut W,
+        options: &HtmlOptions,
+        config: &SVGConfig,
     ) -> std::fmt::Result {
         write!(output, r#"<span class="mana_symbols">"#)?;
 
         for mana in &self.manas {
-            mana.write_html(output, include_css, config)?;
+            mana.write_html_with(output, options, config)?;
         }
 
         write!(output, "</span>")
     }
+
+    /// Display the mana symbols as a [`String`] of [HTML](https://en.wikipedia.org/wiki/HTML),
+    /// where each image refers to an SVG file written by `assets`. See
+    /// [`Mana::write_html_with_assets`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an SVG file could not be written.
+    pub fn as_html_with_assets(
+        &self,
+        options: &HtmlOptions,
+        assets: &mut AssetWriter,
+    ) -> io::Result<String> {
+        let mut out = String::new();
+        self.write_html_with_assets(&mut out, options, assets)?;
+        Ok(out)
+    }
+
+    /// Display the mana symbols as [HTML](https://en.wikipedia.org/wiki/HTML) written to `output`,
+    /// where each image refers to an SVG file written by `assets`. See
+    /// [`Mana::write_html_with_assets`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an SVG file could not be written, or if writing to
+    /// `output` failed.
+    pub fn write_html_with_assets<W: Write>(
+        &self,
+        output: &mut W,
+        options: &HtmlOptions,
+        assets: &mut AssetWriter,
+    ) -> io::Result<()> {
+        let error = |std::fmt::Error| io::Error::other("formatter error");
+        write!(output, r#"<span class="mana_symbols">"#).map_err(error)?;
+
+        for mana in &self.manas {
+            mana.write_html_with_assets(output, options, assets)?;
+        }
+
+        write!(output, "</span>").map_err(error)
+    }
 }
 
 impl From<Manas> for Vec<Mana> {
@@ -241,10 +1768,31 @@ impl From<Vec<Mana>> for Manas {
     }
 }
 
-fn sort_by_colors<T, F: Fn(&T) -> Color>(a: &mut [T], pred: F) {
+/// A rounded rectangle behind a row of symbols, with its corners rounded like
+/// the circles at either end.
+#[cfg(feature = "svg")]
+#[must_use]
+fn with_plate(document: SVG, plate: &Plate, x: f64, y: f64, width: f64, height: f64) -> SVG {
+    let rectangle = Rectangle::new()
+        .set("fill", plate.fill.as_str())
+        .set("stroke", "none")
+        .set("x", x)
+        .set("y", y)
+        .set("width", width)
+        .set("height", height)
+        .set("rx", height / 2.0);
+    document.add(rectangle)
+}
+
+fn sort_by_colors<T, F: Fn(&T) -> Color>(style: SortStyle, a: &mut [T], pred: F) {
+    if style == SortStyle::FixedWUBRG {
+        a.sort_by_key(|x| pred(x) as u8);
+        return;
+    }
+
     let mut color_set = ColorSet::new();
     for v in &*a {
-        color_set.set_color(pred(v));
+        color_set.insert(pred(v));
     }
     let order = color_set.order_values();
 
@@ -273,6 +1821,7 @@ fn skip<T, F: Fn(&T) -> bool>(a: &mut [T], pred: F) -> &mut [T] {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{ManaUnit, PhyrexianStyle};
 
     #[test]
     fn parse_empty() {
@@ -309,6 +1858,478 @@ mod tests {
         assert_eq!(manas_before.to_string(), after);
     }
 
+    #[test]
+    fn not_mana() {
+        // Symbols which are not mana are `Symbol`s, not part of mana costs
+        for cost in ["{T}", "{2}{Q}", "{E}{E}", "{TK}"] {
+            assert_eq!(Manas::from_str(cost), Err(()));
+        }
+    }
+
+    #[test]
+    fn infinity() {
+        // Mox Lotus
+        let mut manas = Manas::from_str("{7}{7}{∞}").unwrap();
+        assert_eq!(manas.mana_value(), usize::MAX);
+        assert_eq!(manas.mana_value_with(1, 0, 0), usize::MAX);
+        assert_eq!(manas.exact_mana_value().to_string(), "∞");
+        manas.sort();
+        assert_eq!(manas.to_string(), "{∞}{7}{7}");
+        assert_eq!(manas.groups().unwrap().generic.len(), 3);
+
+        let deck = [Manas::from_str("{∞}").unwrap(), Manas::from_str("{1}").unwrap()];
+        assert_eq!(deck.iter().sum::<usize>(), usize::MAX);
+        assert_eq!(deck.into_iter().sum::<usize>(), usize::MAX);
+    }
+
+    #[test]
+    fn huge_generic() {
+        let manas = Manas::from_str("{9223372036854775808}").unwrap();
+        assert_eq!(manas.exact_mana_value(), ManaValue::INFINITY);
+        assert_eq!(ManaValue::from(usize::MAX), ManaValue::INFINITY);
+
+        let manas = Manas::from_str("{18446744073709551615}{1}").unwrap();
+        assert_eq!(manas.generic_amount(), usize::MAX);
+        assert_eq!(manas.mana_value(), usize::MAX);
+    }
+
+    #[test]
+    fn phi() {
+        let manas = Manas::from_str("{2}{WΦ}{G/WΦ}").unwrap();
+        assert_eq!(manas.to_string(), "{2}{W/P}{G/W/P}");
+        let options = DisplayOptions { phyrexian: PhyrexianStyle::Phi, ..Default::default() };
+        assert_eq!(manas.display_with(&options).to_string(), "{2}{WΦ}{G/WΦ}");
+        assert_eq!(Manas::from_str(&manas.display_with(&options).to_string()), Ok(manas));
+    }
+
+    #[test]
+    fn legacy() {
+        let manas = Manas::from_legacy_str("oXo1oR").unwrap();
+        assert_eq!(manas.to_string(), "{X}{1}{R}");
+        assert_eq!(Manas::from_legacy_str("o1ocT"), None);
+        assert_eq!(Manas::from_legacy_str("o10oG"), Manas::from_str("{10}{G}").ok());
+        assert_eq!(Manas::from_legacy_str(""), Manas::from_str("").ok());
+        assert_eq!(Manas::from_legacy_str("oT"), None);
+        assert_eq!(Manas::from_legacy_str("o"), None);
+        assert_eq!(Manas::from_legacy_str("oW/U"), None);
+    }
+
+    #[test]
+    fn life_cost() {
+        let manas = Manas::from_str("{G/W/P}{G/P}{W}").unwrap();
+        assert_eq!(manas.max_life_cost(), 4);
+        assert_eq!(manas.min_life_cost(&ManaPool::new()), None);
+
+        let mut pool = ManaPool::new();
+        pool.add(ManaUnit::colored(Color::White), 2);
+        assert_eq!(manas.min_life_cost(&pool), Some(2));
+        pool.add(ManaUnit::colored(Color::Green), 1);
+        assert_eq!(manas.min_life_cost(&pool), Some(0));
+
+        let manas = Manas::from_str("{2}{G}").unwrap();
+        assert_eq!(manas.max_life_cost(), 0);
+        assert_eq!(manas.min_life_cost(&pool), Some(0));
+    }
+
+    #[test]
+    fn sort_custom() {
+        let mut registry = ColorRegistry::new();
+        registry.register('O', "purple", "#c9a0dc").unwrap();
+        registry.register('K', "pink", "#f7a8b8").unwrap();
+
+        let before = "{S}{O}{U}{K}{2}";
+        for (style, after) in [
+            (SortStyle::WheelCanonical, "{2}{U}{K}{O}{S}"),
+            (SortStyle::Scryfall, "{2}{U}{K}{O}{S}"),
+        ] {
+            let mut manas = Manas::from_str_with(before, &registry).unwrap();
+            manas.sort_with(style);
+            assert_eq!(manas.to_string(), after);
+        }
+    }
+
+    #[test]
+    fn custom_harder_than_generic() {
+        let mut registry = ColorRegistry::new();
+        registry.register('O', "purple", "#c9a0dc").unwrap();
+        registry.register('K', "pink", "#f7a8b8").unwrap();
+        let hard = |a: &str, b: &str| {
+            let a = Manas::from_str_with(a, &registry).unwrap();
+            a.is_at_least_as_hard_as(&Manas::from_str_with(b, &registry).unwrap())
+        };
+
+        assert!(hard("{O}", "{1}"));
+        assert!(!hard("{1}", "{O}"));
+        assert!(!hard("{O}", "{K}"));
+        assert!(!hard("{O}", "{S}"));
+        assert!(hard("{O}{K}", "{K}{1}"));
+        assert!(hard("{O}{O}", "{O}"));
+    }
+
+    #[test]
+    fn custom_identity() {
+        let mut registry = ColorRegistry::new();
+        let purple = registry.register('O', "purple", "#c9a0dc").unwrap();
+        let manas = Manas::from_str_with("{O}{O}{W}", &registry).unwrap();
+
+        assert_eq!(manas.custom_colors(), [purple]);
+        assert_eq!(manas.color_identity().to_string(), "W");
+        assert_eq!(manas.minimum_colors().to_string(), "W");
+        assert!(!manas.fits_identity(manas.color_identity()));
+        assert!(!manas.fits_identity("WUBRG".parse().unwrap()));
+        assert!(Manas::from_str("{W}").unwrap().custom_colors().is_empty());
+    }
+
+    #[test]
+    fn sort_long_wubrg() {
+        let before = "{R/P}{X}{C/U}{2/B}{W}{W/U}{B}{B/R/P}{2/R}{G}{C}{G/W/P}{S}{4}{Y}{R/W}";
+        let after = "{X}{Y}{4}{2/B}{2/R}{C}{C/U}{W}{W/U}{B}{B/R/P}{R/P}{R/W}{G}{G/W/P}{S}";
+        let mut manas = Manas::from_str(before).unwrap();
+
+        manas.sort_with(SortStyle::FixedWUBRG);
+        assert_eq!(manas.to_string(), after);
+    }
+
+    #[test]
+    fn sort_long_scryfall() {
+        let before = "{R/P}{X}{C/U}{2/B}{W}{W/U}{B}{B/R/P}{2/R}{G}{C}{G/W/P}{S}{4}{Y}{R/W}";
+        let after = "{X}{Y}{4}{W/U}{R/W}{B/R/P}{G/W/P}{C/U}{2/B}{2/R}{R/P}{W}{B}{G}{C}{S}";
+        let mut manas = Manas::from_str(before).unwrap();
+
+        manas.sort_with(SortStyle::Scryfall);
+        assert_eq!(manas.to_string(), after);
+    }
+
+    #[test]
+    fn groups_long() {
+        let sorted = "{X}{Y}{4}{2/B}{2/R}{C}{C/U}{B}{B/R/P}{R/P}{R/W}{G}{G/W/P}{W}{W/U}{S}";
+        let manas = Manas::from_str(sorted).unwrap();
+        let groups = manas.groups().unwrap();
+        let lengths = groups.as_array().map(<[Mana]>::len);
+        assert_eq!(lengths, [3, 2, 1, 1, 8, 1]);
+
+        assert!(Manas::from_str("").unwrap().groups().is_some());
+        assert!(Manas::from_str("{S}{W}").unwrap().groups().is_none());
+    }
+
+    #[test]
+    fn is_sorted_long() {
+        let before = "{R/P}{X}{C/U}{2/B}{W}{W/U}{B}{B/R/P}{2/R}{G}{C}{G/W/P}{S}{4}{Y}{R/W}";
+        let manas = Manas::from_str(before).unwrap();
+        assert!(!manas.is_sorted());
+
+        let sorted = manas.sorted();
+        assert!(sorted.is_sorted());
+        assert_eq!(manas.to_string(), before);
+    }
+
+    #[test]
+    fn with_variables() {
+        let manas = Manas::from_str("{X}{Y}{Z}{Z}{G}").unwrap();
+        assert_eq!(manas.with_x(1).with_y(2).with_z(3).to_string(), "{9}{G}");
+        assert_eq!(manas.with_y(0).to_string(), "{X}{Z}{Z}{G}");
+        assert_eq!(Manas::from_str("{X}").unwrap().with_x(0).to_string(), "{0}");
+        assert_eq!(Manas::from_str("{G}").unwrap().with_x(3).to_string(), "{G}");
+
+        let max = usize::MAX.to_string();
+        let manas = Manas::from_str("{X}{X}").unwrap().with_x(usize::MAX);
+        assert_eq!(manas.to_string(), format!("{{{max}}}"));
+        let manas = Manas::from_str("{X}{5}").unwrap().with_x(usize::MAX);
+        assert_eq!(manas.to_string(), format!("{{{max}}}"));
+    }
+
+    #[test]
+    fn pip_count_policies() {
+        let manas = Manas::from_str("{2/W}{C/W}{G/W/P}{G/P}{C}{S}").unwrap();
+        let policy = |hybrid, phyrexian| PipPolicy { hybrid, phyrexian };
+        assert_eq!(manas.pip_count(policy(true, true)), [3, 0, 0, 0, 2]);
+        assert_eq!(manas.pip_count(policy(true, false)), [2, 0, 0, 0, 0]);
+        assert_eq!(manas.pip_count(policy(false, true)), [0, 0, 0, 0, 1]);
+        assert_eq!(manas.pip_count(policy(false, false)), [0; 5]);
+    }
+
+    #[test]
+    fn validate() {
+        assert_eq!(Manas::from_str("{0}").unwrap().validate(), Ok(()));
+        assert_eq!(Manas::from_str("").unwrap().validate(), Ok(()));
+
+        let manas = Manas::from_str("{0}{U/W/P}{0/G}").unwrap();
+        let expected = vec![
+            (0, Violation::RedundantZero),
+            (1, Violation::NonCanonicalHybrid(Mana::from_str("W/U/P").unwrap())),
+            (2, Violation::ZeroGenericHybrid(Color::Green)),
+        ];
+        assert_eq!(manas.validate(), Err(expected));
+    }
+
+    #[test]
+    fn minimum_colors() {
+        let manas = Manas::from_str("{W/U}{U/B}{B/R}{R/G}{G/W}").unwrap();
+        assert_eq!(manas.minimum_colors().len(), 3);
+
+        let manas = Manas::from_str("{R}{R/G}{C/W}{W/P}").unwrap();
+        let mut expected = ColorSet::new();
+        expected.insert(Color::Red);
+        assert_eq!(manas.minimum_colors(), expected);
+
+        assert_eq!(Manas::from_str("{X}{S}").unwrap().minimum_colors(), ColorSet::new());
+    }
+
+    #[test]
+    fn expand_hybrids() {
+        let manas = Manas::from_str("{W/U}{U/W}{C/G}").unwrap();
+        let costs: Vec<String> = manas.expand_hybrids().map(|x| x.to_string()).collect();
+        assert_eq!(
+            costs,
+            ["{W}{U}{C}", "{W}{U}{G}", "{W}{W}{C}", "{W}{W}{G}", "{U}{U}{C}", "{U}{U}{G}"]
+        );
+
+        let manas = Manas::from_str("{B/R/P}").unwrap();
+        assert_eq!(manas.expand_hybrids().count(), 3);
+
+        let manas = Manas::from_str("").unwrap();
+        assert_eq!(manas.expand_hybrids().collect::<Vec<_>>(), [manas]);
+    }
+
+    #[test]
+    fn at_least_as_hard() {
+        let hard = |a: &str, b: &str| {
+            Manas::from_str(a).unwrap().is_at_least_as_hard_as(&Manas::from_str(b).unwrap())
+        };
+
+        assert!(hard("{W}{U}", "{W/U}{W/U}"));
+        assert!(!hard("{W/U}{W/U}", "{W}{U}"));
+        assert!(hard("{C}", "{1}"));
+        assert!(!hard("{1}", "{C}"));
+        assert!(!hard("{2/W}", "{2}"));
+        assert!(hard("{2}", "{2/W}"));
+        assert!(!hard("{2/W}", "{W}"));
+        assert!(hard("{W}", "{2/W}"));
+        assert!(hard("{W}{W}", "{2/W}"));
+        assert!(hard("{W}", "{W/P}"));
+        assert!(!hard("{2}", "{C/W}{C/U}"));
+        assert!(hard("{W}{W}{U}{U}", "{W/U}{W/U}{W/U}"));
+        assert!(!hard("{3}", "{W/U}"));
+        assert!(!hard("{X}{1}", "{S}"));
+        assert!(hard("{S}{S}", "{S}{1}"));
+        assert!(hard("", ""));
+        assert!(hard("{∞}", "{1}"));
+        assert!(hard("{∞}", "{∞}"));
+        assert!(!hard("{0}", "{∞}"));
+        assert!(!hard("{W}{U}{B}{R}{G}", "{1}{∞}"));
+    }
+
+    #[test]
+    fn snow_harder_than_generic() {
+        let snow = Manas::from_str("{S}").unwrap();
+        let generic = Manas::from_str("{1}").unwrap();
+        assert!(snow.is_at_least_as_hard_as(&generic));
+        assert!(!generic.is_at_least_as_hard_as(&snow));
+
+        // A pool which can pay for `{1}` but not `{S}`
+        let mut pool = ManaPool::new();
+        pool.add(ManaUnit::colored(Color::Green), 1);
+        assert!(pool.can_pay(&generic));
+        assert!(!pool.can_pay(&snow));
+
+        pool.clear();
+        pool.add(ManaUnit { color: Some(Color::Green), snow: true }, 1);
+        assert!(pool.can_pay(&generic));
+        assert!(pool.can_pay(&snow));
+    }
+
+    #[test]
+    fn semantically_eq() {
+        let a = Manas::from_str("{2}{U}{W}{G/W/P}").unwrap();
+        let b = Manas::from_str("{W}{W/G/P}{U}{2}").unwrap();
+        assert!(a.semantically_eq(&b));
+        assert!(b.semantically_eq(&a));
+
+        let c = Manas::from_str("{2}{U}{W}{W}").unwrap();
+        assert!(!a.semantically_eq(&c));
+
+        let d = Manas::from_str("{2}{U}{W}").unwrap();
+        assert!(!a.semantically_eq(&d));
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn svg_shadow_offset() {
+        let manas: Manas = "{1}{U}".parse().unwrap();
+        let config = SVGConfig { shadow: false, shadow_offset: 4.0, ..Default::default() };
+        let svg = manas.as_svg(&config).to_string();
+        assert!(svg.contains(r#"viewBox="-4 -4 80 40""#));
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn svg_spacing_and_height() {
+        let manas: Manas = "{1}{U}{U}".parse().unwrap();
+        let config = SVGConfig { spacing: 5.0, height: Some(70.0), ..Default::default() };
+        let svg = manas.as_svg(&config).to_string();
+        assert!(svg.contains(r#"viewBox="-1.5 -1.5 115 35""#));
+        assert!(svg.contains(r#"height="70""#));
+        assert!(svg.contains(r#"width="230""#));
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn svg_padding_and_size() {
+        let manas: Manas = "{U}{U}".parse().unwrap();
+        let config = SVGConfig {
+            padding: Some(0.0),
+            width: Some(60.0),
+            height: Some(20.0),
+            ..Default::default()
+        };
+        let svg = manas.as_svg(&config).to_string();
+        assert!(svg.contains(r#"viewBox="0 0 64 32""#));
+        assert!(svg.contains(r#"width="60""#));
+        assert!(svg.contains(r#"height="20""#));
+        assert!(svg.contains(r#"x="32""#));
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn svg_wrapped() {
+        let manas: Manas = "{2}{HW}{U}{B}".parse().unwrap();
+        let config = SVGConfig { spacing: 5.0, ..Default::default() };
+
+        // Everything fits in one row
+        let svg = manas.as_svg_wrapped(1000.0, &config).to_string();
+        assert_eq!(svg, manas.as_svg(&config).to_string());
+
+        // Two rows, the first with the half mana
+        let svg = manas.as_svg_wrapped(90.0, &config).to_string();
+        assert!(svg.contains(r#"viewBox="-1.5 -1.5 75 75""#));
+        assert_eq!(svg.matches(r#"y="38.5""#).count(), 2);
+
+        // Symbols wider than `max_width` still get their own row
+        let svg = manas.as_svg_wrapped(10.0, &config).to_string();
+        assert!(svg.contains(r#"viewBox="-1.5 -1.5 35 155""#));
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn svg_rendering_hints() {
+        use crate::ShapeRendering;
+
+        let manas: Manas = "{2}{G}".parse().unwrap();
+        let config = SVGConfig {
+            shape_rendering: Some(ShapeRendering::CrispEdges),
+            non_scaling_stroke: true,
+            circle_stroke: Some(crate::Stroke { color: "black".to_string(), width: 1.0 }),
+            ..Default::default()
+        };
+        let svg = manas.as_svg(&config).to_string();
+        assert_eq!(svg.matches(r#"shape-rendering="crispEdges""#).count(), 3);
+        assert_eq!(svg.matches(r#"vector-effect="non-scaling-stroke""#).count(), 2);
+
+        // The symbols inherit the hint from the outermost SVG
+        let config = SVGConfig { minify: true, ..config };
+        let svg = manas.as_svg(&config).to_string();
+        assert_eq!(svg.matches(r#"shape-rendering="crispEdges""#).count(), 1);
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn svg_minify() {
+        let manas: Manas = "{2}{G}".parse().unwrap();
+        let config = SVGConfig { precision: Some(1), minify: true, ..Default::default() };
+        let svg = manas.as_svg(&config).to_string();
+        let full = manas.as_svg(&SVGConfig::default()).to_string();
+        assert!(svg.len() < full.len());
+        assert_eq!(svg.matches("xmlns").count(), 1);
+        assert!(!svg.contains(r#"stroke="none""#));
+        assert_eq!(svg.matches("<title>").count(), 1);
+        assert!(!svg.contains(".00"));
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn svg_compatible() {
+        let manas: Manas = "{2}{HW}{G}".parse().unwrap();
+        let config = SVGConfig { compatible: true, ..Default::default() };
+        let svg = manas.as_svg(&config).to_string();
+        // The half mana symbol has to be cut off
+        assert_eq!(svg.matches("<svg").count(), 2);
+        assert!(svg.contains(r#"<g transform="translate(52.5 0)">"#));
+        assert!(!svg.contains("aria-"));
+
+        let mut rendered = String::new();
+        manas.render_svg_to(&mut rendered, &config);
+        assert_eq!(rendered, crate::canonical_svg(&manas.as_svg(&config)));
+    }
+
+    #[cfg(feature = "svgz")]
+    #[test]
+    fn svgz() {
+        use std::io::Read;
+
+        let manas: Manas = "{1}{U}{U}".parse().unwrap();
+        let config = SVGConfig::default();
+        let mut svg = String::new();
+        flate2::read::GzDecoder::new(&manas.as_svgz(&config)[..]).read_to_string(&mut svg).unwrap();
+        assert_eq!(svg, crate::canonical_svg(&manas.as_svg(&config)));
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn png_size() {
+        let manas: Manas = "{1}{U}{U}".parse().unwrap();
+        let config = SVGConfig { shadow: false, shadow_offset: 0.0, ..Default::default() };
+        let png = manas.as_png(20, &config).unwrap();
+
+        // The width and height are stored in the IHDR chunk
+        assert_eq!(png[16..24], [0, 0, 0, 60, 0, 0, 0, 20]);
+        assert_eq!(manas.as_png(0, &config), None);
+        assert_eq!(Manas::from(Vec::new()).as_png(20, &config), None);
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn pdf_size() {
+        let manas: Manas = "{1}{U}{U}".parse().unwrap();
+        let config = SVGConfig { shadow: false, shadow_offset: 0.0, ..Default::default() };
+
+        // An inch is 72 points
+        let pdf = manas.as_pdf(25.4, &config).unwrap();
+        let pdf = String::from_utf8_lossy(&pdf);
+        assert!(pdf.contains("/MediaBox [0 0 216 72]"), "{pdf}");
+        assert_eq!(manas.as_pdf(0.0, &config), None);
+        assert_eq!(manas.as_pdf(f32::NAN, &config), None);
+        assert_eq!(Manas::from(Vec::new()).as_pdf(10.0, &config), None);
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn strip_png() {
+        let manas: Manas = "{1}{U}{U}".parse().unwrap();
+        let config = SVGConfig { shadow: false, shadow_offset: 0.0, ..Default::default() };
+
+        // An inch high at 300 DPI, with a quarter of an inch between symbols
+        let png = manas.render_strip_png(300, 6.35, 25.4, &config).unwrap();
+        assert_eq!(png[16..24], [0, 0, 0x04, 0x1a, 0, 0, 0x01, 0x2c]);
+
+        // 300 DPI is 11811 pixels per meter
+        assert_eq!(png[37..41], *b"pHYs");
+        assert_eq!(png[41..50], [0, 0, 0x2e, 0x23, 0, 0, 0x2e, 0x23, 1]);
+        assert_eq!(manas.render_strip_png(300, 0.0, 0.0, &config), None);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn rgba_image() {
+        let manas: Manas = "{1}{U}{U}".parse().unwrap();
+        let config = SVGConfig { shadow: false, shadow_offset: 0.0, ..Default::default() };
+        let image = manas.as_rgba_image(20, &config).unwrap();
+        assert_eq!(image.dimensions(), (60, 20));
+
+        // The corners are outside of the circles, while the top is blue
+        assert_eq!(image.get_pixel(0, 0).0[3], 0);
+        assert_eq!(image.get_pixel(50, 1).0, [0xaa, 0xe0, 0xfa, 0xff]);
+    }
+
     #[test]
     fn nom_parse_long_1() {
         let unsorted_long = "{R/P}{X}{C/U}{2/B}{W}{W/U}{B}{B/R/P}{2/R}{G}{C}{G/W/P}{S}{4}{Y}{R/W}";
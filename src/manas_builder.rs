@@ -0,0 +1,230 @@
+use std::fmt::Display;
+
+use crate::{Color, GenericMana, Mana, Manas, SingleMana, SplitMana};
+
+/// Builder for [`Manas`], created by [`Manas::builder`].
+///
+/// Generic mana is always placed first (after any `X`, `Y` or `Z`), as a
+/// single symbol of the total amount. All other mana symbols are placed in the
+/// order they were added.
+///
+/// # Example
+///
+/// ```
+/// use mana_symbols::{Color, Manas};
+///
+/// let manas = Manas::builder()
+///     .blue(2)
+///     .generic(2)
+///     .hybrid(Color::White, Color::Blue)
+///     .x()
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(manas.to_string(), "{X}{2}{U}{U}{W/U}");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ManasBuilder {
+    variables: Vec<Mana>,
+    generic: Option<usize>,
+    manas: Vec<Mana>,
+}
+
+/// Error returned by [`ManasBuilder::build`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManasBuilderError {
+    /// A hybrid mana symbol was added with the same color on both halves.
+    HybridSameColor(Color),
+}
+
+impl Display for ManasBuilderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::HybridSameColor(color) => {
+                write!(f, "hybrid mana with {} on both halves", color.name())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ManasBuilderError {}
+
+impl ManasBuilder {
+    /// Add `n` generic mana. The amount saturates at [`usize::MAX`].
+    #[must_use]
+    pub fn generic(mut self, n: usize) -> Self {
+        self.generic = Some(self.generic.unwrap_or(0).saturating_add(n));
+        self
+    }
+
+    /// Add an `{X}`.
+    #[must_use]
+    pub fn x(mut self) -> Self {
+        self.variables.push(Mana::Generic(GenericMana::X));
+        self
+    }
+
+    /// Add a `{Y}`.
+    #[must_use]
+    pub fn y(mut self) -> Self {
+        self.variables.push(Mana::Generic(GenericMana::Y));
+        self
+    }
+
+    /// Add a `{Z}`.
+    #[must_use]
+    pub fn z(mut self) -> Self {
+        self.variables.push(Mana::Generic(GenericMana::Z));
+        self
+    }
+
+    /// Add `n` mana of `color`.
+    #[must_use]
+    pub fn color(self, color: Color, n: usize) -> Self {
+        self.repeat(Mana::Single(SingleMana::Normal(color)), n)
+    }
+
+    /// Add `n` white mana.
+    #[must_use]
+    pub fn white(self, n: usize) -> Self {
+        self.color(Color::White, n)
+    }
+
+    /// Add `n` blue mana.
+    #[must_use]
+    pub fn blue(self, n: usize) -> Self {
+        self.color(Color::Blue, n)
+    }
+
+    /// Add `n` black mana.
+    #[must_use]
+    pub fn black(self, n: usize) -> Self {
+        self.color(Color::Black, n)
+    }
+
+    /// Add `n` red mana.
+    #[must_use]
+    pub fn red(self, n: usize) -> Self {
+        self.color(Color::Red, n)
+    }
+
+    /// Add `n` green mana.
+    #[must_use]
+    pub fn green(self, n: usize) -> Self {
+        self.color(Color::Green, n)
+    }
+
+    /// Add `n` phyrexian mana of `color`.
+    #[must_use]
+    pub fn phyrexian(self, color: Color, n: usize) -> Self {
+        self.repeat(Mana::Single(SingleMana::Phyrexian(color)), n)
+    }
+
+    /// Add `n` colorless mana.
+    #[must_use]
+    pub fn colorless(self, n: usize) -> Self {
+        self.repeat(Mana::Colorless, n)
+    }
+
+    /// Add `n` snow mana.
+    #[must_use]
+    pub fn snow(self, n: usize) -> Self {
+        self.repeat(Mana::Snow, n)
+    }
+
+    /// Add a hybrid mana symbol of two colors.
+    #[must_use]
+    pub fn hybrid(self, a: Color, b: Color) -> Self {
+        self.mana(Mana::Split(SplitMana::Duo { a, b, phyrexian: false }))
+    }
+
+    /// Add a phyrexian hybrid mana symbol of two colors.
+    #[must_use]
+    pub fn phyrexian_hybrid(self, a: Color, b: Color) -> Self {
+        self.mana(Mana::Split(SplitMana::Duo { a, b, phyrexian: true }))
+    }
+
+    /// Add a hybrid generic mana symbol, e.g. `{2/W}`.
+    #[must_use]
+    pub fn generic_hybrid(self, value: usize, color: Color) -> Self {
+        self.mana(Mana::Split(SplitMana::Mono { value, color }))
+    }
+
+    /// Add a hybrid colorless mana symbol, e.g. `{C/W}`.
+    #[must_use]
+    pub fn colorless_hybrid(self, color: Color) -> Self {
+        self.mana(Mana::Split(SplitMana::Colorless { color }))
+    }
+
+    /// Add any mana symbol. Generic mana is merged with the rest of the generic
+    /// mana (see [`ManasBuilder::generic`]).
+    #[must_use]
+    pub fn mana(mut self, mana: Mana) -> Self {
+        match mana {
+            Mana::Generic(GenericMana::Number(n)) => return self.generic(n),
+            Mana::Generic(_) => self.variables.push(mana),
+            _ => self.manas.push(mana),
+        }
+        self
+    }
+
+    fn repeat(mut self, mana: Mana, n: usize) -> Self {
+        self.manas.extend(std::iter::repeat_n(mana, n));
+        self
+    }
+
+    /// Validate and build the [`Manas`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a hybrid mana symbol has the same color on both
+    /// halves.
+    pub fn build(self) -> Result<Manas, ManasBuilderError> {
+        for mana in &self.manas {
+            if let Mana::Split(SplitMana::Duo { a, b, .. }) = mana
+                && a == b
+            {
+                return Err(ManasBuilderError::HybridSameColor(*a));
+            }
+        }
+
+        // `{0}` is only written when there are no other mana symbols
+        let generic = self
+            .generic
+            .filter(|&n| n != 0 || (self.variables.is_empty() && self.manas.is_empty()))
+            .map(|n| Mana::Generic(GenericMana::Number(n)));
+
+        let mut manas = self.variables;
+        manas.extend(generic);
+        manas.extend(self.manas);
+        Ok(manas.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hybrid_same_color() {
+        let res = Manas::builder().hybrid(Color::Red, Color::Red).build();
+        assert_eq!(res, Err(ManasBuilderError::HybridSameColor(Color::Red)));
+    }
+
+    #[test]
+    fn zero() {
+        assert_eq!(Manas::builder().generic(0).build().unwrap().to_string(), "{0}");
+        assert_eq!(Manas::builder().generic(0).red(1).build().unwrap().to_string(), "{R}");
+        assert_eq!(Manas::builder().build().unwrap().to_string(), "");
+    }
+
+    #[test]
+    fn merge_generic() {
+        let manas = "{2}".parse().unwrap();
+        let manas = Manas::builder().generic(1).mana(manas).snow(1).build().unwrap();
+        assert_eq!(manas.to_string(), "{3}{S}");
+
+        let manas = Manas::builder().generic(usize::MAX).generic(1).build().unwrap();
+        assert_eq!(manas.generic_amount(), usize::MAX);
+    }
+}
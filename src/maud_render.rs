@@ -0,0 +1,36 @@
+use maud::Render;
+
+use crate::{Mana, Manas, SVGConfig};
+
+/// Renders the mana symbol as HTML, like [`Mana::as_html`] with CSS included.
+/// For other options, use
+/// `maud::PreEscaped(mana.as_html_with(&options, &config))` instead.
+impl Render for Mana {
+    fn render_to(&self, buffer: &mut String) {
+        self.write_html(buffer, true, &SVGConfig::default()).unwrap();
+    }
+}
+
+/// Renders the mana symbols as HTML, like [`Manas::as_html`] with CSS
+/// included. For other options, use
+/// `maud::PreEscaped(manas.as_html_with(&options, &config))` instead.
+impl Render for Manas {
+    fn render_to(&self, buffer: &mut String) {
+        self.write_html(buffer, true, &SVGConfig::default()).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maud::html;
+
+    use super::*;
+
+    #[test]
+    fn render() {
+        let cost: Manas = "{2}{U}".parse().unwrap();
+        let markup = html! { p { "Cost: " (cost) } };
+        let expected = format!("<p>Cost: {}</p>", cost.as_html(true, &SVGConfig::default()));
+        assert_eq!(markup.into_string(), expected);
+    }
+}
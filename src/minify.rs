@@ -0,0 +1,168 @@
+use svg::node::{Node, element::SVG};
+
+use crate::SVGConfig;
+
+/// Inherited presentation attributes, with their initial values. If an
+/// element sets one of these to the value it would inherit anyway, the
+/// attribute is redundant.
+const INHERITED: [(&str, &str); 3] =
+    [("fill", "black"), ("stroke", "none"), ("shape-rendering", "auto")];
+
+/// Attributes which only contain numbers and separators, e.g. `viewBox` or the
+/// data of paths.
+const NUMERIC: [&str; 15] = [
+    "d",
+    "viewBox",
+    "x",
+    "y",
+    "width",
+    "height",
+    "r",
+    "rx",
+    "cx",
+    "cy",
+    "dx",
+    "dy",
+    "stdDeviation",
+    "stroke-width",
+    "font-size",
+];
+
+/// Make `svg` smaller, as decided by [`SVGConfig::precision`] and
+/// [`SVGConfig::minify`]. Does not change how `svg` is drawn, except for
+/// rounding.
+pub(crate) fn minify(mut svg: SVG, config: &SVGConfig) -> SVG {
+    if config.precision.is_some() || config.minify {
+        let inherited = INHERITED.map(|(name, value)| (name, value.to_string()));
+        minify_node(&mut svg, config, &inherited, true);
+    }
+    svg
+}
+
+fn minify_node(
+    node: &mut dyn Node,
+    config: &SVGConfig,
+    inherited: &[(&'static str, String); 3],
+    root: bool,
+) {
+    let mut inherited = inherited.clone();
+    let mut hidden = false;
+    if let Some(attributes) = node.get_attributes_mut() {
+        if let Some(precision) = config.precision {
+            for name in NUMERIC {
+                if let Some(value) = attributes.get_mut(name) {
+                    *value = round_numbers(value, precision).into();
+                }
+            }
+        }
+
+        if config.minify {
+            // Only the outermost SVG needs to declare its namespace
+            if !root {
+                attributes.remove("xmlns");
+            }
+            // Descriptions of hidden elements are never read
+            hidden = attributes.get("aria-hidden").is_some_and(|value| **value == *"true");
+            if hidden {
+                attributes.remove("aria-label");
+                attributes.remove("role");
+            }
+            for (name, current) in &mut inherited {
+                match attributes.get(*name) {
+                    Some(value) if **value == **current => {
+                        attributes.remove(*name);
+                    }
+                    Some(value) => *current = value.to_string(),
+                    None => {}
+                }
+            }
+        }
+    }
+
+    if let Some(children) = node.get_children_mut() {
+        if hidden {
+            children.retain(|child| !matches!(child.get_name(), "title" | "desc"));
+        }
+        for child in children {
+            minify_node(child.as_mut(), config, &inherited, false);
+        }
+    }
+}
+
+/// Round every number in `value` to `precision` decimals, removing trailing
+/// zeros.
+fn round_numbers(value: &str, precision: usize) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(c) = rest.chars().next() {
+        let length = number_length(rest);
+        if length == 0 {
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        let (number, tail) = rest.split_at(length);
+        match number.parse::<f64>() {
+            Ok(number) => out.push_str(&format_number(number, precision)),
+            Err(_) => out.push_str(number),
+        }
+        rest = tail;
+    }
+    out
+}
+
+/// The length of the number at the start of `s`, or 0 if `s` does not start
+/// with a number.
+fn number_length(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let digits = |mut i: usize| {
+        while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+            i += 1;
+        }
+        i
+    };
+
+    let mut i = usize::from(matches!(bytes.first(), Some(b'-' | b'+')));
+    let integer_end = digits(i);
+    let mut has_digits = integer_end > i;
+    i = integer_end;
+    if bytes.get(i) == Some(&b'.') {
+        let fraction_end = digits(i + 1);
+        has_digits |= fraction_end > i + 1;
+        i = fraction_end;
+    }
+    if !has_digits {
+        return 0;
+    }
+    if matches!(bytes.get(i), Some(b'e' | b'E')) {
+        let sign = usize::from(matches!(bytes.get(i + 1), Some(b'-' | b'+')));
+        let exponent_end = digits(i + 1 + sign);
+        if exponent_end > i + 1 + sign {
+            i = exponent_end;
+        }
+    }
+    i
+}
+
+fn format_number(number: f64, precision: usize) -> String {
+    let mut s = format!("{number:.precision$}");
+    if s.contains('.') {
+        let trimmed = s.trim_end_matches('0').trim_end_matches('.').len();
+        s.truncate(trimmed);
+    }
+    if s == "-0" { "0".to_string() } else { s }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round() {
+        assert_eq!(round_numbers("M23.11,29.095 c-1.903,1.937", 1), "M23.1,29.1 c-1.9,1.9");
+        assert_eq!(round_numbers("-0.04 -1.5 35 35", 1), "0 -1.5 35 35");
+        assert_eq!(round_numbers("200%", 2), "200%");
+        assert_eq!(round_numbers("1e2,.6", 0), "100,1");
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    Color, CustomColor, Foil, Plate,
+    color::{HEX_B, HEX_C, HEX_G, HEX_R, HEX_U, HEX_W},
+};
+
+/// The fill colors of mana symbols, written as SVG colors (e.g. `#aae0fa`).
+///
+/// Used by [`SVGConfig`][crate::SVGConfig].
+///
+/// For the default colors (see [`Color::hex`]), use [`Palette::default`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    /// Fill of white mana.
+    pub white: String,
+
+    /// Fill of blue mana.
+    pub blue: String,
+
+    /// Fill of black mana.
+    pub black: String,
+
+    /// Fill of red mana.
+    pub red: String,
+
+    /// Fill of green mana.
+    pub green: String,
+
+    /// Fill of generic and colorless mana.
+    pub colorless: String,
+
+    /// Fill of mana of custom colors (see
+    /// [`ColorRegistry::extend_palette`][crate::ColorRegistry::extend_palette]).
+    pub custom: BTreeMap<CustomColor, String>,
+
+    /// Names of custom colors, used to describe their mana symbols (see
+    /// [`ColorRegistry::extend_palette`][crate::ColorRegistry::extend_palette]).
+    pub custom_names: BTreeMap<CustomColor, String>,
+
+    /// Color of an outline around each circle, if any.
+    pub outline: Option<String>,
+
+    /// A gradient drawn on top of each mana symbol, if any.
+    pub foil: Option<Foil>,
+
+    /// A plate drawn behind a row of mana symbols, if any.
+    pub plate: Option<Plate>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            white: HEX_W.to_string(),
+            blue: HEX_U.to_string(),
+            black: HEX_B.to_string(),
+            red: HEX_R.to_string(),
+            green: HEX_G.to_string(),
+            colorless: HEX_C.to_string(),
+            custom: BTreeMap::new(),
+            custom_names: BTreeMap::new(),
+            outline: None,
+            foil: None,
+            plate: None,
+        }
+    }
+}
+
+impl Palette {
+    /// Saturated colors with black outlines, which are easier to tell apart
+    /// than the default colors and stand out against any background.
+    #[must_use]
+    pub fn high_contrast() -> Self {
+        Self {
+            white: "#ffffff".to_string(),
+            blue: "#5cbcff".to_string(),
+            black: "#a39a97".to_string(),
+            red: "#ff7a56".to_string(),
+            green: "#45c574".to_string(),
+            colorless: "#d9d9d9".to_string(),
+            custom: BTreeMap::new(),
+            custom_names: BTreeMap::new(),
+            outline: Some("#000000".to_string()),
+            foil: None,
+            plate: None,
+        }
+    }
+
+    /// Slightly darker colors with light outlines, for drawing on dark
+    /// backgrounds (see [`SVGConfig::dark`][crate::SVGConfig::dark]).
+    #[must_use]
+    pub fn dark() -> Self {
+        Self {
+            white: "#e8e4c0".to_string(),
+            blue: "#8fc9e6".to_string(),
+            black: "#a69d9a".to_string(),
+            red: "#e0937a".to_string(),
+            green: "#84bd98".to_string(),
+            colorless: "#a69d9a".to_string(),
+            custom: BTreeMap::new(),
+            custom_names: BTreeMap::new(),
+            outline: Some("#f0f0f0".to_string()),
+            foil: None,
+            plate: None,
+        }
+    }
+
+    /// Colors which remain distinguishable with red-green color blindness
+    /// (deuteranopia and protanopia), based on the palette of
+    /// [Okabe and Ito](https://jfly.uni-koeln.de/color/). Red and green are
+    /// drawn as orange and bluish green, and circles have black outlines.
+    #[must_use]
+    pub fn colorblind() -> Self {
+        Self {
+            white: "#fffbd5".to_string(),
+            blue: "#56b4e9".to_string(),
+            black: "#999999".to_string(),
+            red: "#e69f00".to_string(),
+            green: "#009e73".to_string(),
+            colorless: "#cbc2bf".to_string(),
+            custom: BTreeMap::new(),
+            custom_names: BTreeMap::new(),
+            outline: Some("#000000".to_string()),
+            foil: None,
+            plate: None,
+        }
+    }
+
+    /// The fill of mana of `color`.
+    #[must_use]
+    pub fn color(&self, color: Color) -> &str {
+        match color {
+            Color::White => &self.white,
+            Color::Blue => &self.blue,
+            Color::Black => &self.black,
+            Color::Red => &self.red,
+            Color::Green => &self.green,
+        }
+    }
+
+    /// The fill of mana of a custom color, which is the same as colorless mana
+    /// if it has not been set.
+    #[must_use]
+    pub fn custom_color(&self, color: CustomColor) -> &str {
+        self.custom.get(&color).unwrap_or(&self.colorless)
+    }
+
+    /// The name of a custom color, if it has been set.
+    #[must_use]
+    pub fn custom_name(&self, color: CustomColor) -> Option<&str> {
+        self.custom_names.get(&color).map(String::as_str)
+    }
+}
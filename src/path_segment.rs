@@ -0,0 +1,204 @@
+use svg::node::element::path::{Command, Data, Position};
+
+/// A segment of the outline of a glyph, with absolute coordinates. Returned
+/// by [`Mana::glyph_paths`][crate::Mana::glyph_paths].
+///
+/// Outlines are filled using the `nonzero` fill rule. Each segment corresponds
+/// to one method of a path builder in e.g. [lyon](https://docs.rs/lyon_path)
+/// (`begin`, `line_to`, `quadratic_bezier_to`, `cubic_bezier_to` and `close`),
+/// or of a `CanvasRenderingContext2D`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathSegment {
+    /// Start a new subpath at (`x`, `y`).
+    MoveTo { x: f64, y: f64 },
+
+    /// A straight line to (`x`, `y`).
+    LineTo { x: f64, y: f64 },
+
+    /// A quadratic Bézier curve to (`x`, `y`), with the control point
+    /// (`x1`, `y1`).
+    QuadTo { x1: f64, y1: f64, x: f64, y: f64 },
+
+    /// A cubic Bézier curve to (`x`, `y`), with the control points
+    /// (`x1`, `y1`) and (`x2`, `y2`).
+    CubicTo { x1: f64, y1: f64, x2: f64, y2: f64, x: f64, y: f64 },
+
+    /// A straight line back to the start of the current subpath, which ends
+    /// it.
+    Close,
+}
+
+impl PathSegment {
+    /// Apply `f` to every point of the segment.
+    pub(crate) fn map(self, f: impl Fn(f64, f64) -> (f64, f64)) -> Self {
+        match self {
+            Self::MoveTo { x, y } => {
+                let (x, y) = f(x, y);
+                Self::MoveTo { x, y }
+            }
+            Self::LineTo { x, y } => {
+                let (x, y) = f(x, y);
+                Self::LineTo { x, y }
+            }
+            Self::QuadTo { x1, y1, x, y } => {
+                let (x1, y1) = f(x1, y1);
+                let (x, y) = f(x, y);
+                Self::QuadTo { x1, y1, x, y }
+            }
+            Self::CubicTo { x1, y1, x2, y2, x, y } => {
+                let (x1, y1) = f(x1, y1);
+                let (x2, y2) = f(x2, y2);
+                let (x, y) = f(x, y);
+                Self::CubicTo { x1, y1, x2, y2, x, y }
+            }
+            Self::Close => Self::Close,
+        }
+    }
+}
+
+/// Convert the data of a path into segments with absolute coordinates, where
+/// horizontal, vertical and smooth commands are written out. Returns nothing
+/// if `data` could not be parsed.
+pub(crate) fn parse_path(data: &str) -> Vec<PathSegment> {
+    let Ok(data) = Data::parse(data) else {
+        return Vec::new();
+    };
+
+    let mut segments = Vec::new();
+    let mut current = (0.0, 0.0);
+    let mut start = (0.0, 0.0);
+    // The last control point of the previous segment, if it was a curve of
+    // the same kind, which smooth curves reflect
+    let mut cubic_control = None;
+    let mut quad_control = None;
+
+    for command in data.iter() {
+        let (position, parameters) = match command {
+            Command::Close => {
+                segments.push(PathSegment::Close);
+                current = start;
+                cubic_control = None;
+                quad_control = None;
+                continue;
+            }
+            Command::Move(position, parameters)
+            | Command::Line(position, parameters)
+            | Command::HorizontalLine(position, parameters)
+            | Command::VerticalLine(position, parameters)
+            | Command::QuadraticCurve(position, parameters)
+            | Command::SmoothQuadraticCurve(position, parameters)
+            | Command::CubicCurve(position, parameters)
+            | Command::SmoothCubicCurve(position, parameters)
+            | Command::EllipticalArc(position, parameters) => (position, parameters),
+        };
+        let parameters: Vec<f64> = parameters.iter().map(|&x| f64::from(x)).collect();
+        let relative = matches!(position, Position::Relative);
+
+        let arity = match command {
+            Command::HorizontalLine(..) | Command::VerticalLine(..) => 1,
+            Command::QuadraticCurve(..) | Command::SmoothCubicCurve(..) => 4,
+            Command::CubicCurve(..) => 6,
+            Command::EllipticalArc(..) => 7,
+            _ => 2,
+        };
+        for (i, p) in parameters.chunks_exact(arity).enumerate() {
+            let point = |x: f64, y: f64| {
+                if relative { (current.0 + x, current.1 + y) } else { (x, y) }
+            };
+            let reflect = |control: Option<(f64, f64)>| match control {
+                Some((x, y)) => (2.0 * current.0 - x, 2.0 * current.1 - y),
+                None => current,
+            };
+
+            let mut next_cubic_control = None;
+            let mut next_quad_control = None;
+            let (x, y) = match command {
+                Command::Move(..) => {
+                    let (x, y) = point(p[0], p[1]);
+                    // Any further points are drawn as lines
+                    if i == 0 {
+                        start = (x, y);
+                        segments.push(PathSegment::MoveTo { x, y });
+                    } else {
+                        segments.push(PathSegment::LineTo { x, y });
+                    }
+                    (x, y)
+                }
+                Command::HorizontalLine(..) => {
+                    let x = if relative { current.0 + p[0] } else { p[0] };
+                    segments.push(PathSegment::LineTo { x, y: current.1 });
+                    (x, current.1)
+                }
+                Command::VerticalLine(..) => {
+                    let y = if relative { current.1 + p[0] } else { p[0] };
+                    segments.push(PathSegment::LineTo { x: current.0, y });
+                    (current.0, y)
+                }
+                Command::CubicCurve(..) | Command::SmoothCubicCurve(..) => {
+                    let (first, rest) = match command {
+                        Command::CubicCurve(..) => (point(p[0], p[1]), &p[2..]),
+                        _ => (reflect(cubic_control), p),
+                    };
+                    let (x1, y1) = first;
+                    let (x2, y2) = point(rest[0], rest[1]);
+                    let (x, y) = point(rest[2], rest[3]);
+                    segments.push(PathSegment::CubicTo { x1, y1, x2, y2, x, y });
+                    next_cubic_control = Some((x2, y2));
+                    (x, y)
+                }
+                Command::QuadraticCurve(..) | Command::SmoothQuadraticCurve(..) => {
+                    let (control, rest) = match command {
+                        Command::QuadraticCurve(..) => (point(p[0], p[1]), &p[2..]),
+                        _ => (reflect(quad_control), p),
+                    };
+                    let (x1, y1) = control;
+                    let (x, y) = point(rest[0], rest[1]);
+                    segments.push(PathSegment::QuadTo { x1, y1, x, y });
+                    next_quad_control = Some(control);
+                    (x, y)
+                }
+                // The built-in artwork has no arcs, so they are only drawn as
+                // lines to their end points
+                Command::EllipticalArc(..) => {
+                    let (x, y) = point(p[5], p[6]);
+                    segments.push(PathSegment::LineTo { x, y });
+                    (x, y)
+                }
+                _ => {
+                    let (x, y) = point(p[0], p[1]);
+                    segments.push(PathSegment::LineTo { x, y });
+                    (x, y)
+                }
+            };
+            current = (x, y);
+            cubic_control = next_cubic_control;
+            quad_control = next_quad_control;
+        }
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absolute() {
+        use PathSegment::{Close, CubicTo, LineTo, MoveTo};
+
+        let segments = parse_path("M1,2 h3 v4 l-1,1 c1,0,2,1,2,2 s3,3,3,3 z L0,0");
+        assert_eq!(
+            segments,
+            [
+                MoveTo { x: 1.0, y: 2.0 },
+                LineTo { x: 4.0, y: 2.0 },
+                LineTo { x: 4.0, y: 6.0 },
+                LineTo { x: 3.0, y: 7.0 },
+                CubicTo { x1: 4.0, y1: 7.0, x2: 5.0, y2: 8.0, x: 5.0, y: 9.0 },
+                CubicTo { x1: 5.0, y1: 10.0, x2: 8.0, y2: 12.0, x: 8.0, y: 12.0 },
+                Close,
+                LineTo { x: 0.0, y: 0.0 },
+            ]
+        );
+    }
+}
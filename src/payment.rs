@@ -0,0 +1,497 @@
+use std::cmp::Reverse;
+
+use crate::{Color, ColorSet, GenericMana, Mana, ManaPool, ManaUnit, Manas, SingleMana, SplitMana};
+
+/// The life paid for a Phyrexian symbol instead of mana.
+const PHYREXIAN_LIFE: usize = 2;
+
+/// How a symbol of a cost is paid, see [`Payment`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SymbolPayment {
+    /// Paid using this mana from the pool. This is empty for symbols which
+    /// cost no mana, like `{0}` and `{X}`.
+    Mana(ManaPool),
+    /// Paid using this much life instead of mana, for Phyrexian symbols.
+    Life(usize),
+}
+
+/// A way of paying for a cost using a [`ManaPool`], found using
+/// [`ManaPool::solve_payment`].
+///
+/// This shows what paid for each symbol, which is needed to apply effects
+/// like "if snow mana was spent to cast this spell".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Payment {
+    symbols: Vec<(Mana, SymbolPayment)>,
+}
+
+impl Payment {
+    /// Each symbol of the cost, in order, together with how it was paid.
+    #[must_use]
+    pub fn symbols(&self) -> &[(Mana, SymbolPayment)] {
+        &self.symbols
+    }
+
+    /// All mana spent from the pool.
+    #[must_use]
+    pub fn spent(&self) -> ManaPool {
+        let mut spent = ManaPool::new();
+        for (_, payment) in &self.symbols {
+            if let SymbolPayment::Mana(pool) = payment {
+                for (unit, amount) in pool.units() {
+                    spent.add(unit, amount);
+                }
+            }
+        }
+        spent
+    }
+
+    /// The total life paid instead of mana.
+    #[must_use]
+    pub fn life(&self) -> usize {
+        self.symbols
+            .iter()
+            .map(|(_, payment)| match payment {
+                SymbolPayment::Life(life) => *life,
+                SymbolPayment::Mana(_) => 0,
+            })
+            .sum()
+    }
+
+    /// The colors of the mana spent, e.g. for
+    /// [converge](https://mtg.wiki/page/Converge).
+    #[must_use]
+    pub fn colors_spent(&self) -> ColorSet {
+        self.spent().units().filter_map(|(unit, _)| unit.color).collect()
+    }
+}
+
+/// A set of units, as a bitset indexed by `ManaUnit::index`.
+type UnitSet = u16;
+
+const ALL_UNITS: UnitSet = 0b1111_1111_1111;
+
+/// The units produced by snow sources.
+const SNOW_UNITS: UnitSet = 0b1010_1010_1010;
+
+/// The units of `color`, whether they are snow or not.
+const fn units_of(color: Option<Color>) -> UnitSet {
+    0b11 << ManaUnit { color, snow: false }.index()
+}
+
+/// What is needed from a pool to pay for a mana symbol.
+#[derive(Clone, Copy)]
+enum Requirement {
+    /// One unit in the set.
+    Pip(UnitSet),
+    /// One unit in the set, or life, e.g. `{W/P}`.
+    PipOrLife(UnitSet),
+    /// An amount of any units.
+    Generic(usize),
+    /// Either one unit in the set, or an amount of any units, e.g. `{2/W}`.
+    PipOrGeneric(UnitSet, usize),
+    /// The symbol can never be paid using a pool.
+    Impossible,
+}
+
+impl Requirement {
+    const fn of(mana: &Mana) -> Self {
+        match mana {
+            Mana::Single(SingleMana::Normal(color) | SingleMana::Half(color)) => {
+                Self::Pip(units_of(Some(*color)))
+            }
+            Mana::Single(SingleMana::Phyrexian(color)) => Self::PipOrLife(units_of(Some(*color))),
+            Mana::Generic(GenericMana::Number(n)) => Self::Generic(*n),
+            // Variables are chosen to be 0
+            Mana::Generic(GenericMana::X | GenericMana::Y | GenericMana::Z) => Self::Generic(0),
+            Mana::Split(SplitMana::Mono { value, color }) => {
+                Self::PipOrGeneric(units_of(Some(*color)), *value)
+            }
+            Mana::Split(SplitMana::Colorless { color }) => {
+                Self::Pip(units_of(Some(*color)) | units_of(None))
+            }
+            Mana::Split(SplitMana::Duo { a, b, phyrexian }) => {
+                let units = units_of(Some(*a)) | units_of(Some(*b));
+                if *phyrexian { Self::PipOrLife(units) } else { Self::Pip(units) }
+            }
+            Mana::Colorless => Self::Pip(units_of(None)),
+            Mana::Snow => Self::Pip(SNOW_UNITS),
+            Mana::Generic(GenericMana::Infinity) | Mana::Custom(_) => Self::Impossible,
+        }
+    }
+}
+
+/// A criterion for choosing the best way of paying for a cost, see
+/// [`ManaPool::solve_payment_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentObjective {
+    /// Pay as little life as possible, by paying for Phyrexian symbols using
+    /// mana.
+    MinimizeLife,
+    /// Spend as little mana as possible, e.g. by paying for `{2/W}` using
+    /// `{W}` or for Phyrexian symbols using life.
+    MinimizeMana,
+    /// Spend as little colored mana as possible, by spending colorless mana
+    /// instead.
+    MinimizeColored,
+    /// Spend as little mana of these colors as possible, to keep it for later.
+    Preserve(ColorSet),
+    /// Spend as little mana from snow sources as possible, to keep it for
+    /// later.
+    PreserveSnow,
+}
+
+impl PaymentObjective {
+    /// The cost of spending `unit` according to this objective.
+    fn unit_cost(self, unit: ManaUnit) -> i64 {
+        let spent = match self {
+            Self::MinimizeLife => false,
+            Self::MinimizeMana => true,
+            Self::MinimizeColored => unit.color.is_some(),
+            Self::Preserve(colors) => unit.color.is_some_and(|color| colors.contains(color)),
+            Self::PreserveSnow => unit.snow,
+        };
+        i64::from(spent)
+    }
+
+    /// The cost of paying `life` according to this objective.
+    fn life_cost(self, life: usize) -> i64 {
+        match self {
+            Self::MinimizeLife => i64::try_from(life).unwrap_or(i64::MAX),
+            Self::MinimizeMana | Self::MinimizeColored | Self::Preserve(_) | Self::PreserveSnow => {
+                0
+            }
+        }
+    }
+}
+
+/// The cost of a payment, which is the cost of each objective, compared
+/// lexicographically.
+type Cost = Vec<i64>;
+
+#[derive(Debug)]
+struct Edge {
+    to: usize,
+    capacity: usize,
+    cost: Cost,
+}
+
+/// A flow network, where every edge is stored next to its residual edge, so
+/// the residual edge of `e` is `e ^ 1`.
+struct Network {
+    nodes: usize,
+    edges: Vec<Edge>,
+}
+
+impl Network {
+    const fn new(nodes: usize) -> Self {
+        Self { nodes, edges: Vec::new() }
+    }
+
+    /// Add an edge, returning its index.
+    fn add_edge(&mut self, from: usize, to: usize, capacity: usize, cost: Cost) -> usize {
+        let residual = cost.iter().map(|c| -c).collect();
+        let e = self.edges.len();
+        self.edges.push(Edge { to, capacity, cost });
+        self.edges.push(Edge { to: from, capacity: 0, cost: residual });
+        e
+    }
+
+    /// The flow through edge `e`.
+    fn flow(&self, e: usize) -> usize {
+        self.edges[e ^ 1].capacity
+    }
+
+    /// Send as much flow as possible from `source` to `sink`, with minimal
+    /// cost, by repeatedly sending flow along the cheapest path. Returns the
+    /// amount of flow sent and its cost.
+    fn min_cost_flow(&mut self, source: usize, sink: usize, zero: &Cost) -> (usize, Cost) {
+        let mut total_flow: usize = 0;
+        let mut total_cost = zero.clone();
+        loop {
+            // Bellman-Ford, as residual edges have negative costs
+            let mut distance: Vec<Option<Cost>> = vec![None; self.nodes];
+            let mut previous = vec![None; self.nodes];
+            distance[source] = Some(zero.clone());
+            let mut changed = true;
+            while changed {
+                changed = false;
+                for (e, edge) in self.edges.iter().enumerate() {
+                    let from = self.edges[e ^ 1].to;
+                    let Some(d) = &distance[from] else { continue };
+                    if edge.capacity == 0 {
+                        continue;
+                    }
+                    let candidate: Cost =
+                        d.iter().zip(&edge.cost).map(|(a, b)| a.saturating_add(*b)).collect();
+                    if distance[edge.to].as_ref().is_none_or(|old| candidate < *old) {
+                        distance[edge.to] = Some(candidate);
+                        previous[edge.to] = Some(e);
+                        changed = true;
+                    }
+                }
+            }
+            let Some(path_cost) = distance[sink].take() else {
+                return (total_flow, total_cost);
+            };
+
+            let mut path = Vec::new();
+            let mut node = sink;
+            while let Some(e) = previous[node] {
+                path.push(e);
+                node = self.edges[e ^ 1].to;
+            }
+            let amount = path.iter().map(|&e| self.edges[e].capacity).min().unwrap_or(0);
+            for &e in &path {
+                self.edges[e].capacity -= amount;
+                self.edges[e ^ 1].capacity += amount;
+            }
+            total_flow += amount;
+            let amount = i64::try_from(amount).unwrap_or(i64::MAX);
+            for (total, c) in total_cost.iter_mut().zip(path_cost) {
+                *total = total.saturating_add(c.saturating_mul(amount));
+            }
+        }
+    }
+}
+
+const SOURCE: usize = 0;
+const SINK: usize = 1;
+const FIRST_UNIT: usize = 2;
+const FIRST_SYMBOL: usize = FIRST_UNIT + 12;
+
+/// Pay for `cost`, where `as_pip[i]` decides how symbol `i` is paid if it is
+/// like `{2/W}`. Returns the cheapest payment, together with its cost.
+fn solve_fixed(
+    pool: &ManaPool,
+    cost: &Manas,
+    objectives: &[PaymentObjective],
+    requirements: &[Requirement],
+    as_pip: &[bool],
+) -> Option<(Cost, Payment)> {
+    let zero: Cost = vec![0; objectives.len()];
+    let mut network = Network::new(FIRST_SYMBOL + requirements.len());
+    for i in 0..12 {
+        let unit = ManaUnit::from_index(i);
+        let cost = objectives.iter().map(|o| o.unit_cost(unit)).collect();
+        network.add_edge(FIRST_UNIT + i, SINK, pool.amount(unit), cost);
+    }
+
+    let mut demand: usize = 0;
+    // The edges from each symbol to the units, and to the sink for life
+    let mut unit_edges = vec![Vec::new(); requirements.len()];
+    let mut life_edges = vec![None; requirements.len()];
+    for (i, requirement) in requirements.iter().enumerate() {
+        let (units, amount) = match *requirement {
+            Requirement::Pip(units) | Requirement::PipOrLife(units) => (units, 1),
+            Requirement::Generic(n) => (ALL_UNITS, n),
+            Requirement::PipOrGeneric(units, n) => {
+                if as_pip[i] {
+                    (units, 1)
+                } else {
+                    (ALL_UNITS, n)
+                }
+            }
+            Requirement::Impossible => continue,
+        };
+        if amount == 0 {
+            continue;
+        }
+        let node = FIRST_SYMBOL + i;
+        network.add_edge(SOURCE, node, amount, zero.clone());
+        for unit in (0..12).filter(|unit| units & (1 << unit) != 0) {
+            let e = network.add_edge(node, FIRST_UNIT + unit, amount, zero.clone());
+            unit_edges[i].push((unit, e));
+        }
+        if let Requirement::PipOrLife(_) = requirement {
+            let cost = objectives.iter().map(|o| o.life_cost(PHYREXIAN_LIFE)).collect();
+            life_edges[i] = Some(network.add_edge(node, SINK, 1, cost));
+        }
+        demand = demand.saturating_add(amount);
+    }
+    let (flow, total_cost) = network.min_cost_flow(SOURCE, SINK, &zero);
+    if flow < demand {
+        return None;
+    }
+
+    let symbols = cost
+        .manas
+        .iter()
+        .enumerate()
+        .map(|(i, mana)| {
+            let payment = if life_edges[i].is_some_and(|e| network.flow(e) != 0) {
+                SymbolPayment::Life(PHYREXIAN_LIFE)
+            } else {
+                let mut paid = ManaPool::new();
+                for &(unit, e) in &unit_edges[i] {
+                    paid.add(ManaUnit::from_index(unit), network.flow(e));
+                }
+                SymbolPayment::Mana(paid)
+            };
+            (*mana, payment)
+        })
+        .collect();
+    Some((total_cost, Payment { symbols }))
+}
+
+pub(crate) fn solve(
+    pool: &ManaPool,
+    cost: &Manas,
+    objectives: &[PaymentObjective],
+) -> Option<Payment> {
+    let requirements: Vec<Requirement> = cost.manas.iter().map(Requirement::of).collect();
+    if requirements.iter().any(|r| matches!(r, Requirement::Impossible)) {
+        return None;
+    }
+
+    // The `{2/W}`-like symbols, grouped by the units which can pay for them.
+    // It is never worse to pay for the largest generic amounts using a unit,
+    // so they are sorted by their generic amount, in descending order.
+    let mut choices: Vec<(UnitSet, Vec<(usize, usize)>)> = Vec::new();
+    for (i, requirement) in requirements.iter().enumerate() {
+        if let Requirement::PipOrGeneric(units, n) = *requirement {
+            match choices.iter_mut().find(|(u, _)| *u == units) {
+                Some((_, symbols)) => symbols.push((n, i)),
+                None => choices.push((units, vec![(n, i)])),
+            }
+        }
+    }
+    for (_, symbols) in &mut choices {
+        symbols.sort_by_key(|&(n, _)| Reverse(n));
+    }
+
+    let mut best: Option<(Cost, Payment)> = None;
+    // How many symbols of each group are paid using a unit
+    let mut taken = vec![0; choices.len()];
+    loop {
+        let mut as_pip = vec![false; requirements.len()];
+        for ((_, symbols), &taken) in choices.iter().zip(&taken) {
+            for &(_, i) in &symbols[..taken] {
+                as_pip[i] = true;
+            }
+        }
+        if let Some((cost, payment)) = solve_fixed(pool, cost, objectives, &requirements, &as_pip)
+            && best.as_ref().is_none_or(|(best, _)| cost < *best)
+        {
+            best = Some((cost, payment));
+        }
+
+        let mut i = 0;
+        loop {
+            if i == choices.len() {
+                return best.map(|(_, payment)| payment);
+            }
+            if taken[i] < choices[i].1.len() {
+                taken[i] += 1;
+                break;
+            }
+            taken[i] = 0;
+            i += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(units: &[(Option<Color>, bool, usize)]) -> ManaPool {
+        let mut pool = ManaPool::new();
+        for &(color, snow, amount) in units {
+            pool.add(ManaUnit { color, snow }, amount);
+        }
+        pool
+    }
+
+    fn solve(pool: &ManaPool, cost: &str) -> Option<Payment> {
+        pool.solve_payment(&cost.parse().unwrap())
+    }
+
+    #[test]
+    fn generic_uses_leftovers() {
+        let pool = pool(&[(Some(Color::Red), false, 2), (None, true, 1)]);
+        let payment = solve(&pool, "{1}{R}{S}").unwrap();
+        assert_eq!(payment.spent(), pool);
+        let [(_, generic), (_, red), (_, snow)] = payment.symbols() else { panic!() };
+        assert_eq!(
+            generic,
+            &SymbolPayment::Mana([ManaUnit::colored(Color::Red)].into_iter().collect())
+        );
+        assert_eq!(
+            red,
+            &SymbolPayment::Mana([ManaUnit::colored(Color::Red)].into_iter().collect())
+        );
+        assert_eq!(
+            snow,
+            &SymbolPayment::Mana([ManaUnit { color: None, snow: true }].into_iter().collect())
+        );
+        assert!(payment.colors_spent().contains(Color::Red));
+        assert_eq!(payment.colors_spent().len(), 1);
+    }
+
+    #[test]
+    fn phyrexian() {
+        let pool = pool(&[(Some(Color::Black), false, 1)]);
+        let payment = solve(&pool, "{B/P}{B/P}").unwrap();
+        assert_eq!(payment.life(), 2);
+        assert_eq!(payment.spent().total(), 1);
+
+        let payment = solve(&pool, "{1}{B/P}").unwrap();
+        assert_eq!(payment.life(), 2);
+        assert_eq!(payment.symbols()[1].1, SymbolPayment::Life(2));
+    }
+
+    #[test]
+    fn mono_hybrid() {
+        let pool = pool(&[(Some(Color::White), false, 3)]);
+        let payment = solve(&pool, "{2/W}{2/W}{X}").unwrap();
+        assert_eq!(payment.spent().total(), 2);
+        assert_eq!(payment.symbols()[2].1, SymbolPayment::Mana(ManaPool::new()));
+
+        let pool = self::pool(&[(Some(Color::White), false, 1), (None, false, 2)]);
+        let payment = solve(&pool, "{2/W}{W}").unwrap();
+        let [(_, hybrid), _] = payment.symbols() else { panic!() };
+        assert_eq!(hybrid, &SymbolPayment::Mana([ManaUnit::COLORLESS; 2].into_iter().collect()));
+    }
+
+    #[test]
+    fn objectives() {
+        let pool = pool(&[
+            (Some(Color::Green), false, 1),
+            (Some(Color::Green), true, 1),
+            (Some(Color::Red), false, 2),
+            (None, false, 1),
+        ]);
+        let cost: Manas = "{2}{R/G}{G/P}".parse().unwrap();
+        let solve =
+            |objectives: &[PaymentObjective]| pool.solve_payment_with(&cost, objectives).unwrap();
+
+        let green = [Color::Green].into_iter().collect();
+        let payment = solve(&[PaymentObjective::Preserve(green), PaymentObjective::MinimizeLife]);
+        assert_eq!(payment.spent().to_string(), "2 {R}, 1 {C}");
+        assert_eq!(payment.life(), 2);
+
+        let payment = solve(&[PaymentObjective::MinimizeLife, PaymentObjective::PreserveSnow]);
+        assert_eq!(payment.spent().to_string(), "2 {R}, 1 {G}, 1 {C}");
+
+        let payment = solve(&[PaymentObjective::MinimizeLife, PaymentObjective::MinimizeColored]);
+        assert_eq!(payment.spent().amount_of(None), 1);
+        assert_eq!(payment.spent().total(), 4);
+
+        let payment = solve(&[PaymentObjective::MinimizeMana]);
+        assert_eq!(payment.spent().total(), 3);
+        assert_eq!(payment.life(), 2);
+
+        // Without objectives, any payment can be chosen
+        assert!(pool.solve_payment_with(&cost, &[]).is_some());
+    }
+
+    #[test]
+    fn impossible() {
+        let pool = pool(&[(Some(Color::Green), false, 100)]);
+        assert_eq!(solve(&pool, "{∞}"), None);
+        assert_eq!(solve(&pool, "{101}"), None);
+        assert_eq!(solve(&pool, "{G/U}{U}"), None);
+        assert!(solve(&pool, "{100}").is_some());
+    }
+}
@@ -0,0 +1,451 @@
+use std::io::Write;
+
+use flate2::{Compression, write::ZlibEncoder};
+use pdf_writer::{
+    Chunk, Content, Filter, Finish, Name, Pdf, Rect, Ref,
+    types::{LineCapStyle, LineJoinStyle, MaskType},
+    writers::{FormXObject, Resources},
+};
+use resvg::{
+    tiny_skia,
+    usvg::{self, Node, NonZeroRect, Paint, Transform, tiny_skia_path::PathSegment},
+};
+use svg::node::element::SVG;
+
+use crate::svg_tree::svg_to_tree;
+
+const MM_PER_INCH: f32 = 25.4;
+const PT_PER_INCH: f32 = 72.0;
+
+/// How many pixels are used for each SVG unit when drawing the parts which
+/// PDF can't express as vector graphics, like drop shadows and gradients.
+const RASTER_SCALE: f32 = 8.0;
+
+/// Convert `svg` to a PDF with a single page, which is exactly `height_mm`
+/// millimeters high. Returns `None` if `height_mm` is not positive.
+pub(crate) fn svg_to_pdf(svg: &SVG, height_mm: f32) -> Option<Vec<u8>> {
+    // Also rejects NaN
+    (height_mm > 0.0).then_some(())?;
+    let tree = svg_to_tree(svg)?;
+    let size = tree.size();
+    let height = height_mm / MM_PER_INCH * PT_PER_INCH;
+    let width = height * size.width() / size.height();
+
+    let mut next = Ref::new(1);
+    let catalog_id = next.bump();
+    let pages_id = next.bump();
+    let page_id = next.bump();
+    let content_id = next.bump();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(pages_id);
+    pdf.pages(pages_id).kids([page_id]).count(1);
+
+    // PDF has the y axis pointing up, unlike SVG
+    let scale = height / size.height();
+    let root = Transform::from_row(scale, 0.0, 0.0, -scale, 0.0, height);
+    let (canvas, _) = Writer { chunk: &mut pdf, next, alphas: Vec::new() }.root(&tree, root);
+
+    let content = canvas.content.finish();
+    let mut page = pdf.page(page_id);
+    page.media_box(Rect::new(0.0, 0.0, width, height)).parent(pages_id).contents(content_id);
+    canvas.resources.write(page.resources());
+    page.finish();
+    pdf.stream(content_id, &content);
+    Some(pdf.finish())
+}
+
+/// Write `svg` to `chunk` as a form XObject with the ID `id`, scaled to fit a
+/// unit square. Returns the next unused ID and the aspect ratio (width divided
+/// by height) of `svg`.
+pub(crate) fn svg_to_pdf_xobject(svg: &SVG, chunk: &mut Chunk, id: Ref) -> Option<(Ref, f32)> {
+    let tree = svg_to_tree(svg)?;
+    let size = tree.size();
+
+    let mut next = id;
+    next.bump();
+    let root = Transform::from_row(1.0 / size.width(), 0.0, 0.0, -1.0 / size.height(), 0.0, 1.0);
+    let (canvas, next) = Writer { chunk, next, alphas: Vec::new() }.root(&tree, root);
+    canvas.write_form(chunk, id, Rect::new(0.0, 0.0, 1.0, 1.0));
+    Some((next, size.width() / size.height()))
+}
+
+/// A content stream together with the resources it uses.
+struct Canvas {
+    content: Content,
+    resources: ResourceList,
+}
+
+impl Canvas {
+    fn new() -> Self {
+        Self { content: Content::new(), resources: ResourceList::default() }
+    }
+
+    fn x_object(&mut self, id: Ref) {
+        let name = format!("X{}", self.resources.x_objects.len());
+        self.resources.x_objects.push(id);
+        self.content.x_object(Name(name.as_bytes()));
+    }
+
+    fn ext_g_state(&mut self, id: Ref) {
+        let name = format!("G{}", self.resources.ext_g_states.len());
+        self.resources.ext_g_states.push(id);
+        self.content.set_parameters(Name(name.as_bytes()));
+    }
+
+    /// Write the canvas to `chunk` as a transparency group, which is what
+    /// opacity and soft masks are applied to.
+    fn write_form(self, chunk: &mut Chunk, id: Ref, bbox: Rect) {
+        let content = self.content.finish();
+        let mut form: FormXObject = chunk.form_xobject(id, &content);
+        form.bbox(bbox);
+        form.group().transparency().color_space().device_rgb();
+        self.resources.write(form.resources());
+    }
+}
+
+#[derive(Default)]
+struct ResourceList {
+    x_objects: Vec<Ref>,
+    ext_g_states: Vec<Ref>,
+}
+
+impl ResourceList {
+    fn write(&self, mut resources: Resources) {
+        let mut x_objects = resources.x_objects();
+        for (i, &id) in self.x_objects.iter().enumerate() {
+            x_objects.pair(Name(format!("X{i}").as_bytes()), id);
+        }
+        x_objects.finish();
+        let mut ext_g_states = resources.ext_g_states();
+        for (i, &id) in self.ext_g_states.iter().enumerate() {
+            ext_g_states.pair(Name(format!("G{i}").as_bytes()), id);
+        }
+    }
+}
+
+/// Converts a [`usvg::Tree`] to PDF objects. Everything is drawn as vector
+/// graphics, except for filters, gradients, patterns and images, which are
+/// rasterized using [`resvg`].
+struct Writer<'a> {
+    chunk: &'a mut Chunk,
+    next: Ref,
+    /// The graphics states which set the opacity, so that they can be reused.
+    alphas: Vec<(f32, Ref)>,
+}
+
+impl Writer<'_> {
+    /// Draw `tree` with the transformation `root`, returning the canvas and
+    /// the next unused ID.
+    fn root(mut self, tree: &usvg::Tree, root: Transform) -> (Canvas, Ref) {
+        let mut canvas = Canvas::new();
+        canvas.content.transform(matrix(root));
+        self.group(&mut canvas, tree.root());
+        (canvas, self.next)
+    }
+
+    fn alloc(&mut self) -> Ref {
+        self.next.bump()
+    }
+
+    fn node(&mut self, canvas: &mut Canvas, node: &Node) {
+        match node {
+            Node::Group(group) if is_vector_group(group) => self.group(canvas, group),
+            Node::Path(path) if is_vector_path(path) => self.path(canvas, path),
+            Node::Text(text) => self.group(canvas, text.flattened()),
+            _ => self.raster(canvas, node),
+        }
+    }
+
+    fn group(&mut self, canvas: &mut Canvas, group: &usvg::Group) {
+        canvas.content.save_state();
+        if !group.transform().is_identity() {
+            canvas.content.transform(matrix(group.transform()));
+        }
+        if let Some((path, transform)) = group.clip_path().and_then(clip_path) {
+            write_path(&mut canvas.content, path.data(), transform);
+            match path.fill().map(usvg::Fill::rule) {
+                Some(usvg::FillRule::EvenOdd) => canvas.content.clip_even_odd(),
+                _ => canvas.content.clip_nonzero(),
+            };
+            canvas.content.end_path();
+        }
+        if let Some(mask) = group.mask() {
+            let id = self.mask(mask);
+            canvas.ext_g_state(id);
+        }
+
+        let opacity = group.opacity().get();
+        if opacity < 1.0 || group.mask().is_some() {
+            // The opacity and mask apply to the group as a whole, not to each
+            // child
+            let mut inner = Canvas::new();
+            for child in group.children() {
+                self.node(&mut inner, child);
+            }
+            let id = self.alloc();
+            inner.write_form(self.chunk, id, rect(group.layer_bounding_box()));
+            self.set_alpha(canvas, opacity);
+            canvas.x_object(id);
+        } else {
+            for child in group.children() {
+                self.node(canvas, child);
+            }
+        }
+        canvas.content.restore_state();
+    }
+
+    /// Write `mask` as a graphics state with a soft mask.
+    fn mask(&mut self, mask: &usvg::Mask) -> Ref {
+        let mut inner = Canvas::new();
+        let area = mask.rect();
+        inner.content.rect(area.x(), area.y(), area.width(), area.height());
+        inner.content.clip_nonzero().end_path();
+        for child in mask.root().children() {
+            self.node(&mut inner, child);
+        }
+        let group_id = self.alloc();
+        inner.write_form(self.chunk, group_id, rect(area));
+
+        let id = self.alloc();
+        let kind = match mask.kind() {
+            usvg::MaskType::Luminance => MaskType::Luminosity,
+            usvg::MaskType::Alpha => MaskType::Alpha,
+        };
+        self.chunk.ext_graphics(id).soft_mask().subtype(kind).group(group_id);
+        id
+    }
+
+    fn path(&mut self, canvas: &mut Canvas, path: &usvg::Path) {
+        if !path.is_visible() {
+            return;
+        }
+        match path.paint_order() {
+            usvg::PaintOrder::FillAndStroke => {
+                self.fill(canvas, path);
+                self.stroke(canvas, path);
+            }
+            usvg::PaintOrder::StrokeAndFill => {
+                self.stroke(canvas, path);
+                self.fill(canvas, path);
+            }
+        }
+    }
+
+    fn fill(&mut self, canvas: &mut Canvas, path: &usvg::Path) {
+        let Some(fill) = path.fill() else { return };
+        let Paint::Color(color) = fill.paint() else { return };
+        canvas.content.save_state();
+        self.set_alpha(canvas, fill.opacity().get());
+        let [r, g, b] = rgb(*color);
+        canvas.content.set_fill_rgb(r, g, b);
+        write_path(&mut canvas.content, path.data(), Transform::identity());
+        match fill.rule() {
+            usvg::FillRule::NonZero => canvas.content.fill_nonzero(),
+            usvg::FillRule::EvenOdd => canvas.content.fill_even_odd(),
+        };
+        canvas.content.restore_state();
+    }
+
+    fn stroke(&mut self, canvas: &mut Canvas, path: &usvg::Path) {
+        let Some(stroke) = path.stroke() else { return };
+        let Paint::Color(color) = stroke.paint() else { return };
+        canvas.content.save_state();
+        self.set_alpha(canvas, stroke.opacity().get());
+        let [r, g, b] = rgb(*color);
+        canvas.content.set_stroke_rgb(r, g, b);
+        canvas.content.set_line_width(stroke.width().get());
+        canvas.content.set_line_cap(match stroke.linecap() {
+            usvg::LineCap::Butt => LineCapStyle::ButtCap,
+            usvg::LineCap::Round => LineCapStyle::RoundCap,
+            usvg::LineCap::Square => LineCapStyle::ProjectingSquareCap,
+        });
+        canvas.content.set_line_join(match stroke.linejoin() {
+            usvg::LineJoin::Miter | usvg::LineJoin::MiterClip => LineJoinStyle::MiterJoin,
+            usvg::LineJoin::Round => LineJoinStyle::RoundJoin,
+            usvg::LineJoin::Bevel => LineJoinStyle::BevelJoin,
+        });
+        canvas.content.set_miter_limit(stroke.miterlimit().get());
+        if let Some(dashes) = stroke.dasharray() {
+            canvas.content.set_dash_pattern(dashes.iter().copied(), stroke.dashoffset());
+        }
+        write_path(&mut canvas.content, path.data(), Transform::identity());
+        canvas.content.stroke();
+        canvas.content.restore_state();
+    }
+
+    fn set_alpha(&mut self, canvas: &mut Canvas, alpha: f32) {
+        if alpha < 1.0 {
+            let id = self.alpha(alpha);
+            canvas.ext_g_state(id);
+        }
+    }
+
+    /// A graphics state which sets the opacity to `alpha`.
+    fn alpha(&mut self, alpha: f32) -> Ref {
+        if let Some(&(_, id)) = self.alphas.iter().find(|(a, _)| *a == alpha) {
+            return id;
+        }
+        let id = self.alloc();
+        self.chunk.ext_graphics(id).non_stroking_alpha(alpha).stroking_alpha(alpha);
+        self.alphas.push((alpha, id));
+        id
+    }
+
+    /// Draw `node` as an image, using the same coordinates as its parent.
+    fn raster(&mut self, canvas: &mut Canvas, node: &Node) {
+        let area = match node {
+            Node::Group(group) => group.layer_bounding_box().transform(group.transform()),
+            Node::Path(path) => path.stroke_bounding_box().to_non_zero_rect(),
+            Node::Image(image) => image.bounding_box().to_non_zero_rect(),
+            Node::Text(text) => text.stroke_bounding_box().to_non_zero_rect(),
+        };
+        let (Some(area), Some(abs_area)) = (area, node.abs_layer_bounding_box()) else {
+            return;
+        };
+
+        // Leave room for anti-aliasing
+        let padding = 2.0 / RASTER_SCALE;
+        let (x, y) = (area.x() - padding, area.y() - padding);
+        let width = ((area.width() + 2.0 * padding) * RASTER_SCALE).ceil() as u32;
+        let height = ((area.height() + 2.0 * padding) * RASTER_SCALE).ceil() as u32;
+        let Some(mut pixmap) = tiny_skia::Pixmap::new(width, height) else { return };
+
+        // `render_node` places the absolute bounding box of `node` at the
+        // origin, which we undo
+        let transform = Transform::from_scale(RASTER_SCALE, RASTER_SCALE)
+            .pre_translate(-x, -y)
+            .pre_translate(abs_area.x(), abs_area.y());
+        resvg::render_node(node, transform, &mut pixmap.as_mut());
+
+        let id = self.image(&pixmap);
+        let (width, height) = (width as f32 / RASTER_SCALE, height as f32 / RASTER_SCALE);
+        canvas.content.save_state();
+        // Images are drawn in a unit square, with the first row at the top
+        canvas.content.transform([width, 0.0, 0.0, -height, x, y + height]);
+        canvas.x_object(id);
+        canvas.content.restore_state();
+    }
+
+    /// Write `pixmap` as an image XObject, with its alpha channel as a soft
+    /// mask.
+    fn image(&mut self, pixmap: &tiny_skia::Pixmap) -> Ref {
+        let mut colors = Vec::with_capacity(pixmap.pixels().len() * 3);
+        let mut alphas = Vec::with_capacity(pixmap.pixels().len());
+        for pixel in pixmap.pixels() {
+            // The pixels of `pixmap` are premultiplied by alpha, but not in PDF
+            let pixel = pixel.demultiply();
+            colors.extend([pixel.red(), pixel.green(), pixel.blue()]);
+            alphas.push(pixel.alpha());
+        }
+
+        let id = self.alloc();
+        let mask_id = self.alloc();
+        let (width, height) = (pixmap.width() as i32, pixmap.height() as i32);
+        let colors = deflate(&colors);
+        let mut image = self.chunk.image_xobject(id, &colors);
+        image.width(width).height(height).bits_per_component(8);
+        image.filter(Filter::FlateDecode);
+        image.color_space().device_rgb();
+        image.s_mask(mask_id);
+        image.finish();
+        let alphas = deflate(&alphas);
+        let mut mask = self.chunk.image_xobject(mask_id, &alphas);
+        mask.width(width).height(height).bits_per_component(8);
+        mask.filter(Filter::FlateDecode);
+        mask.color_space().device_gray();
+        id
+    }
+}
+
+/// Groups with filters, nested masks, blend modes or complex clip paths can't
+/// be drawn as vector graphics.
+fn is_vector_group(group: &usvg::Group) -> bool {
+    group.filters().is_empty()
+        && group.blend_mode() == usvg::BlendMode::Normal
+        && group.mask().is_none_or(|mask| mask.mask().is_none())
+        && group.clip_path().is_none_or(|clip| clip_path(clip).is_some())
+}
+
+/// Paths painted with gradients or patterns can't be drawn as vector graphics.
+fn is_vector_path(path: &usvg::Path) -> bool {
+    path.fill().is_none_or(|fill| matches!(fill.paint(), Paint::Color(_)))
+        && path.stroke().is_none_or(|stroke| matches!(stroke.paint(), Paint::Color(_)))
+}
+
+/// The path of `clip` and its transformation, if it consists of a single path.
+fn clip_path(clip: &usvg::ClipPath) -> Option<(&usvg::Path, Transform)> {
+    if clip.clip_path().is_some() {
+        return None;
+    }
+    let mut transform = clip.transform();
+    let mut group = clip.root();
+    loop {
+        match group.children() {
+            [Node::Path(path)] => return Some((path, transform)),
+            [Node::Group(inner)] if inner.should_isolate() => return None,
+            [Node::Group(inner)] => {
+                transform = transform.pre_concat(inner.transform());
+                group = inner;
+            }
+            _ => return None,
+        }
+    }
+}
+
+fn write_path(content: &mut Content, path: &tiny_skia::Path, transform: Transform) {
+    let path = path.clone().transform(transform);
+    let Some(path) = path else { return };
+
+    let mut start = tiny_skia::Point::zero();
+    let mut last = start;
+    for segment in path.segments() {
+        match segment {
+            PathSegment::MoveTo(p) => {
+                content.move_to(p.x, p.y);
+                start = p;
+                last = p;
+            }
+            PathSegment::LineTo(p) => {
+                content.line_to(p.x, p.y);
+                last = p;
+            }
+            PathSegment::QuadTo(p1, p) => {
+                // PDF only has cubic Bézier curves
+                let (x1, y1) =
+                    (last.x + (p1.x - last.x) * 2.0 / 3.0, last.y + (p1.y - last.y) * 2.0 / 3.0);
+                let (x2, y2) = (p.x + (p1.x - p.x) * 2.0 / 3.0, p.y + (p1.y - p.y) * 2.0 / 3.0);
+                content.cubic_to(x1, y1, x2, y2, p.x, p.y);
+                last = p;
+            }
+            PathSegment::CubicTo(p1, p2, p) => {
+                content.cubic_to(p1.x, p1.y, p2.x, p2.y, p.x, p.y);
+                last = p;
+            }
+            PathSegment::Close => {
+                content.close_path();
+                last = start;
+            }
+        }
+    }
+}
+
+fn deflate(bytes: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+    // Writing to a `Vec` can't fail
+    encoder.write_all(bytes).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn matrix(transform: Transform) -> [f32; 6] {
+    let Transform { sx, ky, kx, sy, tx, ty } = transform;
+    [sx, ky, kx, sy, tx, ty]
+}
+
+fn rect(rect: NonZeroRect) -> Rect {
+    Rect::new(rect.left(), rect.top(), rect.right(), rect.bottom())
+}
+
+fn rgb(color: usvg::Color) -> [f32; 3] {
+    [color.red, color.green, color.blue].map(|x| f32::from(x) / 255.0)
+}
@@ -0,0 +1,24 @@
+/// Which mana symbols are counted as colored pips.
+///
+/// Used by [`Manas::pip_count`][crate::Manas::pip_count]. Colored mana, e.g.
+/// `{U}`, is always counted.
+///
+/// For default options, which counts every symbol with a color, use
+/// [`PipPolicy::default`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PipPolicy {
+    /// Whether hybrid mana symbols are counted, once for each of their colors.
+    /// This includes hybrid generic mana (e.g. `{2/W}`) and hybrid colorless
+    /// mana (e.g. `{C/W}`).
+    pub hybrid: bool,
+
+    /// Whether phyrexian mana symbols are counted. Phyrexian hybrid mana is
+    /// only counted if both `hybrid` and `phyrexian` are set.
+    pub phyrexian: bool,
+}
+
+impl Default for PipPolicy {
+    fn default() -> Self {
+        Self { hybrid: true, phyrexian: true }
+    }
+}
@@ -0,0 +1,84 @@
+use resvg::tiny_skia;
+use svg::node::element::SVG;
+
+use crate::svg_tree::svg_to_tree;
+
+/// Render `svg` as a PNG image which is `height` pixels high. Returns `None`
+/// if the image would be empty.
+#[cfg(feature = "png")]
+pub(crate) fn svg_to_png(svg: &SVG, height: u32) -> Option<Vec<u8>> {
+    let pixmap = svg_to_pixmap(svg, height)?;
+    pixmap.encode_png().ok()
+}
+
+/// Render `svg` as an image which is `height` pixels high. Returns `None` if
+/// the image would be empty.
+#[cfg(feature = "image")]
+pub(crate) fn svg_to_image(svg: &SVG, height: u32) -> Option<image::RgbaImage> {
+    let pixmap = svg_to_pixmap(svg, height)?;
+
+    // The pixels of `pixmap` are premultiplied by alpha, but not in `image`
+    let data = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|x| {
+            let x = x.demultiply();
+            [x.red(), x.green(), x.blue(), x.alpha()]
+        })
+        .collect();
+    image::RgbaImage::from_raw(pixmap.width(), pixmap.height(), data)
+}
+
+/// Store the resolution `dpi` in `png`, as a `pHYs` chunk directly after the
+/// `IHDR` chunk, so that it is printed at the right size.
+#[cfg(feature = "png")]
+pub(crate) fn set_png_dpi(png: &mut Vec<u8>, dpi: u32) {
+    // The signature (8 bytes) and the `IHDR` chunk (25 bytes)
+    const IHDR_END: usize = 33;
+
+    let pixels_per_meter = (f64::from(dpi) / 0.0254).round() as u32;
+    let mut chunk = Vec::with_capacity(21);
+    chunk.extend(9u32.to_be_bytes());
+    chunk.extend(b"pHYs");
+    chunk.extend(pixels_per_meter.to_be_bytes());
+    chunk.extend(pixels_per_meter.to_be_bytes());
+    // The unit is meters
+    chunk.push(1);
+    chunk.extend(crc32(&chunk[4..]).to_be_bytes());
+    png.splice(IHDR_END..IHDR_END, chunk);
+}
+
+/// The CRC used by PNG chunks.
+#[cfg(feature = "png")]
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn svg_to_pixmap(svg: &SVG, height: u32) -> Option<tiny_skia::Pixmap> {
+    let tree = svg_to_tree(svg)?;
+    let size = tree.size();
+    let scale = height as f32 / size.height();
+    let width = (size.width() * scale).round() as u32;
+
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)?;
+    resvg::render(&tree, tiny_skia::Transform::from_scale(scale, scale), &mut pixmap.as_mut());
+    Some(pixmap)
+}
+
+#[cfg(all(test, feature = "png"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc() {
+        // The CRC of the `IEND` chunk, which has no data
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+    }
+}
@@ -0,0 +1,646 @@
+//! Writes the same SVGs as [`Mana::as_svg`] and [`Manas::as_svg`], in the
+//! canonical form of [`write_canonical_svg`], but directly to a [`String`]
+//! instead of building a tree of nodes first.
+//!
+//! Every function here mirrors the function in `mana.rs` which builds the
+//! same elements, so the two have to be changed together. Minified and
+//! compatible SVGs (see [`SVGConfig::compatible`]) are always built as a tree.
+
+use std::{
+    f64,
+    fmt::{self, Display, Write},
+};
+
+use svg::node::element::SVG;
+
+use crate::{
+    DropShadow, Foil, GenericMana, Glyph, Mana, Manas, Plate, SVG_WIDTH, SVGConfig, SingleMana,
+    SplitMana,
+    mana::{circle_mask_id, drop_shadow_id, foil_id},
+    symbols::glyph_paths,
+    symbols::glyph_symbol,
+    write_canonical_svg,
+};
+
+/// An attribute of an element, or `None` if it is left out.
+type Attribute<'a> = Option<(&'static str, &'a dyn Display)>;
+
+const XMLNS: &str = "http://www.w3.org/2000/svg";
+
+/// Where a mana symbol is placed inside the SVG of [`Manas`].
+#[derive(Clone, Copy)]
+struct Placement {
+    x: f64,
+    y: f64,
+    /// The height, which is also the width unless the symbol is half mana.
+    size: f64,
+}
+
+struct ViewBox(f64, f64, f64, f64);
+
+impl Display for ViewBox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.0, self.1, self.2, self.3)
+    }
+}
+
+/// Escapes what it writes, either as text or as an attribute value.
+struct Escaped<'a> {
+    out: &'a mut String,
+    attribute: bool,
+}
+
+impl Write for Escaped<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '&' => self.out.push_str("&amp;"),
+                '<' => self.out.push_str("&lt;"),
+                '>' => self.out.push_str("&gt;"),
+                '"' if self.attribute => self.out.push_str("&quot;"),
+                '\'' if self.attribute => self.out.push_str("&apos;"),
+                c => self.out.push(c),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Write the start of an element, without closing the tag. Attributes are
+/// sorted by name, like in [`write_canonical_svg`].
+fn start_tag(out: &mut String, name: &str, attributes: &mut [Attribute<'_>]) {
+    attributes.sort_unstable_by_key(|attribute| attribute.map(|(name, _)| name));
+    out.push('<');
+    out.push_str(name);
+    for (name, value) in attributes.iter().flatten() {
+        write!(out, r#" {name}=""#).unwrap();
+        write!(Escaped { out, attribute: true }, "{value}").unwrap();
+        out.push('"');
+    }
+}
+
+/// Write an element without children on a new line.
+fn empty_element(out: &mut String, name: &str, attributes: &mut [Attribute<'_>]) {
+    out.push('\n');
+    start_tag(out, name, attributes);
+    out.push_str("/>");
+}
+
+/// Write an element which only contains text on a new line.
+fn text_element(out: &mut String, name: &str, text: impl Display) {
+    write!(out, "\n<{name}>").unwrap();
+    write!(Escaped { out, attribute: false }, "{text}").unwrap();
+    write!(out, "</{name}>").unwrap();
+}
+
+pub(crate) fn render_mana(out: &mut String, mana: &Mana, config: &SVGConfig) {
+    write_mana(out, mana, config, None);
+}
+
+pub(crate) fn render_manas(out: &mut String, manas: &Manas, config: &SVGConfig) {
+    let description = manas;
+    let manas = &manas.manas;
+    let n = manas.len();
+    if n == 0 {
+        start_tag(out, "svg", &mut [Some(("xmlns", &XMLNS))]);
+        out.push_str("/>");
+        return;
+    }
+
+    let margin = config.margin();
+    let width_single = 2.0f64.mul_add(margin, SVG_WIDTH);
+    let step = width_single + config.spacing;
+    let half = width_single / 2.0;
+    let halves = manas.iter().filter(|mana| mana.is_half()).count();
+    let width_total = step.mul_add(n as f64, -config.spacing) - half * halves as f64;
+    let origin = 0.0 - margin;
+
+    let view_box = ViewBox(origin, origin, width_total, width_single);
+    let mut label = String::new();
+    for (i, mana) in manas.iter().enumerate() {
+        if i != 0 {
+            label.push_str(", ");
+        }
+        label.push_str(&mana.name_with(&config.palette));
+    }
+    let size = config.size(width_total / width_single);
+    let width = size.map(|(width, _)| width);
+    let height = size.map(|(_, height)| height);
+    start_tag(
+        out,
+        "svg",
+        &mut [
+            Some(("viewBox", &view_box)),
+            Some(("role", &"img")),
+            Some(("aria-label", &label)),
+            Some(("xmlns", &XMLNS)),
+            width.as_ref().map(|width| ("width", width as &dyn Display)),
+            height.as_ref().map(|height| ("height", height as &dyn Display)),
+            config.shape_rendering.as_ref().map(|value| ("shape-rendering", value as &dyn Display)),
+        ],
+    );
+    out.push('>');
+    text_element(out, "title", &label);
+    text_element(out, "desc", description);
+    if let Some(plate) = &config.palette.plate {
+        write_plate(out, plate, origin, width_total, width_single);
+    }
+
+    let mut halves_before = 0.0;
+    for (i, mana) in manas.iter().enumerate() {
+        let x = step * (i as f64) - margin - half * halves_before;
+        let placement = Placement { x, y: origin, size: width_single };
+        out.push('\n');
+        write_mana(out, mana, config, Some(placement));
+        if mana.is_half() {
+            halves_before += 1.0;
+        }
+    }
+    out.push_str("\n</svg>");
+}
+
+/// Mirrors `with_plate` in `manas.rs`.
+fn write_plate(out: &mut String, plate: &Plate, origin: f64, width: f64, height: f64) {
+    let rx = height / 2.0;
+    empty_element(
+        out,
+        "rect",
+        &mut [
+            Some(("fill", &plate.fill)),
+            Some(("stroke", &"none")),
+            Some(("x", &origin)),
+            Some(("y", &origin)),
+            Some(("width", &width)),
+            Some(("height", &height)),
+            Some(("rx", &rx)),
+        ],
+    );
+}
+
+/// Mirrors [`Mana::as_svg_with_theme`], using [`SVGConfig::palette`] as the
+/// theme.
+fn write_mana(out: &mut String, mana: &Mana, config: &SVGConfig, placement: Option<Placement>) {
+    let margin = config.margin();
+    let width = 2.0f64.mul_add(margin, SVG_WIDTH);
+    let origin = 0.0 - margin;
+    let aspect_ratio = if mana.is_half() { 0.5 } else { 1.0 };
+    let view_box = ViewBox(origin, origin, width * aspect_ratio, width);
+    let label = mana.name_with(&config.palette);
+
+    let size = match placement {
+        Some(placement) => Some((placement.size * aspect_ratio, placement.size)),
+        None => config.size(aspect_ratio),
+    };
+    let width = size.map(|(width, _)| width);
+    let height = size.map(|(_, height)| height);
+    let x = placement.map(|placement| placement.x);
+    let y = placement.map(|placement| placement.y);
+    start_tag(
+        out,
+        "svg",
+        &mut [
+            Some(("viewBox", &view_box)),
+            Some(("role", &"img")),
+            Some(("aria-label", &label)),
+            Some(("xmlns", &XMLNS)),
+            width.as_ref().map(|width| ("width", width as &dyn Display)),
+            height.as_ref().map(|height| ("height", height as &dyn Display)),
+            config.shape_rendering.as_ref().map(|value| ("shape-rendering", value as &dyn Display)),
+            x.as_ref().map(|x| ("x", x as &dyn Display)),
+            y.as_ref().map(|y| ("y", y as &dyn Display)),
+            placement.map(|_| ("aria-hidden", &"true" as &dyn Display)),
+        ],
+    );
+    out.push('>');
+    text_element(out, "title", &label);
+    text_element(out, "desc", format_args!("{{{mana}}}"));
+
+    if config.shadow {
+        match &config.drop_shadow {
+            Some(drop_shadow) => write_drop_shadow(out, drop_shadow),
+            _ => write_shadow(out, config.shadow_offset, &config.shadow_color),
+        }
+    }
+
+    if let Some(artwork) = config.symbol_override.and_then(|f| f(mana)) {
+        write_override(out, artwork);
+        out.push_str("\n</svg>");
+        return;
+    }
+
+    let palette = &config.palette;
+    let colorless = palette.colorless.as_str();
+    match mana {
+        Mana::Single(SingleMana::Normal(color) | SingleMana::Half(color)) => {
+            write_circle(out, palette.color(*color));
+            write_symbol(out, Glyph::Color(*color), 0.8125, config);
+        }
+        Mana::Single(SingleMana::Phyrexian(color)) => {
+            write_circle(out, palette.color(*color));
+            write_symbol(out, Glyph::Phyrexian, 0.8125, config);
+        }
+        Mana::Generic(GenericMana::Number(n)) => {
+            write_circle(out, colorless);
+            write_symbol(out, Glyph::Number(*n), 0.70, config);
+        }
+        Mana::Generic(GenericMana::X) => {
+            write_circle(out, colorless);
+            write_symbol(out, Glyph::Variable('X'), 0.8125, config);
+        }
+        Mana::Generic(GenericMana::Y) => {
+            write_circle(out, colorless);
+            write_symbol(out, Glyph::Variable('Y'), 0.8125, config);
+        }
+        Mana::Generic(GenericMana::Z) => {
+            write_circle(out, colorless);
+            write_symbol(out, Glyph::Variable('Z'), 0.8125, config);
+        }
+        Mana::Generic(GenericMana::Infinity) => {
+            write_circle(out, colorless);
+            write_symbol(out, Glyph::Infinity, 0.8125, config);
+        }
+        Mana::Split(SplitMana::Colorless { color }) => {
+            write_split_circle(out, colorless, palette.color(*color));
+            write_symbols(out, Glyph::Colorless, Glyph::Color(*color), 0.875, config);
+        }
+        Mana::Split(SplitMana::Mono { color, value }) => {
+            write_split_circle(out, colorless, palette.color(*color));
+            write_symbols(out, Glyph::Number(*value), Glyph::Color(*color), 0.875, config);
+        }
+        Mana::Split(SplitMana::Duo { a, b, phyrexian }) => {
+            write_split_circle(out, palette.color(*a), palette.color(*b));
+            if *phyrexian {
+                write_symbols(out, Glyph::Phyrexian, Glyph::Phyrexian, 0.875, config);
+            } else {
+                write_symbols(out, Glyph::Color(*a), Glyph::Color(*b), 0.875, config);
+            }
+        }
+        Mana::Colorless => {
+            write_circle(out, colorless);
+            write_symbol(out, Glyph::Colorless, 0.8125, config);
+        }
+        Mana::Snow => {
+            write_circle(out, colorless);
+            write_symbol(out, Glyph::Snow, 1.0, config);
+        }
+        Mana::Custom(color) => {
+            write_circle(out, palette.custom_color(*color));
+            write_symbol(out, Glyph::Custom(*color), 0.8125, config);
+        }
+    }
+
+    if let Some(foil) = &palette.foil {
+        write_foil(out, foil, config.shimmer);
+    }
+
+    let stroke = match &config.circle_stroke {
+        Some(stroke) => Some((stroke.color.as_str(), stroke.width)),
+        None => palette.outline.as_deref().map(|outline| (outline, 1.0)),
+    };
+    if let Some((stroke, width)) = stroke {
+        write_outline(out, stroke, width, config.non_scaling_stroke);
+    }
+
+    out.push_str("\n</svg>");
+}
+
+/// Mirrors `with_symbol` in `mana.rs`.
+fn write_symbol(out: &mut String, glyph: Glyph, size: f64, config: &SVGConfig) {
+    let symbol_width = SVG_WIDTH * size;
+    let x_pos = SVG_WIDTH / 2.0;
+    let y_pos = SVG_WIDTH / 2.0;
+    let x = x_pos - symbol_width / 2.0;
+    let y = y_pos - symbol_width / 2.0;
+    write_glyph(out, glyph, x, y, symbol_width, config);
+}
+
+/// Mirrors `with_symbol` in `mana.rs`, for [`SVGConfig::symbol_override`].
+fn write_override(out: &mut String, artwork: SVG) {
+    let artwork = artwork.set("width", SVG_WIDTH).set("height", SVG_WIDTH).set("x", 0).set("y", 0);
+    out.push('\n');
+    write_canonical_svg(out, &artwork).unwrap();
+}
+
+/// Mirrors `with_symbols` in `mana.rs`.
+fn write_symbols(out: &mut String, left: Glyph, right: Glyph, size: f64, config: &SVGConfig) {
+    let pi = f64::consts::PI;
+    let x_right = f64::cos(pi / 4.0) * (SVG_WIDTH / 4.0) + (SVG_WIDTH / 2.0);
+    let y_right = f64::sin(pi / 4.0) * (SVG_WIDTH / 4.0) + (SVG_WIDTH / 2.0);
+
+    let x_left = f64::cos(pi / 4.0 + pi) * (SVG_WIDTH / 4.0) + (SVG_WIDTH / 2.0);
+    let y_left = f64::sin(pi / 4.0 + pi) * (SVG_WIDTH / 4.0) + (SVG_WIDTH / 2.0);
+
+    let symbol_width = (SVG_WIDTH / 2.0) * size;
+    let right_x = x_right - symbol_width / 2.0;
+    let right_y = y_right - symbol_width / 2.0;
+    write_glyph(out, right, right_x, right_y, symbol_width, config);
+
+    let left_x = x_left - symbol_width / 2.0;
+    let left_y = y_left - symbol_width / 2.0;
+    write_glyph(out, left, left_x, left_y, symbol_width, config);
+}
+
+/// Write the built-in artwork of `glyph`. Artwork which is more than plain
+/// paths is rare, so it is built as an SVG first.
+fn write_glyph(out: &mut String, glyph: Glyph, x: f64, y: f64, width: f64, config: &SVGConfig) {
+    let stroke = config.glyph_stroke.as_ref();
+    let Some(paths) = glyph_paths(glyph) else {
+        if let Some(mut symbol) = glyph_symbol(glyph) {
+            if let Some(stroke) = stroke {
+                symbol = symbol
+                    .set("stroke", stroke.color.as_str())
+                    .set("stroke-width", stroke.width)
+                    .set("paint-order", "stroke");
+            }
+            let symbol = symbol.set("width", width).set("height", width).set("x", x).set("y", y);
+            out.push('\n');
+            write_canonical_svg(out, &symbol).unwrap();
+        }
+        return;
+    };
+
+    let view_box = ViewBox(0.0, 0.0, SVG_WIDTH, SVG_WIDTH);
+    out.push('\n');
+    start_tag(
+        out,
+        "svg",
+        &mut [
+            Some(("viewBox", &view_box)),
+            Some(("xmlns", &XMLNS)),
+            stroke.map(|stroke| ("stroke", &stroke.color as &dyn Display)),
+            stroke.map(|stroke| ("stroke-width", &stroke.width as &dyn Display)),
+            stroke.map(|_| ("paint-order", &"stroke" as &dyn Display)),
+            Some(("width", &width)),
+            Some(("height", &width)),
+            Some(("x", &x)),
+            Some(("y", &y)),
+        ],
+    );
+    if paths.is_empty() {
+        out.push_str("/>");
+        return;
+    }
+    out.push('>');
+    for data in paths {
+        empty_element(out, "path", &mut [Some(("d", data))]);
+    }
+    out.push_str("\n</svg>");
+}
+
+/// Mirrors `with_circle` in `mana.rs`.
+fn write_circle(out: &mut String, fill: &str) {
+    let center = SVG_WIDTH / 2.0;
+    empty_element(
+        out,
+        "circle",
+        &mut [
+            Some(("fill", &fill)),
+            Some(("stroke", &"none")),
+            Some(("r", &center)),
+            Some(("cx", &center)),
+            Some(("cy", &center)),
+        ],
+    );
+}
+
+/// Mirrors `with_outline` in `mana.rs`.
+fn write_outline(out: &mut String, stroke: &str, width: f64, non_scaling: bool) {
+    let center = SVG_WIDTH / 2.0;
+    let r = (SVG_WIDTH - width) / 2.0;
+    empty_element(
+        out,
+        "circle",
+        &mut [
+            Some(("fill", &"none")),
+            Some(("stroke", &stroke)),
+            Some(("stroke-width", &width)),
+            Some(("r", &r)),
+            Some(("cx", &center)),
+            Some(("cy", &center)),
+            non_scaling.then_some(("vector-effect", &"non-scaling-stroke" as &dyn Display)),
+        ],
+    );
+}
+
+/// Mirrors `with_foil` in `mana.rs`.
+fn write_foil(out: &mut String, foil: &Foil, shimmer: Option<f64>) {
+    let id = foil_id(foil, shimmer);
+    out.push_str("\n<defs>\n");
+    start_tag(
+        out,
+        "linearGradient",
+        &mut [
+            Some(("id", &id)),
+            Some(("x1", &0)),
+            Some(("y1", &0)),
+            Some(("x2", &1)),
+            Some(("y2", &1)),
+        ],
+    );
+    let n = foil.colors.len();
+    if n == 0 && shimmer.is_none() {
+        out.push_str("/>");
+    } else {
+        out.push('>');
+        for (i, color) in foil.colors.iter().enumerate() {
+            let offset = if n > 1 { i as f64 / (n - 1) as f64 } else { 0.0 };
+            empty_element(
+                out,
+                "stop",
+                &mut [Some(("offset", &offset)), Some(("stop-color", color))],
+            );
+        }
+        if let Some(duration) = shimmer {
+            let duration = format_args!("{duration}s");
+            empty_element(
+                out,
+                "animateTransform",
+                &mut [
+                    Some(("attributeName", &"gradientTransform")),
+                    Some(("type", &"translate")),
+                    Some(("from", &"-1 -1")),
+                    Some(("to", &"1 1")),
+                    Some(("dur", &duration)),
+                    Some(("repeatCount", &"indefinite")),
+                ],
+            );
+        }
+        out.push_str("\n</linearGradient>");
+    }
+    out.push_str("\n</defs>");
+
+    let center = SVG_WIDTH / 2.0;
+    empty_element(
+        out,
+        "circle",
+        &mut [
+            Some(("fill", &format_args!("url(#{id})"))),
+            Some(("stroke", &"none")),
+            Some(("r", &center)),
+            Some(("cx", &center)),
+            Some(("cy", &center)),
+        ],
+    );
+}
+
+/// Mirrors `with_drop_shadow` in `mana.rs`.
+fn write_drop_shadow(out: &mut String, drop_shadow: &DropShadow) {
+    let id = drop_shadow_id(drop_shadow);
+    out.push_str("\n<defs>\n");
+    start_tag(
+        out,
+        "filter",
+        &mut [
+            Some(("id", &id)),
+            Some(("x", &"-50%")),
+            Some(("y", &"-50%")),
+            Some(("width", &"200%")),
+            Some(("height", &"200%")),
+        ],
+    );
+    out.push('>');
+    empty_element(
+        out,
+        "feDropShadow",
+        &mut [
+            Some(("dx", &drop_shadow.dx)),
+            Some(("dy", &drop_shadow.dy)),
+            Some(("stdDeviation", &drop_shadow.blur)),
+            Some(("flood-color", &drop_shadow.color)),
+            Some(("flood-opacity", &drop_shadow.opacity)),
+        ],
+    );
+    out.push_str("\n</filter>\n</defs>");
+
+    let center = SVG_WIDTH / 2.0;
+    empty_element(
+        out,
+        "circle",
+        &mut [
+            Some(("fill", &"black")),
+            Some(("stroke", &"none")),
+            Some(("filter", &format_args!("url(#{id})"))),
+            Some(("r", &center)),
+            Some(("cx", &center)),
+            Some(("cy", &center)),
+        ],
+    );
+}
+
+/// Mirrors `with_shadow` in `mana.rs`.
+fn write_shadow(out: &mut String, offset: f64, fill: &str) {
+    let center = SVG_WIDTH / 2.0;
+    let cx = center - offset;
+    let cy = center + offset;
+    empty_element(
+        out,
+        "circle",
+        &mut [
+            Some(("fill", &fill)),
+            Some(("stroke", &"none")),
+            Some(("r", &center)),
+            Some(("cx", &cx)),
+            Some(("cy", &cy)),
+        ],
+    );
+}
+
+/// Mirrors `with_split_circle` in `mana.rs`.
+fn write_split_circle(out: &mut String, fill_left: &str, fill_right: &str) {
+    let id = circle_mask_id();
+    out.push('\n');
+    start_tag(out, "mask", &mut [Some(("id", &id)), Some(("mask-type", &"luminance"))]);
+    out.push('>');
+    let center = SVG_WIDTH / 2.0;
+    empty_element(
+        out,
+        "circle",
+        &mut [
+            Some(("fill", &"white")),
+            Some(("stroke", &"none")),
+            Some(("r", &center)),
+            Some(("cx", &center)),
+            Some(("cy", &center)),
+        ],
+    );
+    out.push_str("\n</mask>\n");
+
+    start_tag(out, "g", &mut [Some(("mask", &format_args!("url(#{id})")))]);
+    out.push('>');
+    let right = format_args!("M0,0 H{SVG_WIDTH} V{SVG_WIDTH} H0 z");
+    empty_element(out, "path", &mut [Some(("d", &right)), Some(("fill", &fill_right))]);
+    let left = format_args!("M0,0 H{SVG_WIDTH} L0,{SVG_WIDTH} z");
+    empty_element(out, "path", &mut [Some(("d", &left)), Some(("fill", &fill_left))]);
+    out.push_str("\n</g>");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ColorRegistry, Palette, ShapeRendering, Stroke, canonical_svg};
+
+    fn square_colorless(mana: &Mana) -> Option<SVG> {
+        let square = svg::node::element::Rectangle::new().set("width", 32).set("height", 32);
+        (*mana == Mana::Colorless).then(|| SVG::new().set("viewBox", (0, 0, 32, 32)).add(square))
+    }
+
+    fn configs() -> Vec<SVGConfig> {
+        vec![
+            SVGConfig::default(),
+            SVGConfig { shadow: false, ..Default::default() },
+            SVGConfig::dark(),
+            SVGConfig { drop_shadow: Some(DropShadow::default()), ..Default::default() },
+            SVGConfig {
+                palette: Palette { foil: Some(Foil::rainbow()), ..Palette::high_contrast() },
+                circle_stroke: Some(Stroke { color: "#333".to_string(), width: 2.0 }),
+                glyph_stroke: Some(Stroke { color: "'\"".to_string(), width: 0.5 }),
+                shimmer: Some(2.5),
+                shape_rendering: Some(ShapeRendering::GeometricPrecision),
+                non_scaling_stroke: true,
+                ..Default::default()
+            },
+            SVGConfig {
+                drop_shadow: Some(DropShadow::default()),
+                palette: Palette {
+                    foil: Some(Foil::rainbow()),
+                    plate: Some(Plate::arena()),
+                    ..Default::default()
+                },
+                symbol_override: Some(square_colorless),
+                ..Default::default()
+            },
+            SVGConfig {
+                padding: Some(0.0),
+                spacing: 3.0,
+                width: Some(100.0),
+                height: Some(20.0),
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn same_as_svg() {
+        for config in configs() {
+            for mana in Mana::all_printed() {
+                let mut out = String::new();
+                render_mana(&mut out, &mana, &config);
+                assert_eq!(out, canonical_svg(&mana.as_svg(&config)));
+            }
+        }
+    }
+
+    #[test]
+    fn manas_same_as_svg() {
+        let mut registry = ColorRegistry::default();
+        registry.register('O', "orange", "#ff8000").unwrap();
+        for config in configs() {
+            for cost in ["", "{W}", "{25}{S}{O}{X}", "{2/G}{C/W}{U/B/P}{1000000}", "{HW}{R}{HR}"] {
+                let manas = Manas::from_str_with(cost, &registry).unwrap();
+                let mut out = String::new();
+                render_manas(&mut out, &manas, &config);
+                assert_eq!(out, canonical_svg(&manas.as_svg(&config)));
+            }
+        }
+    }
+}
@@ -1,13 +1,26 @@
 use std::fmt::Display;
 
-use nom::{IResult, Parser, branch::alt, bytes::complete::tag, sequence::terminated};
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::tag,
+    sequence::{preceded, terminated},
+};
 
 use crate::Color;
 
+/// The marker after the color of Phyrexian mana, which is usually `/P`, but is
+/// written as `Φ` by some older sources, e.g. `{UΦ}` or `{U/Φ}`.
+pub fn phyrexian_marker(input: &str) -> IResult<&str, &str> {
+    alt((tag("/P"), tag("/Φ"), tag("Φ"))).parse(input)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SingleMana {
     Normal(Color),
     Phyrexian(Color),
+    /// Half of a colored mana symbol, e.g. `{HW}`, from Un-sets.
+    Half(Color),
 }
 
 impl Display for SingleMana {
@@ -15,6 +28,7 @@ impl Display for SingleMana {
         match self {
             Self::Normal(color) => color.fmt(f),
             Self::Phyrexian(color) => write!(f, "{color}/P"),
+            Self::Half(color) => write!(f, "H{color}"),
         }
     }
 }
@@ -22,13 +36,14 @@ impl Display for SingleMana {
 impl SingleMana {
     pub const fn color(self) -> Color {
         match self {
-            Self::Normal(color) | Self::Phyrexian(color) => color,
+            Self::Normal(color) | Self::Phyrexian(color) | Self::Half(color) => color,
         }
     }
 
     pub fn parse(input: &str) -> IResult<&str, Self> {
-        let phyrexian = terminated(Color::parse, tag("/P")).map(Self::Phyrexian);
+        let phyrexian = terminated(Color::parse, phyrexian_marker).map(Self::Phyrexian);
+        let half = preceded(tag("H"), Color::parse).map(Self::Half);
         let normal = Color::parse.map(Self::Normal);
-        alt((phyrexian, normal)).parse(input)
+        alt((phyrexian, half, normal)).parse(input)
     }
 }
@@ -0,0 +1,32 @@
+/// How mana symbols should be ordered by
+/// [`Manas::sort_with`][crate::Manas::sort_with].
+///
+/// For the default style, use [`SortStyle::default`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortStyle {
+    /// Mana symbols are sorted in groups and colors are ordered to minimize
+    /// the distance between them on the color wheel (see
+    /// [`Manas::sort`][crate::Manas::sort]).
+    #[default]
+    WheelCanonical,
+
+    /// Mana symbols are sorted in the same groups as
+    /// [`SortStyle::WheelCanonical`], but colors are always ordered as `W`,
+    /// `U`, `B`, `R`, `G`.
+    FixedWUBRG,
+
+    /// Mana symbols are sorted in the same order as they are listed in the
+    /// [symbology of Scryfall](https://scryfall.com/docs/api/card-symbols):
+    /// 1. Generic mana (`X`, `Y`, `Z`, then numbers)
+    /// 2. Hybrid mana of two colors
+    /// 3. Phyrexian hybrid mana of two colors
+    /// 4. Hybrid colorless mana
+    /// 5. Hybrid generic mana
+    /// 6. Phyrexian mana
+    /// 7. Colored mana
+    /// 8. Colorless mana
+    /// 9. Snow mana
+    ///
+    /// Within each group, colors are ordered as `W`, `U`, `B`, `R`, `G`.
+    Scryfall,
+}
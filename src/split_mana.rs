@@ -8,7 +8,7 @@ use nom::{
     sequence::{preceded, separated_pair, terminated},
 };
 
-use crate::{Color, color_set::ColorSet};
+use crate::{Color, color_set::ColorSet, single_mana::phyrexian_marker};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SplitMana {
@@ -38,8 +38,8 @@ impl SplitMana {
         if let Self::Duo { a, b, phyrexian } = self {
             // We sort hybrid mana with two colors
             let mut color_set = ColorSet::new();
-            color_set.set_color(*a);
-            color_set.set_color(*b);
+            color_set.insert(*a);
+            color_set.insert(*b);
             let order = color_set.order_values();
             if order[*a as usize] > order[*b as usize] {
                 *self = Self::Duo { a: *b, b: *a, phyrexian: *phyrexian }
@@ -64,7 +64,7 @@ impl SplitMana {
     pub fn parse(input: &str) -> IResult<&str, Self> {
         let colorless = preceded(tag("C/"), Color::parse).map(|color| Self::Colorless { color });
         let phyrexian =
-            terminated(separated_pair(Color::parse, char('/'), Color::parse), tag("/P"))
+            terminated(separated_pair(Color::parse, char('/'), Color::parse), phyrexian_marker)
                 .map(|(a, b)| Self::Duo { a, b, phyrexian: true });
         let normal = separated_pair(Color::parse, char('/'), Color::parse)
             .map(|(a, b)| Self::Duo { a, b, phyrexian: false });
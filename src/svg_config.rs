@@ -1,3 +1,9 @@
+use std::fmt::Display;
+
+use svg::node::element::SVG;
+
+use crate::{Mana, Palette};
+
 /// Configuration for SVG outputs.
 ///
 /// Used by [`Mana::as_svg`][crate::Mana::as_svg]
@@ -12,12 +18,236 @@ pub struct SVGConfig {
 
     /// How large should the shadow be offset from the main circle.
     /// Even if the shadow is not drawn, this will affect the size of the margin
-    /// around the main circle.
+    /// around the main circle, unless [`SVGConfig::padding`] is set.
     pub shadow_offset: f64,
+
+    /// The fill color of the shadow.
+    pub shadow_color: String,
+
+    /// If set, the shadow is drawn as a blurred drop shadow instead of as a
+    /// circle offset by [`SVGConfig::shadow_offset`], which looks better at
+    /// large sizes. The margin around the main circle is still decided by
+    /// [`SVGConfig::shadow_offset`].
+    pub drop_shadow: Option<DropShadow>,
+
+    /// The fill colors of the circles.
+    pub palette: Palette,
+
+    /// An outline around each circle, used instead of the outline of the
+    /// palette (or theme).
+    pub circle_stroke: Option<Stroke>,
+
+    /// An outline around each glyph, e.g. to keep white glyphs visible on
+    /// light backgrounds.
+    pub glyph_stroke: Option<Stroke>,
+
+    /// The space between mana symbols drawn by
+    /// [`Manas::as_svg`][crate::Manas::as_svg], where the width of a mana
+    /// symbol is 32.
+    pub spacing: f64,
+
+    /// The margin around each circle, where the width of a mana symbol is 32.
+    /// If `None`, [`SVGConfig::shadow_offset`] is used so that the shadow
+    /// fits. Anything drawn outside the margin is cut off.
+    pub padding: Option<f64>,
+
+    /// The width of the SVG. If only one of `width` and
+    /// [`SVGConfig::height`] is set, the other is scaled to keep the aspect
+    /// ratio. If both are set, the symbols are centered in the given size.
+    pub width: Option<f64>,
+
+    /// The height of the SVG. If only one of [`SVGConfig::width`] and `height`
+    /// is set, the other is scaled to keep the aspect ratio. If both are
+    /// `None`, the SVG has no fixed size.
+    pub height: Option<f64>,
+
+    /// The number of decimals used for coordinates and sizes, e.g. in the
+    /// paths of glyphs. If `None`, numbers are written with full precision.
+    pub precision: Option<usize>,
+
+    /// Whether to omit attributes which do not change how the SVG is drawn,
+    /// e.g. the namespace of nested SVGs, fills which are already inherited or
+    /// descriptions of hidden elements.
+    pub minify: bool,
+
+    /// If set, the foil of the palette (or theme) moves diagonally across
+    /// each symbol, repeating every `shimmer` seconds. This uses SMIL
+    /// animation, which is supported by browsers but usually ignored when
+    /// rasterizing. Has no effect if there is no foil.
+    pub shimmer: Option<f64>,
+
+    /// Whether to only use features of SVG 1.1 Tiny, for renderers which do
+    /// not support all of SVG, e.g. on e-readers or old versions of librsvg.
+    /// Filters, masks and gradients are not used, so
+    /// [`SVGConfig::drop_shadow`] and foils are ignored, and hybrid symbols are
+    /// split using an arc instead of a mask. Glyphs and the symbols of
+    /// [`Manas::as_svg`][crate::Manas::as_svg] are drawn as transformed groups
+    /// instead of nested SVGs, so they are not cut off at their edges, except
+    /// for half mana symbols in [`Manas::as_svg`][crate::Manas::as_svg], which
+    /// need to be cut in half.
+    /// [`SVGConfig::non_scaling_stroke`] is ignored, glyph outlines are drawn
+    /// as a separate copy below each glyph, and the `role` and `aria-*`
+    /// attributes are left out, leaving the title and description.
+    pub compatible: bool,
+
+    /// How renderers should trade speed for precision when drawing the
+    /// circles and glyphs, set as `shape-rendering` on the whole SVG. If
+    /// `None`, the renderer decides, which can look blurry at small sizes.
+    pub shape_rendering: Option<ShapeRendering>,
+
+    /// Whether the width of the outline around each circle is measured in
+    /// pixels of the final image (using `vector-effect="non-scaling-stroke"`)
+    /// instead of in units where the width of a mana symbol is 32, so that
+    /// the outline stays sharp when the symbols are drawn small. Ignored if
+    /// [`SVGConfig::compatible`] is set.
+    pub non_scaling_stroke: bool,
+
+    /// Artwork to draw instead of the circle and glyph of some mana symbols,
+    /// using a `viewBox` of `0 0 32 32`. If it returns `None`, the symbol is
+    /// drawn as usual. The shadow and the description of the symbol are kept.
+    ///
+    /// ```
+    /// use mana_symbols::{Mana, SVGConfig};
+    /// use svg::node::element::{Rectangle, SVG};
+    ///
+    /// fn square_colorless(mana: &Mana) -> Option<SVG> {
+    ///     let square = Rectangle::new().set("width", 32).set("height", 32);
+    ///     (*mana == Mana::Colorless).then(|| SVG::new().set("viewBox", (0, 0, 32, 32)).add(square))
+    /// }
+    ///
+    /// let config = SVGConfig { symbol_override: Some(square_colorless), ..Default::default() };
+    /// let manas: mana_symbols::Manas = "{C}{U}".parse().unwrap();
+    /// let svg = manas.as_svg(&config).to_string();
+    /// assert_eq!(svg.matches("<rect").count(), 1);
+    /// ```
+    pub symbol_override: Option<fn(&Mana) -> Option<SVG>>,
 }
 
 impl Default for SVGConfig {
     fn default() -> Self {
-        Self { shadow: true, shadow_offset: 1.5 }
+        Self {
+            shadow: true,
+            shadow_offset: 1.5,
+            shadow_color: "black".to_string(),
+            drop_shadow: None,
+            palette: Palette::default(),
+            circle_stroke: None,
+            glyph_stroke: None,
+            spacing: 0.0,
+            padding: None,
+            width: None,
+            height: None,
+            precision: None,
+            minify: false,
+            shimmer: None,
+            compatible: false,
+            shape_rendering: None,
+            non_scaling_stroke: false,
+            symbol_override: None,
+        }
+    }
+}
+
+impl SVGConfig {
+    /// Options for drawing on dark backgrounds, using [`Palette::dark`] and a
+    /// light shadow which looks like a glow.
+    ///
+    /// ```
+    /// use mana_symbols::{Mana, SVGConfig};
+    ///
+    /// let mana: Mana = "W".parse().unwrap();
+    /// let svg = mana.as_svg(&SVGConfig::dark()).to_string();
+    /// assert!(svg.contains(r##"stroke="#f0f0f0""##));
+    /// ```
+    #[must_use]
+    pub fn dark() -> Self {
+        Self { shadow_color: "#f0f0f0".to_string(), palette: Palette::dark(), ..Self::default() }
+    }
+
+    /// The margin around each circle.
+    pub(crate) fn margin(&self) -> f64 {
+        self.padding.unwrap_or(self.shadow_offset)
+    }
+
+    /// The width and height of the SVG, given the aspect ratio (width divided
+    /// by height) of its `viewBox`.
+    pub(crate) fn size(&self, aspect_ratio: f64) -> Option<(f64, f64)> {
+        match (self.width, self.height) {
+            (Some(width), Some(height)) => Some((width, height)),
+            (Some(width), None) => Some((width, width / aspect_ratio)),
+            (None, Some(height)) => Some((height * aspect_ratio, height)),
+            (None, None) => None,
+        }
+    }
+}
+
+/// An outline, used by [`SVGConfig`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stroke {
+    /// The color of the outline.
+    pub color: String,
+
+    /// The width of the outline, where the width of a mana symbol is 32.
+    pub width: f64,
+}
+
+/// A value of the SVG attribute
+/// [`shape-rendering`](https://developer.mozilla.org/en-US/docs/Web/SVG/Reference/Attribute/shape-rendering),
+/// used by [`SVGConfig::shape_rendering`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeRendering {
+    /// Let the renderer decide.
+    Auto,
+
+    /// Draw quickly, e.g. without anti-aliasing.
+    OptimizeSpeed,
+
+    /// Keep edges sharp, e.g. by aligning them to pixels.
+    CrispEdges,
+
+    /// Draw the shapes as precisely as possible.
+    GeometricPrecision,
+}
+
+impl Display for ShapeRendering {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "auto",
+            Self::OptimizeSpeed => "optimizeSpeed",
+            Self::CrispEdges => "crispEdges",
+            Self::GeometricPrecision => "geometricPrecision",
+        })
+    }
+}
+
+/// A blurred shadow, drawn using an SVG
+/// [`feDropShadow`](https://developer.mozilla.org/en-US/docs/Web/SVG/Reference/Element/feDropShadow)
+/// filter. Used by [`SVGConfig`].
+///
+/// For a shadow similar to the default shadow, use [`DropShadow::default`].
+///
+/// The id of the filter is `drop_shadow-` followed by a hash of its fields, so
+/// SVGs with different shadows can be inlined in the same HTML page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropShadow {
+    /// The horizontal offset of the shadow.
+    pub dx: f64,
+
+    /// The vertical offset of the shadow.
+    pub dy: f64,
+
+    /// The standard deviation of the blur.
+    pub blur: f64,
+
+    /// The color of the shadow.
+    pub color: String,
+
+    /// The opacity of the shadow, between 0 and 1.
+    pub opacity: f64,
+}
+
+impl Default for DropShadow {
+    fn default() -> Self {
+        Self { dx: -1.0, dy: 1.0, blur: 0.75, color: "black".to_string(), opacity: 0.8 }
     }
 }
@@ -0,0 +1,22 @@
+use std::sync::{Arc, OnceLock};
+
+use resvg::usvg::{self, fontdb};
+use svg::node::element::SVG;
+
+/// Parse `svg` with [`usvg`], so that it can be rasterized or converted.
+/// Returns `None` if it could not be parsed.
+pub(crate) fn svg_to_tree(svg: &SVG) -> Option<usvg::Tree> {
+    let options = usvg::Options { fontdb: Arc::clone(fonts()), ..Default::default() };
+    usvg::Tree::from_str(&svg.to_string(), &options).ok()
+}
+
+/// The system fonts, which are only needed for the letters of custom colors.
+/// Loading them is slow, so it is only done once.
+fn fonts() -> &'static Arc<fontdb::Database> {
+    static FONTS: OnceLock<Arc<fontdb::Database>> = OnceLock::new();
+    FONTS.get_or_init(|| {
+        let mut fonts = fontdb::Database::new();
+        fonts.load_system_fonts();
+        Arc::new(fonts)
+    })
+}
@@ -0,0 +1,15 @@
+use std::io::Write;
+
+use flate2::{Compression, write::GzEncoder};
+use svg::node::element::SVG;
+
+use crate::canonical_svg;
+
+/// Compress `svg` using [gzip](https://en.wikipedia.org/wiki/Gzip), as used by
+/// `.svgz` files.
+pub(crate) fn svg_to_svgz(svg: &SVG) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    // Writing to a `Vec` can't fail
+    encoder.write_all(canonical_svg(svg).as_bytes()).unwrap();
+    encoder.finish().unwrap()
+}
@@ -0,0 +1,322 @@
+use std::{
+    fmt::{Display, Write},
+    str::FromStr,
+};
+
+use nom::{
+    Finish, IResult, Parser,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::char,
+    combinator::{eof, value},
+    sequence::{delimited, terminated},
+};
+#[cfg(feature = "svg")]
+use svg::node::element::SVG;
+
+use crate::{LoyaltyCost, Mana};
+#[cfg(feature = "svg")]
+use crate::{Palette, SVGConfig, Theme};
+
+/// A symbol in the rules text of a card, e.g. `{T}` in `{T}: Add {G}.`
+///
+/// This is every [`Mana`] symbol, together with the symbols of costs which are
+/// not mana (e.g. the tap symbol) and symbols which never appear in costs, so
+/// a whole line of text can be handled using one type (see
+/// [`Symbol::split_text`]). [`Manas`](crate::Manas) only contains mana.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symbol {
+    /// A mana symbol.
+    Mana(Mana),
+    /// The tap symbol, `{T}`, which is part of the costs of many activated
+    /// abilities.
+    Tap,
+    /// The untap symbol, `{Q}`.
+    Untap,
+    /// The energy symbol, `{E}`, which is paid using energy counters.
+    Energy,
+    /// The ticket symbol, `{TK}`, from Unfinity, which is paid using ticket
+    /// counters.
+    Ticket,
+    /// The chaos symbol, `{CHAOS}`, of
+    /// [Planechase](https://mtg.wiki/page/Planechase) planes.
+    Chaos,
+    /// The loyalty cost of a planeswalker ability, e.g. `[+1]`.
+    Loyalty(LoyaltyCost),
+}
+
+/// A part of a line of rules text, see [`Symbol::split_text`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextPart<'a> {
+    /// Text without any symbols.
+    Text(&'a str),
+    /// A symbol, which was written in braces (or in brackets, for loyalty
+    /// costs).
+    Symbol(Symbol),
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mana(mana) => mana.fmt(f),
+            Self::Tap => f.write_char('T'),
+            Self::Untap => f.write_char('Q'),
+            Self::Energy => f.write_char('E'),
+            Self::Ticket => f.write_str("TK"),
+            Self::Chaos => f.write_str("CHAOS"),
+            Self::Loyalty(cost) => cost.fmt(f),
+        }
+    }
+}
+
+impl FromStr for Symbol {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let p = terminated(Self::parse, eof).parse(s).finish();
+
+        match p {
+            Ok((_, symbol)) => Ok(symbol),
+            Err(_) => Err(()),
+        }
+    }
+}
+
+impl From<Mana> for Symbol {
+    fn from(mana: Mana) -> Self {
+        Self::Mana(mana)
+    }
+}
+
+impl Symbol {
+    fn parse_inner(input: &str) -> IResult<&str, Self> {
+        let chaos = value(Self::Chaos, tag("CHAOS"));
+        let ticket = value(Self::Ticket, tag("TK"));
+        let tap = value(Self::Tap, char('T'));
+        let untap = value(Self::Untap, char('Q'));
+        let energy = value(Self::Energy, char('E'));
+        let mana = Mana::parse_inner.map(Self::Mana);
+
+        // `CHAOS` would otherwise be parsed as `C`, and `TK` as `T`
+        alt((chaos, ticket, tap, untap, energy, mana)).parse(input)
+    }
+
+    /// A symbol as written in rules text, i.e. in braces or, for loyalty
+    /// costs, in brackets.
+    fn parse_written(input: &str) -> IResult<&str, Self> {
+        let braces = delimited(char('{'), Self::parse_inner, char('}'));
+        let loyalty = LoyaltyCost::parse.map(Self::Loyalty);
+        alt((braces, loyalty)).parse(input)
+    }
+
+    /// Parse a `Symbol` using [`nom`]. If you just want to parse normally, use
+    /// [`Symbol::from_str`].
+    pub fn parse(input: &str) -> IResult<&str, Self> {
+        alt((Self::parse_written, Self::parse_inner)).parse(input)
+    }
+
+    /// Parse a `Symbol` written in the legacy encoding of old Oracle text dumps,
+    /// where `ocT` is the tap symbol and mana is written as in
+    /// [`Mana::parse_legacy`].
+    ///
+    /// ```
+    /// use mana_symbols::Symbol;
+    ///
+    /// let (rest, symbol) = Symbol::parse_legacy("ocT: Add oG.").unwrap();
+    /// assert_eq!(symbol, Symbol::Tap);
+    /// assert_eq!(rest, ": Add oG.");
+    /// ```
+    pub fn parse_legacy(input: &str) -> IResult<&str, Self> {
+        let tap = value(Self::Tap, tag("ocT"));
+        let mana = Mana::parse_legacy.map(Self::Mana);
+        alt((tap, mana)).parse(input)
+    }
+
+    /// Split a line of rules text into text and the symbols in braces, or
+    /// loyalty costs in brackets. Braces and brackets which do not contain a
+    /// known symbol are kept as text.
+    ///
+    /// ```
+    /// use mana_symbols::{Symbol, TextPart};
+    ///
+    /// let parts = Symbol::split_text("{T}: Add {G}.");
+    /// assert_eq!(parts.len(), 4);
+    /// assert_eq!(parts[1], TextPart::Text(": Add "));
+    /// assert_eq!(parts[2], TextPart::Symbol("G".parse().unwrap()));
+    /// ```
+    #[must_use]
+    pub fn split_text(line: &str) -> Vec<TextPart<'_>> {
+        let mut parts = Vec::new();
+        let mut text_start = 0;
+        let mut rest = line;
+        while let Some(open) = rest.find(['{', '[']) {
+            let candidate = &rest[open..];
+            let (after, symbol) = match Self::parse_written(candidate) {
+                Ok(parsed) => parsed,
+                Err(_) => {
+                    rest = &candidate[1..];
+                    continue;
+                }
+            };
+            let start = line.len() - candidate.len();
+            if text_start < start {
+                parts.push(TextPart::Text(&line[text_start..start]));
+            }
+            parts.push(TextPart::Symbol(symbol));
+            rest = after;
+            text_start = line.len() - rest.len();
+        }
+        if text_start < line.len() {
+            parts.push(TextPart::Text(&line[text_start..]));
+        }
+        parts
+    }
+
+    /// The mana symbol, if this is one.
+    #[must_use]
+    pub const fn mana(&self) -> Option<Mana> {
+        match self {
+            Self::Mana(mana) => Some(*mana),
+            Self::Tap
+            | Self::Untap
+            | Self::Energy
+            | Self::Ticket
+            | Self::Chaos
+            | Self::Loyalty(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "svg")]
+impl Symbol {
+    /// Display the symbol as an [SVG](https://en.wikipedia.org/wiki/SVG),
+    /// drawn like mana symbols (see [`Mana::as_svg`]). Loyalty costs are
+    /// drawn as shields instead (see [`LoyaltyCost::as_svg`]).
+    #[must_use]
+    pub fn as_svg(&self, config: &SVGConfig) -> SVG {
+        self.as_svg_with_theme(config, &config.palette)
+    }
+
+    /// Display the symbol as an [SVG](https://en.wikipedia.org/wiki/SVG), where
+    /// the circles and glyphs are drawn using `theme` instead of
+    /// [`SVGConfig::palette`].
+    #[must_use]
+    pub fn as_svg_with_theme<T: Theme + ?Sized>(&self, config: &SVGConfig, theme: &T) -> SVG {
+        match self {
+            Self::Loyalty(cost) => cost.as_svg(config),
+            _ => Mana::symbol_svg(self, config, theme),
+        }
+    }
+
+    pub(crate) fn name(&self) -> String {
+        match self {
+            Self::Mana(mana) => mana.name(),
+            Self::Tap => "Tap symbol".to_string(),
+            Self::Untap => "Untap symbol".to_string(),
+            Self::Energy => "Energy symbol".to_string(),
+            Self::Ticket => "Ticket symbol".to_string(),
+            Self::Chaos => "Chaos symbol".to_string(),
+            Self::Loyalty(cost) => cost.name(),
+        }
+    }
+
+    /// The name, where custom colors are called by their names in `palette`.
+    pub(crate) fn name_with(&self, palette: &Palette) -> String {
+        match self {
+            Self::Mana(mana) => mana.name_with(palette),
+            _ => self.name(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(Symbol::from_str("{CHAOS}"), Ok(Symbol::Chaos));
+        assert_eq!(Symbol::from_str("CHAOS"), Ok(Symbol::Chaos));
+        assert_eq!(Symbol::from_str("{C}"), Ok(Symbol::Mana(Mana::Colorless)));
+        assert_eq!(Symbol::from_str("{T}"), Ok(Symbol::Tap));
+        assert_eq!(Symbol::from_str("Q"), Ok(Symbol::Untap));
+        assert_eq!(Symbol::from_str("{E}"), Ok(Symbol::Energy));
+        assert_eq!(Symbol::from_str("{TK}"), Ok(Symbol::Ticket));
+        assert_eq!(Mana::from_str("{T}"), Err(()));
+        assert_eq!(Symbol::from_str("{CHAO}"), Err(()));
+        assert_eq!(Symbol::Chaos.to_string(), "CHAOS");
+        assert_eq!(Symbol::Ticket.to_string(), "TK");
+        assert_eq!(Symbol::Tap.mana(), None);
+    }
+
+    #[test]
+    fn legacy() {
+        assert_eq!(Symbol::parse_legacy("ocToG"), Ok(("oG", Symbol::Tap)));
+        assert_eq!(Symbol::parse_legacy("o2"), Ok(("", Symbol::Mana("2".parse().unwrap()))));
+        assert!(Symbol::parse_legacy("oT").is_err());
+    }
+
+    #[test]
+    fn loyalty() {
+        for (text, cost) in [
+            ("[+2]", LoyaltyCost::Plus(2)),
+            ("[-10]", LoyaltyCost::Minus(10)),
+            ("[-X]", LoyaltyCost::MinusX),
+            ("[0]", LoyaltyCost::Zero),
+        ] {
+            let symbol = Symbol::from_str(text).unwrap();
+            assert_eq!(symbol, Symbol::Loyalty(cost));
+            assert_eq!(symbol.to_string(), text);
+        }
+        assert_eq!(Symbol::from_str("[−X]"), Ok(Symbol::Loyalty(LoyaltyCost::MinusX)));
+        assert_eq!(Symbol::from_str("[+X]"), Err(()));
+        assert_eq!(Symbol::from_str("{+1}"), Err(()));
+
+        assert_eq!(
+            Symbol::split_text("[+1]: Scry 1. [X]"),
+            [
+                TextPart::Symbol(Symbol::Loyalty(LoyaltyCost::Plus(1))),
+                TextPart::Text(": Scry 1. [X]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_text() {
+        let line = "Whenever chaos ensues ({CHAOS}), add {2}{R/P} {unknown}";
+        assert_eq!(
+            Symbol::split_text(line),
+            [
+                TextPart::Text("Whenever chaos ensues ("),
+                TextPart::Symbol(Symbol::Chaos),
+                TextPart::Text("), add "),
+                TextPart::Symbol("2".parse().unwrap()),
+                TextPart::Symbol("R/P".parse().unwrap()),
+                TextPart::Text(" {unknown}"),
+            ]
+        );
+        assert_eq!(Symbol::split_text(""), []);
+        assert_eq!(
+            Symbol::split_text("{{Q}"),
+            [TextPart::Text("{"), TextPart::Symbol(Symbol::Untap)]
+        );
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn svg() {
+        let config = SVGConfig::default();
+        let svg = Symbol::Chaos.as_svg(&config).to_string();
+        assert!(svg.contains("<title>Chaos symbol</title>"));
+
+        let svg = Symbol::Loyalty(LoyaltyCost::MinusX).as_svg(&config).to_string();
+        assert!(svg.contains("<title>Minus X loyalty</title>"));
+        assert!(svg.contains("<desc>[-X]</desc>"));
+
+        let svg = Symbol::Energy.as_svg(&config).to_string();
+        assert!(svg.contains("<title>Energy symbol</title>"));
+
+        let u: Mana = "U".parse().unwrap();
+        assert_eq!(Symbol::Mana(u).as_svg(&config).to_string(), u.as_svg(&config).to_string());
+    }
+}
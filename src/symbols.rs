@@ -1,17 +1,16 @@
 use svg::{
     Document,
-    node::element::{Path, SVG, path::Data, tag::Type},
+    node::element::{Path, SVG, Text, path::Data, tag::Type},
     parser::Event,
 };
 
-use crate::{Color, SVG_WIDTH};
+use crate::{Color, Glyph, PathSegment, SVG_WIDTH, path_segment::parse_path};
 
 /// We store each symbol as a seperate SVG file in "/symbols", but when
-/// compiling we statically load them using `include_str!`.
-macro_rules! include_symbol {
-    ($e:expr) => {
-        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/symbols/", $e))
-    };
+/// compiling we convert the data of their paths into constants (see
+/// `build.rs`), named after the file, e.g. `NUMBERS_12` for "numbers/12.svg".
+mod paths {
+    include!(concat!(env!("OUT_DIR"), "/symbol_paths.rs"));
 }
 
 /// Every symbol has identical SVG containers
@@ -19,94 +18,183 @@ fn document() -> SVG {
     Document::new().set("viewBox", (0, 0, SVG_WIDTH, SVG_WIDTH))
 }
 
-pub fn colorless_symbol() -> SVG {
-    parse_add(include_symbol!("c.svg"), document())
+/// The built-in artwork of `glyph`. Returns `None` for variables other than X,
+/// Y and Z.
+pub fn glyph_symbol(glyph: Glyph) -> Option<SVG> {
+    if let Some(paths) = glyph_paths(glyph) {
+        return Some(paths_symbol(paths));
+    }
+    match glyph {
+        Glyph::Snow => Some(snow_symbol()),
+        Glyph::Number(n) => Some(composed_number_symbol(n)),
+        Glyph::Custom(color) => Some(letter_symbol(color.letter())),
+        _ => None,
+    }
+}
+
+/// The data of the paths of the built-in artwork of `glyph`, if the artwork
+/// only consists of paths using the inherited fill. Returns `None` for snow
+/// mana, numbers larger than 20, custom colors and unknown variables.
+pub fn glyph_paths(glyph: Glyph) -> Option<&'static [&'static str]> {
+    let paths = match glyph {
+        Glyph::Color(Color::White) => paths::W,
+        Glyph::Color(Color::Blue) => paths::U,
+        Glyph::Color(Color::Black) => paths::B,
+        Glyph::Color(Color::Red) => paths::R,
+        Glyph::Color(Color::Green) => paths::G,
+        Glyph::Colorless => paths::C,
+        Glyph::Phyrexian => paths::P,
+        Glyph::Number(n) => return number_paths(n),
+        Glyph::Variable('X') => paths::X,
+        Glyph::Variable('Y') => paths::Y,
+        Glyph::Variable('Z') => paths::Z,
+        Glyph::Infinity => paths::INFINITY,
+        Glyph::Tap => paths::T,
+        Glyph::Untap => paths::Q,
+        Glyph::Energy => paths::E,
+        Glyph::Ticket => paths::TK,
+        Glyph::Chaos => paths::CHAOS,
+        Glyph::Snow | Glyph::Variable(_) | Glyph::Custom(_) => return None,
+    };
+    Some(paths)
 }
 
-pub fn phyrexian_symbol() -> SVG {
-    parse_add(include_symbol!("p.svg"), document())
+/// The outline of the built-in artwork of `glyph`, using a `viewBox` of
+/// `0 0 32 32`. Only the black outline of snow mana is included. Returns `None`
+/// for custom colors and unknown variables, whose artwork is text.
+pub fn glyph_segments(glyph: Glyph) -> Option<Vec<PathSegment>> {
+    if let Some(paths) = glyph_paths(glyph) {
+        return Some(paths.iter().flat_map(|data| parse_path(data)).collect());
+    }
+    match glyph {
+        Glyph::Snow => Some(parse_path(paths::S[1])),
+        Glyph::Number(n) => Some(composed_number_segments(n)),
+        _ => None,
+    }
 }
 
-pub fn snow_symbol() -> SVG {
-    let content = include_symbol!("s.svg");
-    let mut paths = get_paths(content);
-    let mut inner_path = paths.next().unwrap();
-    let mut outline_path = paths.next().unwrap();
-    inner_path = inner_path.set("fill", "white");
-    outline_path = outline_path.set("fill", "black");
+fn snow_symbol() -> SVG {
+    let [inner, outline] = paths::S else { unreachable!("The snow symbol consists of two paths") };
+    let inner_path = Path::new().set("d", *inner).set("fill", "white");
+    let outline_path = Path::new().set("d", *outline).set("fill", "black");
 
     document().add(inner_path).add(outline_path)
 }
 
-pub fn color_symbol(color: Color) -> SVG {
-    let content = match color {
-        Color::White => include_symbol!("w.svg"),
-        Color::Blue => include_symbol!("u.svg"),
-        Color::Black => include_symbol!("b.svg"),
-        Color::Red => include_symbol!("r.svg"),
-        Color::Green => include_symbol!("g.svg"),
+/// Returns `None` if there is no artwork of `n`, i.e. if it is larger than 20
+/// and not one of the printed values 100 and 1000000
+fn number_paths(n: usize) -> Option<&'static [&'static str]> {
+    let paths = match n {
+        0 => paths::NUMBERS_0,
+        1 => paths::NUMBERS_1,
+        2 => paths::NUMBERS_2,
+        3 => paths::NUMBERS_3,
+        4 => paths::NUMBERS_4,
+        5 => paths::NUMBERS_5,
+        6 => paths::NUMBERS_6,
+        7 => paths::NUMBERS_7,
+        8 => paths::NUMBERS_8,
+        9 => paths::NUMBERS_9,
+        10 => paths::NUMBERS_10,
+        11 => paths::NUMBERS_11,
+        12 => paths::NUMBERS_12,
+        13 => paths::NUMBERS_13,
+        14 => paths::NUMBERS_14,
+        15 => paths::NUMBERS_15,
+        16 => paths::NUMBERS_16,
+        17 => paths::NUMBERS_17,
+        18 => paths::NUMBERS_18,
+        19 => paths::NUMBERS_19,
+        20 => paths::NUMBERS_20,
+        100 => paths::NUMBERS_100,
+        1_000_000 => paths::NUMBERS_1000000,
+        _ => return None,
     };
-    parse_add(content, document())
+    Some(paths)
 }
 
-/// Returns `None` if `n` is larger than 20
-pub fn number_symbol(n: usize) -> Option<SVG> {
-    let content = match n {
-        0 => include_symbol!("numbers/0.svg"),
-        1 => include_symbol!("numbers/1.svg"),
-        2 => include_symbol!("numbers/2.svg"),
-        3 => include_symbol!("numbers/3.svg"),
-        4 => include_symbol!("numbers/4.svg"),
-        5 => include_symbol!("numbers/5.svg"),
-        6 => include_symbol!("numbers/6.svg"),
-        7 => include_symbol!("numbers/7.svg"),
-        8 => include_symbol!("numbers/8.svg"),
-        9 => include_symbol!("numbers/9.svg"),
-        10 => include_symbol!("numbers/10.svg"),
-        11 => include_symbol!("numbers/11.svg"),
-        12 => include_symbol!("numbers/12.svg"),
-        13 => include_symbol!("numbers/13.svg"),
-        14 => include_symbol!("numbers/14.svg"),
-        15 => include_symbol!("numbers/15.svg"),
-        16 => include_symbol!("numbers/16.svg"),
-        17 => include_symbol!("numbers/17.svg"),
-        18 => include_symbol!("numbers/18.svg"),
-        19 => include_symbol!("numbers/19.svg"),
-        20 => include_symbol!("numbers/20.svg"),
-        _ => return None,
-    };
-    Some(parse_add(content, document()))
+/// Other numbers larger than 20 have no artwork, so we compose them from the
+/// artwork of their digits. Like in the artwork of 10 to 20, each digit is squeezed to
+/// half its width, and they are shrunk further if they would not fit.
+fn composed_number_symbol(n: usize) -> SVG {
+    let digits = n.to_string();
+    let width = SVG_WIDTH / digits.len() as f64;
+    let height = f64::min(SVG_WIDTH, 2.0 * width);
+    let mut svg = document();
+    for (i, digit) in digits.bytes().enumerate() {
+        let paths = number_paths(usize::from(digit - b'0')).unwrap();
+        let digit = paths_symbol(paths)
+            .set("x", width * i as f64)
+            .set("y", (SVG_WIDTH - height) / 2.0)
+            .set("width", width)
+            .set("height", height)
+            .set("preserveAspectRatio", "none");
+        svg = svg.add(digit);
+    }
+    svg
 }
 
-pub fn x_symbol() -> SVG {
-    parse_add(include_symbol!("x.svg"), document())
+/// The outline of [`composed_number_symbol`].
+fn composed_number_segments(n: usize) -> Vec<PathSegment> {
+    let digits = n.to_string();
+    let width = SVG_WIDTH / digits.len() as f64;
+    let height = f64::min(SVG_WIDTH, 2.0 * width);
+    let mut segments = Vec::new();
+    for (i, digit) in digits.bytes().enumerate() {
+        let x = width * i as f64;
+        let y = (SVG_WIDTH - height) / 2.0;
+        let place = |px, py| (px * width / SVG_WIDTH + x, py * height / SVG_WIDTH + y);
+        for data in number_paths(usize::from(digit - b'0')).unwrap() {
+            segments.extend(parse_path(data).into_iter().map(|segment| segment.map(place)));
+        }
+    }
+    segments
 }
 
-pub fn y_symbol() -> SVG {
-    parse_add(include_symbol!("y.svg"), document())
+/// Custom colors have no artwork, so we write their letter instead
+fn letter_symbol(letter: char) -> SVG {
+    let text = Text::new(letter.to_string())
+        .set("x", SVG_WIDTH / 2.0)
+        .set("y", SVG_WIDTH / 2.0)
+        .set("font-family", "sans-serif")
+        .set("font-weight", "bold")
+        .set("font-size", SVG_WIDTH * 0.9)
+        .set("text-anchor", "middle")
+        .set("dominant-baseline", "central");
+    document().add(text)
 }
 
-pub fn z_symbol() -> SVG {
-    parse_add(include_symbol!("z.svg"), document())
+/// Parse artwork which was not included when compiling. Only the `viewBox` and
+/// the paths (with their fills) are kept.
+pub fn parse_artwork(content: &str) -> Option<SVG> {
+    let mut svg = document();
+    for event in svg::read(content).ok()? {
+        match event {
+            Event::Tag("svg", Type::Start, attributes) => {
+                if let Some(view_box) = attributes.get("viewBox") {
+                    svg = svg.set("viewBox", view_box.clone());
+                }
+            }
+            Event::Tag("path", Type::Empty | Type::Start, attributes) => {
+                let data = Data::parse(attributes.get("d")?).ok()?;
+                let mut path = Path::new().set("d", data);
+                if let Some(fill) = attributes.get("fill") {
+                    path = path.set("fill", fill.clone());
+                }
+                svg = svg.add(path);
+            }
+            Event::Error(_) => return None,
+            _ => {}
+        }
+    }
+    Some(svg)
 }
 
-fn parse_add(content: &str, mut svg: SVG) -> SVG {
-    for path in get_paths(content) {
-        svg = svg.add(path);
+fn paths_symbol(paths: &[&str]) -> SVG {
+    let mut svg = document();
+    for data in paths {
+        svg = svg.add(Path::new().set("d", *data));
     }
 
     svg
 }
-
-fn get_paths(content: &str) -> impl Iterator<Item = Path> {
-    svg::read(content).unwrap().filter_map(|event| {
-        if let Event::Tag("path", Type::Empty | Type::Start, attributes) = event {
-            let data = attributes.get("d").unwrap();
-            let data = Data::parse(data).unwrap();
-            let path = Path::new().set("d", data);
-            Some(path)
-        } else {
-            None
-        }
-    })
-}
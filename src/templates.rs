@@ -0,0 +1,194 @@
+//! Helpers for template engines, which show mana costs from template data as
+//! HTML or SVG, using the default options of [`Manas::as_html`] and
+//! [`Manas::as_svg`].
+
+use crate::{Manas, SVGConfig, canonical_svg};
+
+/// How a cost is shown by a helper.
+#[derive(Clone, Copy)]
+enum Output {
+    Html,
+    Svg,
+}
+
+impl Output {
+    #[cfg(any(feature = "tera", feature = "handlebars"))]
+    const ALL: [(&'static str, Self); 2] = [("mana_html", Self::Html), ("mana_svg", Self::Svg)];
+
+    /// Returns `None` if `cost` is not a valid mana cost.
+    fn render(self, cost: &str) -> Option<String> {
+        let manas: Manas = cost.parse().ok()?;
+        let config = SVGConfig::default();
+        Some(match self {
+            Self::Html => manas.as_html(true, &config),
+            Self::Svg => canonical_svg(&manas.as_svg(&config)),
+        })
+    }
+}
+
+#[cfg(feature = "tera")]
+impl tera::Filter for Output {
+    fn filter(
+        &self,
+        value: &tera::Value,
+        _args: &std::collections::HashMap<String, tera::Value>,
+    ) -> tera::Result<tera::Value> {
+        let cost = value.as_str().ok_or_else(|| tera::Error::msg("mana cost must be a string"))?;
+        let out = self
+            .render(cost)
+            .ok_or_else(|| tera::Error::msg(format!("invalid mana cost: {cost}")))?;
+        Ok(tera::Value::String(out))
+    }
+
+    fn is_safe(&self) -> bool {
+        true
+    }
+}
+
+/// Register the filters `mana_html` and `mana_svg` with `tera`, which show a
+/// mana cost (e.g. `{2}{U}`) as HTML or as an SVG.
+///
+/// ```
+/// let mut tera = tera::Tera::default();
+/// mana_symbols::register_tera_filters(&mut tera);
+///
+/// let mut context = tera::Context::new();
+/// context.insert("cost", "{2}{U}");
+/// let html = tera.render_str("{{ cost | mana_html }}", &context).unwrap();
+/// assert!(html.starts_with(r#"<span class="mana_symbols">"#));
+/// ```
+#[cfg(feature = "tera")]
+pub fn register_tera_filters(tera: &mut tera::Tera) {
+    for (name, output) in Output::ALL {
+        tera.register_filter(name, output);
+    }
+}
+
+#[cfg(feature = "handlebars")]
+impl handlebars::HelperDef for Output {
+    fn call<'reg: 'rc, 'rc>(
+        &self,
+        h: &handlebars::Helper<'rc>,
+        _: &'reg handlebars::Handlebars<'reg>,
+        _: &'rc handlebars::Context,
+        _: &mut handlebars::RenderContext<'reg, 'rc>,
+        out: &mut dyn handlebars::Output,
+    ) -> handlebars::HelperResult {
+        use handlebars::RenderErrorReason;
+
+        let name = h.name();
+        let param =
+            h.param(0).ok_or_else(|| RenderErrorReason::ParamNotFoundForIndex("mana", 0))?;
+        let cost = param
+            .value()
+            .as_str()
+            .ok_or_else(|| RenderErrorReason::InvalidParamType("mana cost must be a string"))?;
+        let rendered = self.render(cost).ok_or_else(|| {
+            RenderErrorReason::Other(format!("{name}: invalid mana cost: {cost}"))
+        })?;
+        out.write(&rendered)?;
+        Ok(())
+    }
+}
+
+/// Register the helpers `mana_html` and `mana_svg` with `handlebars`, which
+/// show a mana cost (e.g. `{2}{U}`) as HTML or as an SVG.
+///
+/// ```
+/// let mut handlebars = handlebars::Handlebars::new();
+/// mana_symbols::register_handlebars_helpers(&mut handlebars);
+///
+/// let data = std::collections::HashMap::from([("cost", "{2}{U}")]);
+/// let html = handlebars.render_template("{{mana_html cost}}", &data).unwrap();
+/// assert!(html.starts_with(r#"<span class="mana_symbols">"#));
+/// ```
+#[cfg(feature = "handlebars")]
+pub fn register_handlebars_helpers(handlebars: &mut handlebars::Handlebars<'_>) {
+    for (name, output) in Output::ALL {
+        handlebars.register_helper(name, Box::new(output));
+    }
+}
+
+/// Filters `mana_html` and `mana_svg` for [Askama](https://askama.rs)
+/// templates, which show a mana cost (e.g. `{2}{U}` or a [`Manas`]) as HTML or
+/// as an SVG. Askama looks for filters in a module called `filters`, so
+/// re-export them there.
+///
+/// ```
+/// mod filters {
+///     pub use mana_symbols::askama_filters::*;
+/// }
+///
+/// #[derive(askama::Template)]
+/// #[template(source = "<p>{{ cost|mana_html }}</p>", ext = "html")]
+/// struct Card<'a> {
+///     cost: &'a str,
+/// }
+///
+/// let html = askama::Template::render(&Card { cost: "{2}{U}" }).unwrap();
+/// assert!(html.starts_with(r#"<p><span class="mana_symbols">"#));
+/// ```
+#[cfg(feature = "askama")]
+pub mod askama_filters {
+    use std::fmt::Display;
+
+    use askama::{Result, Values, filters::Safe};
+
+    use super::Output;
+
+    /// Show a mana cost as HTML.
+    #[askama::filter_fn]
+    pub fn mana_html<T: Display>(cost: T, _: &dyn Values) -> Result<Safe<String>> {
+        render(Output::Html, &cost)
+    }
+
+    /// Show a mana cost as an SVG.
+    #[askama::filter_fn]
+    pub fn mana_svg<T: Display>(cost: T, _: &dyn Values) -> Result<Safe<String>> {
+        render(Output::Svg, &cost)
+    }
+
+    fn render(output: Output, cost: &dyn Display) -> Result<Safe<String>> {
+        let cost = cost.to_string();
+        let rendered = output.render(&cost);
+        rendered
+            .map(Safe)
+            .ok_or_else(|| askama::Error::custom(format!("invalid mana cost: {cost}")))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use askama::Template;
+
+        use crate::{Manas, SVGConfig, canonical_svg};
+
+        mod filters {
+            pub use super::super::*;
+        }
+
+        #[derive(Template)]
+        #[template(source = "<p>{{ cost|mana_html }}</p><p>{{ cost|mana_svg }}</p>", ext = "html")]
+        struct Card<'a> {
+            cost: &'a str,
+        }
+
+        #[derive(Template)]
+        #[template(source = "{{ cost|mana_html }}", ext = "html")]
+        struct Cost {
+            cost: Manas,
+        }
+
+        #[test]
+        fn filters() {
+            let manas: Manas = "{2}{U}".parse().unwrap();
+            let config = SVGConfig::default();
+            let html = manas.as_html(true, &config);
+            let svg = canonical_svg(&manas.as_svg(&config));
+
+            let card = Card { cost: "{2}{U}" }.render().unwrap();
+            assert_eq!(card, format!("<p>{html}</p><p>{svg}</p>"));
+            assert_eq!(Cost { cost: manas }.render().unwrap(), html);
+            assert!(Card { cost: "{2}{" }.render().is_err());
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use svg::node::element::SVG;
+
+use crate::{Color, CustomColor, Palette};
+
+/// The look of mana symbols drawn as SVGs.
+///
+/// Used by [`Mana::as_svg_with_theme`][crate::Mana::as_svg_with_theme] and
+/// [`Manas::as_svg_with_theme`][crate::Manas::as_svg_with_theme]. The default
+/// look is given by [`Palette`], which only decides the fills of the circles.
+///
+/// # Example
+///
+/// ```
+/// use mana_symbols::{Fill, Glyph, Mana, SVGConfig, Theme};
+///
+/// /// Gray circles with white glyphs.
+/// struct Minimal;
+///
+/// impl Theme for Minimal {
+///     fn circle_fill(&self, _fill: Fill) -> String {
+///         "#444444".to_string()
+///     }
+///
+///     fn glyph_fill(&self, _glyph: Glyph) -> Option<String> {
+///         Some("white".to_string())
+///     }
+/// }
+///
+/// let mana: Mana = "U".parse().unwrap();
+/// let svg = mana.as_svg_with_theme(&SVGConfig::default(), &Minimal).to_string();
+/// assert!(svg.contains(r##"fill="#444444""##));
+/// ```
+pub trait Theme {
+    /// The fill of a circle, or of half a circle for hybrid mana.
+    fn circle_fill(&self, fill: Fill) -> String;
+
+    /// The fill of a glyph drawn on top of a circle. If `None`, the glyph keeps
+    /// the fill of its artwork, which is black for most glyphs.
+    fn glyph_fill(&self, _glyph: Glyph) -> Option<String> {
+        None
+    }
+
+    /// The color and width of an outline around each circle, if any.
+    fn stroke(&self) -> Option<(String, f64)> {
+        None
+    }
+
+    /// A gradient drawn on top of each circle and its glyphs, if any.
+    fn foil(&self) -> Option<Foil> {
+        None
+    }
+
+    /// A plate drawn behind a row of mana symbols, if any. Only used by
+    /// [`Manas::as_svg_with_theme`][crate::Manas::as_svg_with_theme].
+    fn plate(&self) -> Option<Plate> {
+        None
+    }
+
+    /// Artwork to draw instead of the built-in artwork of `glyph`, using a
+    /// `viewBox` of `0 0 32 32`. If `None`, the built-in artwork is used.
+    fn glyph(&self, _glyph: Glyph) -> Option<SVG> {
+        None
+    }
+}
+
+/// What a circle (or half of a circle) is filled by, used by [`Theme`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fill {
+    /// Mana of one of the five colors.
+    Color(Color),
+
+    /// Generic and colorless mana, and symbols which are not mana (e.g. the
+    /// tap symbol).
+    Colorless,
+
+    /// Mana of a custom color.
+    Custom(CustomColor),
+}
+
+/// A glyph drawn on top of a circle, used by [`Theme`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Glyph {
+    /// The symbol of a color, e.g. the drop of blue mana.
+    Color(Color),
+
+    /// The symbol of colorless mana.
+    Colorless,
+
+    /// The symbol of phyrexian mana.
+    Phyrexian,
+
+    /// The symbol of snow mana.
+    Snow,
+
+    /// The tap symbol.
+    Tap,
+
+    /// The untap symbol.
+    Untap,
+
+    /// The energy symbol.
+    Energy,
+
+    /// The ticket symbol.
+    Ticket,
+
+    /// The chaos symbol.
+    Chaos,
+
+    /// A number of generic mana.
+    Number(usize),
+
+    /// The infinity symbol of `{∞}`.
+    Infinity,
+
+    /// A variable amount of generic mana, `'X'`, `'Y'` or `'Z'`.
+    Variable(char),
+
+    /// The letter of a custom color.
+    Custom(CustomColor),
+}
+
+impl Theme for Palette {
+    fn circle_fill(&self, fill: Fill) -> String {
+        match fill {
+            Fill::Color(color) => self.color(color),
+            Fill::Colorless => &self.colorless,
+            Fill::Custom(color) => self.custom_color(color),
+        }
+        .to_string()
+    }
+
+    fn stroke(&self) -> Option<(String, f64)> {
+        self.outline.clone().map(|x| (x, 1.0))
+    }
+
+    fn foil(&self) -> Option<Foil> {
+        self.foil.clone()
+    }
+
+    fn plate(&self) -> Option<Plate> {
+        self.plate.clone()
+    }
+}
+
+/// A diagonal gradient drawn on top of mana symbols, which makes them look like
+/// foil cards. Used by [`Theme::foil`] and [`Palette::foil`].
+///
+/// The id of the gradient is `foil-` followed by a hash of its colors, so SVGs
+/// with different foils can be inlined in the same HTML page.
+///
+/// ```
+/// use mana_symbols::{Foil, Mana, Palette, SVGConfig};
+///
+/// let palette = Palette { foil: Some(Foil::rainbow()), ..Default::default() };
+/// let config = SVGConfig { palette, ..Default::default() };
+///
+/// let mana: Mana = "U".parse().unwrap();
+/// let svg = mana.as_svg(&config).to_string();
+/// assert!(svg.contains("url(#foil-"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Foil {
+    /// The colors of the gradient, from the top left to the bottom right,
+    /// which should usually be translucent (e.g. `#ffffff80`).
+    pub colors: Vec<String>,
+}
+
+impl Foil {
+    /// A translucent rainbow sheen.
+    #[must_use]
+    pub fn rainbow() -> Self {
+        let colors = ["#ffffff00", "#ff80c060", "#ffffffa0", "#80c0ff60", "#80ffa040", "#ffffff00"];
+        Self { colors: colors.map(str::to_string).to_vec() }
+    }
+}
+
+/// A rounded rectangle drawn behind a row of mana symbols, like the costs shown
+/// by MTG Arena. Its corners are rounded to match the circles at either end,
+/// and it covers the margin around the symbols, including the space between
+/// them. Used by [`Theme::plate`] and [`Palette::plate`].
+///
+/// ```
+/// use mana_symbols::{Manas, Palette, Plate, SVGConfig};
+///
+/// let palette = Palette { plate: Some(Plate::arena()), ..Default::default() };
+/// let config = SVGConfig { palette, spacing: 2.0, ..Default::default() };
+///
+/// let manas: Manas = "{2}{U}{U}".parse().unwrap();
+/// let svg = manas.as_svg(&config).to_string();
+/// assert!(svg.contains("<rect"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plate {
+    /// The fill of the plate.
+    pub fill: String,
+}
+
+impl Plate {
+    /// A dark gray plate.
+    #[must_use]
+    pub fn arena() -> Self {
+        Self { fill: "#262626".to_string() }
+    }
+}
+
+/// A [`Theme`] where glyphs are drawn using `currentColor`, so that they can be
+/// tinted using CSS, e.g. for icons. Circles are not filled, but may be
+/// outlined. The built-in artwork of snow mana keeps its own colors.
+///
+/// The shadow is not affected by the theme, so it should usually be disabled
+/// using [`SVGConfig::shadow`][crate::SVGConfig::shadow].
+///
+/// ```
+/// use mana_symbols::{Mana, Monochrome, SVGConfig};
+///
+/// let mana: Mana = "U".parse().unwrap();
+/// let config = SVGConfig { shadow: false, ..Default::default() };
+/// let svg = mana.as_svg_with_theme(&config, &Monochrome { outlined: true }).to_string();
+/// assert!(svg.contains(r#"fill="currentColor""#));
+/// assert!(svg.contains(r#"stroke="currentColor""#));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Monochrome {
+    /// Whether to draw an outline around each circle.
+    pub outlined: bool,
+}
+
+impl Theme for Monochrome {
+    fn circle_fill(&self, _fill: Fill) -> String {
+        "none".to_string()
+    }
+
+    fn glyph_fill(&self, _glyph: Glyph) -> Option<String> {
+        Some("currentColor".to_string())
+    }
+
+    fn stroke(&self) -> Option<(String, f64)> {
+        self.outlined.then(|| ("currentColor".to_string(), 1.0))
+    }
+}
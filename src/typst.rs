@@ -0,0 +1,22 @@
+use std::fmt::{self, Write};
+
+use crate::{Mana, SVGConfig, canonical_svg};
+
+/// Write `mana` as Typst markup which embeds its SVG as an image, one line of
+/// text high.
+pub(crate) fn write_typst<W: Write>(
+    output: &mut W,
+    mana: &Mana,
+    config: &SVGConfig,
+) -> fmt::Result {
+    write!(output, r#"#box(baseline: 15%, image(bytes(""#)?;
+    for c in canonical_svg(&mana.as_svg(config)).chars() {
+        match c {
+            '\\' => output.write_str(r"\\")?,
+            '"' => output.write_str(r#"\""#)?,
+            '\n' => output.write_str(r"\n")?,
+            c => output.write_char(c)?,
+        }
+    }
+    write!(output, r#""), format: "svg", height: 1em))"#)
+}
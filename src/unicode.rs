@@ -0,0 +1,104 @@
+use std::fmt::{self, Write};
+
+use crate::{GenericMana, Mana, SingleMana, SplitMana};
+
+/// The symbol used for phyrexian mana, the Greek letter phi.
+const PHYREXIAN: char = 'Φ';
+
+/// The symbol used for snow mana.
+const SNOW: char = '❄';
+
+/// Write `mana` using circled letters and numbers, e.g. `Ⓤ` for `{U}`. Halves
+/// of hybrid mana and phyrexian mana are separated by `/`, like in the text
+/// representation.
+pub(crate) fn write_unicode<W: Write>(output: &mut W, mana: &Mana) -> fmt::Result {
+    match mana {
+        Mana::Single(SingleMana::Normal(color)) => write_letter(output, color.char()),
+        Mana::Single(SingleMana::Phyrexian(color)) => {
+            write_letter(output, color.char())?;
+            write!(output, "/{PHYREXIAN}")
+        }
+        Mana::Single(SingleMana::Half(color)) => {
+            output.write_char('½')?;
+            write_letter(output, color.char())
+        }
+        Mana::Generic(GenericMana::Number(n)) => write_number(output, *n),
+        Mana::Generic(GenericMana::X) => write_letter(output, 'X'),
+        Mana::Generic(GenericMana::Y) => write_letter(output, 'Y'),
+        Mana::Generic(GenericMana::Z) => write_letter(output, 'Z'),
+        Mana::Generic(GenericMana::Infinity) => output.write_char('∞'),
+        Mana::Split(SplitMana::Colorless { color }) => {
+            write_letter(output, 'C')?;
+            output.write_char('/')?;
+            write_letter(output, color.char())
+        }
+        Mana::Split(SplitMana::Mono { value, color }) => {
+            write_number(output, *value)?;
+            output.write_char('/')?;
+            write_letter(output, color.char())
+        }
+        Mana::Split(SplitMana::Duo { a, b, phyrexian }) => {
+            write_letter(output, a.char())?;
+            output.write_char('/')?;
+            write_letter(output, b.char())?;
+            if *phyrexian {
+                write!(output, "/{PHYREXIAN}")?;
+            }
+            Ok(())
+        }
+        Mana::Colorless => write_letter(output, 'C'),
+        Mana::Snow => output.write_char(SNOW),
+        Mana::Custom(color) => write_letter(output, color.letter()),
+    }
+}
+
+/// Write `letter` as a circled letter, e.g. `Ⓦ`.
+fn write_letter<W: Write>(output: &mut W, letter: char) -> fmt::Result {
+    let circled = letter
+        .is_ascii_uppercase()
+        .then(|| char::from_u32(u32::from('Ⓐ') + (u32::from(letter) - u32::from('A'))))
+        .flatten();
+    match circled {
+        Some(circled) => output.write_char(circled),
+        None => write!(output, "({letter})"),
+    }
+}
+
+/// Write `n` as a circled number. Unicode only has circled numbers up to 50,
+/// so larger numbers are written in parentheses, e.g. `(100)`.
+fn write_number<W: Write>(output: &mut W, n: usize) -> fmt::Result {
+    let circled = match n {
+        0 => Some('⓪'),
+        1..=20 => char::from_u32(u32::from('①') + n as u32 - 1),
+        21..=35 => char::from_u32(u32::from('㉑') + n as u32 - 21),
+        36..=50 => char::from_u32(u32::from('㊱') + n as u32 - 36),
+        _ => None,
+    };
+    match circled {
+        Some(circled) => output.write_char(circled),
+        None => write!(output, "({n})"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unicode(n: usize) -> String {
+        let mut out = String::new();
+        write_number(&mut out, n).unwrap();
+        out
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(unicode(0), "⓪");
+        assert_eq!(unicode(1), "①");
+        assert_eq!(unicode(20), "⑳");
+        assert_eq!(unicode(21), "㉑");
+        assert_eq!(unicode(35), "㉟");
+        assert_eq!(unicode(36), "㊱");
+        assert_eq!(unicode(50), "㊿");
+        assert_eq!(unicode(51), "(51)");
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt::Display;
+
+use crate::{Color, Mana};
+
+/// A reason why mana symbols are not structurally valid.
+///
+/// Returned by [`Mana::validate`] and [`Manas::validate`][crate::Manas::validate].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// A hybrid mana symbol has the same color on both halves, e.g. `{W/W}`.
+    DuplicateHybridColor(Color),
+
+    /// The halves of a hybrid mana symbol are not in their normalized order,
+    /// e.g. `{U/W}`. Contains the normalized mana symbol, e.g. `{W/U}` (see
+    /// [`Mana::normalize_hybrid`]).
+    NonCanonicalHybrid(Mana),
+
+    /// A hybrid generic mana symbol has no generic mana, e.g. `{0/W}`.
+    ZeroGenericHybrid(Color),
+
+    /// `{0}` is used together with other mana symbols.
+    RedundantZero,
+
+    /// Generic mana is split into several mana symbols, e.g. `{2}{1}`.
+    SplitGeneric,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicateHybridColor(color) => {
+                write!(f, "hybrid mana with {} on both halves", color.name())
+            }
+            Self::NonCanonicalHybrid(mana) => {
+                write!(f, "hybrid mana should be written as {{{mana}}}")
+            }
+            Self::ZeroGenericHybrid(color) => {
+                write!(f, "hybrid mana of zero generic or {}", color.name())
+            }
+            Self::RedundantZero => f.write_str("{0} together with other mana"),
+            Self::SplitGeneric => f.write_str("generic mana split into several symbols"),
+        }
+    }
+}
+
+impl std::error::Error for Violation {}
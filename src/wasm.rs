@@ -0,0 +1,70 @@
+//! Bindings for JavaScript, using [`wasm_bindgen`].
+
+use wasm_bindgen::prelude::*;
+
+use crate::Manas;
+
+/// A mana cost, exported to JavaScript as the class `Manas`.
+///
+/// ```js
+/// const manas = new Manas("{U}{2}{U}");
+/// manas.sort();
+/// manas.toString(); // "{2}{U}{U}"
+/// manas.manaValue(); // 4
+/// ```
+#[wasm_bindgen(js_name = Manas)]
+pub struct JsManas {
+    manas: Manas,
+}
+
+#[wasm_bindgen(js_class = Manas)]
+impl JsManas {
+    /// Parse a mana cost, e.g. `{2}{U}{U}`.
+    ///
+    /// # Errors
+    ///
+    /// Throws an error if `cost` is not a valid mana cost.
+    #[wasm_bindgen(constructor)]
+    pub fn new(cost: &str) -> Result<Self, JsError> {
+        let manas =
+            cost.parse().map_err(|()| JsError::new(&format!("invalid mana cost: {cost}")))?;
+        Ok(Self { manas })
+    }
+
+    /// The [mana value](Manas::mana_value).
+    #[wasm_bindgen(js_name = manaValue)]
+    #[must_use]
+    pub fn mana_value(&self) -> usize {
+        self.manas.mana_value()
+    }
+
+    /// Sort the mana symbols, see [`Manas::sort`].
+    pub fn sort(&mut self) {
+        self.manas.sort();
+    }
+
+    /// The mana cost as text, e.g. `{2}{U}{U}`.
+    #[wasm_bindgen(js_name = toString)]
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        self.manas.to_string()
+    }
+
+    /// The mana symbols as an SVG, using the default [`SVGConfig`](crate::SVGConfig).
+    #[cfg(feature = "svg")]
+    #[wasm_bindgen(js_name = toSvg)]
+    #[must_use]
+    pub fn to_svg(&self) -> String {
+        let mut out = String::new();
+        self.manas.render_svg_to(&mut out, &crate::SVGConfig::default());
+        out
+    }
+
+    /// The mana symbols as HTML, see [`Manas::as_html`].
+    #[cfg(feature = "svg")]
+    #[wasm_bindgen(js_name = toHtml)]
+    #[must_use]
+    pub fn to_html(&self, include_css: bool) -> String {
+        self.manas.as_html(include_css, &crate::SVGConfig::default())
+    }
+}
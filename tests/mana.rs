@@ -1,4 +1,8 @@
-use mana_symbols::{Mana, Manas, SVGConfig};
+#![cfg(feature = "svg")]
+
+use mana_symbols::{
+    DropShadow, Fill, Glyph, Mana, Manas, Palette, SVGConfig, Stroke, Symbol, Theme, canonical_svg,
+};
 use svg::node::element::SVG;
 
 fn compare_snapshot(name: &str, svg: SVG) {
@@ -13,10 +17,7 @@ fn compare_snapshot(name: &str, svg: SVG) {
     settings.set_snapshot_path(path);
 
     settings.bind(|| {
-        insta::assert_binary_snapshot!(
-            name,
-            svg.to_string().as_bytes().into_iter().cloned().collect()
-        );
+        insta::assert_binary_snapshot!(name, canonical_svg(&svg).into_bytes());
     });
 }
 
@@ -29,13 +30,26 @@ pub fn test_render(name: &str, symbol: &str) {
 
 pub fn test_render_no_shadow(name: &str, symbol: &str) {
     let m: Mana = symbol.parse().unwrap();
-    let mut config = SVGConfig::default();
-    config.shadow = false;
+    let config = SVGConfig { shadow: false, ..Default::default() };
 
     let svg = m.as_svg(&config);
     compare_snapshot(name, svg);
 }
 
+pub fn test_render_config(name: &str, symbol: &str, config: &SVGConfig) {
+    let m: Mana = symbol.parse().unwrap();
+
+    let svg = m.as_svg(config);
+    compare_snapshot(name, svg);
+}
+
+pub fn test_render_symbol(name: &str, symbol: &str) {
+    let s: Symbol = symbol.parse().unwrap();
+
+    let svg = s.as_svg(&SVGConfig::default());
+    compare_snapshot(name, svg);
+}
+
 pub fn test_render_manas(name: &str, symbol: &str) {
     let m: Manas = symbol.parse().unwrap();
 
@@ -78,6 +92,31 @@ fn snow() {
     test_render("s.svg", "S");
 }
 
+#[test]
+fn tap() {
+    test_render_symbol("t.svg", "T");
+}
+
+#[test]
+fn untap() {
+    test_render_symbol("q.svg", "Q");
+}
+
+#[test]
+fn energy() {
+    test_render_symbol("e.svg", "E");
+}
+
+#[test]
+fn infinity() {
+    test_render("infinity.svg", "∞");
+}
+
+#[test]
+fn ticket() {
+    test_render_symbol("tk.svg", "TK");
+}
+
 #[test]
 fn zero() {
     test_render("0.svg", "0");
@@ -93,6 +132,26 @@ fn twenty() {
     test_render("20.svg", "20");
 }
 
+#[test]
+fn twenty_five() {
+    test_render("25.svg", "25");
+}
+
+#[test]
+fn hundred() {
+    test_render("100.svg", "100");
+}
+
+#[test]
+fn million() {
+    test_render("1000000.svg", "1000000");
+}
+
+#[test]
+fn large_number() {
+    test_render("1234.svg", "1234");
+}
+
 #[test]
 fn generic_hybrid() {
     test_render("two_g.svg", "2/G");
@@ -115,3 +174,64 @@ fn numbers() {
         "{0}{1}{2}{3}{4}{5}{6}{7}{8}{9}{10}{11}{12}{13}{14}{15}{16}{17}{18}{19}{20}",
     );
 }
+
+#[test]
+fn hybrid_palette() {
+    let palette =
+        Palette { white: "#ffffff".to_string(), blue: "#0000ff".to_string(), ..Default::default() };
+    let config = SVGConfig { palette, ..Default::default() };
+    test_render_config("w_u_palette.svg", "W/U", &config);
+}
+
+#[test]
+fn high_contrast_palette() {
+    let config = SVGConfig { palette: Palette::high_contrast(), ..Default::default() };
+    test_render_config("g_w_high_contrast.svg", "G/W", &config);
+}
+
+#[test]
+fn strokes() {
+    let config = SVGConfig {
+        circle_stroke: Some(Stroke { color: "#333333".to_string(), width: 2.0 }),
+        glyph_stroke: Some(Stroke { color: "black".to_string(), width: 0.5 }),
+        ..Default::default()
+    };
+    test_render_config("w_stroke.svg", "W", &config);
+}
+
+#[test]
+fn drop_shadow() {
+    let config = SVGConfig { drop_shadow: Some(DropShadow::default()), ..Default::default() };
+    test_render_config("u_drop_shadow.svg", "U", &config);
+}
+
+#[test]
+fn dark() {
+    test_render_config("w_dark.svg", "W", &SVGConfig::dark());
+}
+
+struct Inverted;
+
+impl Theme for Inverted {
+    fn circle_fill(&self, _fill: Fill) -> String {
+        "black".to_string()
+    }
+
+    fn glyph_fill(&self, glyph: Glyph) -> Option<String> {
+        Some(Palette::default().circle_fill(Fill::Color(match glyph {
+            Glyph::Color(color) => color,
+            _ => return Some("white".to_string()),
+        })))
+    }
+
+    fn stroke(&self) -> Option<(String, f64)> {
+        Some(("white".to_string(), 0.5))
+    }
+}
+
+#[test]
+fn theme() {
+    let m: Manas = "{2}{U}{B/P}".parse().unwrap();
+    let svg = m.as_svg_with_theme(&SVGConfig::default(), &Inverted);
+    compare_snapshot("inverted_theme.svg", svg);
+}